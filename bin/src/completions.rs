@@ -0,0 +1,50 @@
+use crate::config::Opts;
+
+use clap::{App, IntoApp};
+use clap_complete::Shell;
+use lib::LINTS;
+
+const BIN: &str = "statix";
+
+// `statix explain` takes lint names, which are added as possible values
+// only for completion, as it also takes warning codes
+fn app() -> App<'static> {
+    let names = LINTS.iter().map(|l| l.name());
+    Opts::into_app().mut_subcommand("explain", |explain| {
+        explain.mut_arg("target", |target| target.possible_values(names))
+    })
+}
+
+pub fn generate(shell: Shell) -> String {
+    let mut out = Vec::new();
+    clap_complete::generate(shell, &mut app(), BIN, &mut out);
+    String::from_utf8(out).expect("completions are valid utf-8")
+}
+
+pub mod main {
+    use crate::{config::Completions as CompletionsConfig, err::StatixErr};
+
+    pub fn main(config: CompletionsConfig) -> Result<(), StatixErr> {
+        print!("{}", super::generate(config.shell));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn explain_completes_lint_names() {
+        let bash = generate(Shell::Bash);
+        let explain = bash.split("statix__explain)").nth(1).unwrap();
+        assert!(explain.contains("bool_comparison"));
+    }
+
+    #[test]
+    fn completions_offers_shells() {
+        let bash = generate(Shell::Bash);
+        let completions = bash.split("statix__completions)").nth(1).unwrap();
+        assert!(completions.contains("bash elvish fish powershell zsh"));
+    }
+}
@@ -1,4 +1,5 @@
 use std::{
+    collections::BTreeMap,
     default::Default,
     fmt, fs,
     path::{Path, PathBuf},
@@ -7,8 +8,12 @@ use std::{
 
 use crate::{dirs, err::ConfigErr, utils, LintMap};
 
-use clap::Parser;
-use lib::{session::Version, LINTS};
+use clap::{ArgEnum, Parser, ValueHint};
+use clap_complete::Shell;
+use lib::{
+    session::{LintOptions, Mode, SessionInfo, Version},
+    Lint, LINTS,
+};
 use serde::{Deserialize, Serialize};
 use vfs::ReadOnlyVfs;
 
@@ -29,17 +34,23 @@ pub enum SubCommand {
     Single(Single),
     /// Print detailed explanation for a lint warning
     Explain(Explain),
-    /// Dump a sample config to stdout
+    /// Dump a sample config to stdout, or the syntax tree of a file
     Dump(Dump),
     /// List all available lints
     List(List),
+    /// Generate markdown documentation for all lints
+    Doc(Doc),
+    /// Generate shell completions
+    Completions(Completions),
+    /// Generate man pages
+    Man(Man),
 }
 
 #[derive(Parser, Debug)]
 pub struct Check {
-    /// File or directory to run check on
+    /// Files or directories to run check on
     #[clap(default_value = ".", parse(from_os_str))]
-    target: PathBuf,
+    targets: Vec<PathBuf>,
 
     /// Globs of file patterns to skip
     #[clap(short, long)]
@@ -49,37 +60,163 @@ pub struct Check {
     #[clap(short, long)]
     unrestricted: bool,
 
+    /// Walk into symlinked files and directories, skipped by default
+    #[clap(long = "follow-symlinks")]
+    follow_symlinks: bool,
+
+    /// Skip files larger than this many bytes, a K, M or G suffix
+    /// multiplies by 1024, 1024² or 1024³
+    #[clap(long = "max-file-size", parse(try_from_str = parse_file_size))]
+    max_file_size: Option<u64>,
+
     /// Output format.
-    #[cfg_attr(feature = "json", doc = "Supported values: stderr, errfmt, json")]
-    #[cfg_attr(not(feature = "json"), doc = "Supported values: stderr, errfmt")]
+    #[cfg_attr(feature = "json", doc = "Supported values: stderr, errfmt, nix, json")]
+    #[cfg_attr(not(feature = "json"), doc = "Supported values: stderr, errfmt, nix")]
     #[clap(short = 'o', long, default_value_t, parse(try_from_str))]
     pub format: OutFormat,
 
-    /// Path to statix.toml or its parent directory
-    #[clap(short = 'c', long = "config", default_value = ".")]
-    pub conf_path: PathBuf,
+    /// Path to statix.toml or its parent directory, found from each
+    /// target if omitted
+    #[clap(short = 'c', long = "config", value_hint = ValueHint::AnyPath)]
+    pub conf_path: Option<PathBuf>,
 
     /// Enable "streaming" mode, accept file on stdin, output diagnostics on stdout
     #[clap(short, long = "stdin")]
     pub streaming: bool,
+
+    /// Nix version to lint for, detected from the project's minver.nix
+    /// or the installed interpreter if omitted
+    #[clap(long = "nix-version", parse(try_from_str = parse_nix_version))]
+    pub nix_version: Option<Version>,
+
+    /// Kind of code to lint, enabling lints specific to it, one of:
+    /// plain, nixpkgs, nixos, flake. Detected per file if omitted
+    #[clap(long, parse(try_from_str = parse_mode))]
+    pub mode: Option<Mode>,
+
+    /// Also run lints that look across files, following relative imports
+    #[clap(long)]
+    pub project: bool,
+
+    /// Also lint the ```nix blocks of markdown files
+    #[clap(long)]
+    pub markdown: bool,
+
+    /// Run shellcheck over the phases of derivations and the scripts of
+    /// `writeShellScript` and friends
+    #[clap(long)]
+    pub shellcheck: bool,
+
+    /// Show findings together by file, by lint or by severity
+    #[clap(long = "group-by", arg_enum, default_value = "file")]
+    pub group_by: GroupBy,
+
+    /// Order groups by path, by severity (errors first), by lint code,
+    /// or by count (largest groups first)
+    #[clap(long, arg_enum, default_value = "path")]
+    pub sort: SortBy,
+
+    /// Show paths relative to the working directory, absolute, or
+    /// relative to the root of the repository (from-root)
+    #[clap(long = "path-style", arg_enum, default_value = "relative")]
+    pub path_style: PathStyle,
+}
+
+/// A target of `check`, with the config that applies to it
+pub struct Target {
+    pub path: PathBuf,
+    pub conf_file: ConfFile,
 }
 
 impl Check {
-    pub fn vfs(&self, extra_ignores: &[String]) -> Result<ReadOnlyVfs, ConfigErr> {
-        if self.streaming {
-            use std::io::{self, BufRead};
-            let src = io::stdin()
-                .lock()
-                .lines()
-                .map(|l| l.unwrap())
-                .collect::<Vec<String>>()
-                .join("\n");
-            Ok(ReadOnlyVfs::singleton("<stdin>", src.as_bytes()))
+    /// Targets to check, each with the config at `--config`, or else the
+    /// closest one to the target
+    pub fn targets(&self) -> Result<Vec<Target>, ConfigErr> {
+        let paths = if self.streaming {
+            vec![PathBuf::from(".")]
         } else {
-            let all_ignores = [self.ignore.as_slice(), extra_ignores].concat();
-            let ignore = dirs::build_ignore_set(&all_ignores, &self.target, self.unrestricted)?;
-            let files = dirs::walk_nix_files(ignore, &self.target)?;
-            vfs(files.collect::<Vec<_>>())
+            self.targets.clone()
+        };
+        paths
+            .into_iter()
+            .map(|path| {
+                let conf_file = match &self.conf_path {
+                    Some(conf_path) => ConfFile::discover(conf_path)?,
+                    None => ConfFile::discover(search_dir(&path))?,
+                };
+                Ok(Target { path, conf_file })
+            })
+            .collect()
+    }
+
+    /// Files of all targets, and the index of the target each one belongs
+    /// to. A file found under several targets is read once, and belongs to
+    /// the innermost of them
+    pub fn vfs(&self, targets: &[Target]) -> Result<(ReadOnlyVfs, Vec<usize>), ConfigErr> {
+        if self.streaming {
+            return Ok((stdin_vfs()?, vec![0]));
+        }
+        let depths = targets
+            .iter()
+            .map(|t| Ok(fs::canonicalize(&t.path)?.components().count()))
+            .collect::<Result<Vec<_>, ConfigErr>>()?;
+        let mut found = BTreeMap::<PathBuf, (PathBuf, usize)>::new();
+        for (idx, target) in targets.iter().enumerate() {
+            let conf_file = &target.conf_file;
+            let all_ignores = [self.ignore.as_slice(), conf_file.ignore.as_slice()].concat();
+            let ignore = dirs::build_ignore_set(&all_ignores, &target.path, self.unrestricted)?;
+            let follow_symlinks = self.follow_symlinks || conf_file.follow_symlinks;
+            let extensions: &[&str] = if self.markdown {
+                &["nix", "md"]
+            } else {
+                &["nix"]
+            };
+            for file in dirs::walk_files(ignore, &target.path, follow_symlinks, extensions)? {
+                let key = fs::canonicalize(&file).unwrap_or_else(|_| file.clone());
+                match found.get(&key) {
+                    Some((_, other)) if depths[*other] >= depths[idx] => (),
+                    _ => {
+                        found.insert(key, (file, idx));
+                    }
+                }
+            }
+        }
+        let mut vfs = ReadOnlyVfs::default();
+        let mut owners = Vec::new();
+        for (idx, target) in targets.iter().enumerate() {
+            let files = found
+                .values()
+                .filter(|(_, owner)| *owner == idx)
+                .map(|(file, _)| file.clone())
+                .collect();
+            let max_file_size = match self.max_file_size {
+                Some(size) => Some(size),
+                None => target.conf_file.max_file_size()?,
+            };
+            read_files(&mut vfs, files, max_file_size);
+            owners.resize(vfs.len(), idx);
+        }
+        Ok((vfs, owners))
+    }
+
+    pub fn session(&self, target: &Target) -> Result<SessionInfo, ConfigErr> {
+        target.conf_file.session(&target.path, self.nix_version)
+    }
+
+    /// Entry points of the project for `--project`, see `ConfFile::roots`
+    pub fn roots(&self, target: &Target) -> Vec<PathBuf> {
+        target
+            .conf_file
+            .roots()
+            .iter()
+            .map(|r| target.path.join(r))
+            .collect()
+    }
+
+    pub fn mode(&self, conf_file: &ConfFile) -> Result<Option<Mode>, ConfigErr> {
+        match self.mode {
+            Some(m) => Ok(Some(m)),
+            None => conf_file.mode(),
         }
     }
 }
@@ -98,17 +235,69 @@ pub struct Fix {
     #[clap(short, long)]
     unrestricted: bool,
 
+    /// Walk into symlinked files and directories, skipped by default
+    #[clap(long = "follow-symlinks")]
+    follow_symlinks: bool,
+
+    /// Skip files larger than this many bytes, a K, M or G suffix
+    /// multiplies by 1024, 1024² or 1024³
+    #[clap(long = "max-file-size", parse(try_from_str = parse_file_size))]
+    max_file_size: Option<u64>,
+
     /// Do not fix files in place, display a diff instead
     #[clap(short, long = "dry-run")]
     pub diff_only: bool,
 
+    /// Pipe fixed files through a formatter before writing them, one of:
+    /// alejandra, nixfmt, nixpkgs-fmt, or a command formatting stdin to stdout
+    #[clap(long = "format-with", parse(from_str))]
+    pub formatter: Option<Formatter>,
+
     /// Path to statix.toml or its parent directory
-    #[clap(short = 'c', long = "config", default_value = ".")]
+    #[clap(short = 'c', long = "config", default_value = ".", value_hint = ValueHint::AnyPath)]
     pub conf_path: PathBuf,
 
     /// Enable "streaming" mode, accept file on stdin, output diagnostics on stdout
     #[clap(short, long = "stdin")]
     pub streaming: bool,
+
+    /// Nix version to lint for, detected from the project's minver.nix
+    /// or the installed interpreter if omitted
+    #[clap(long = "nix-version", parse(try_from_str = parse_nix_version))]
+    pub nix_version: Option<Version>,
+
+    /// Kind of code to lint, enabling lints specific to it, one of:
+    /// plain, nixpkgs, nixos, flake. Detected per file if omitted
+    #[clap(long, parse(try_from_str = parse_mode))]
+    pub mode: Option<Mode>,
+}
+
+/// External formatter run over fixed files, see `fix --format-with`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Formatter {
+    pub program: String,
+    pub args: Vec<String>,
+}
+
+impl From<&str> for Formatter {
+    fn from(src: &str) -> Self {
+        let preset = |program: &str, args: &[&str]| Self {
+            program: program.to_owned(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        };
+        match src {
+            "alejandra" => preset("alejandra", &["--quiet"]),
+            "nixfmt" => preset("nixfmt", &[]),
+            "nixpkgs-fmt" => preset("nixpkgs-fmt", &[]),
+            _ => {
+                let mut words = src.split_whitespace().map(ToOwned::to_owned);
+                Self {
+                    program: words.next().unwrap_or_default(),
+                    args: words.collect(),
+                }
+            }
+        }
+    }
 }
 
 pub enum FixOut {
@@ -118,21 +307,19 @@ pub enum FixOut {
 }
 
 impl Fix {
-    pub fn vfs(&self, extra_ignores: &[String]) -> Result<ReadOnlyVfs, ConfigErr> {
+    pub fn vfs(&self, conf_file: &ConfFile) -> Result<ReadOnlyVfs, ConfigErr> {
         if self.streaming {
-            use std::io::{self, BufRead};
-            let src = io::stdin()
-                .lock()
-                .lines()
-                .map(|l| l.unwrap())
-                .collect::<Vec<String>>()
-                .join("\n");
-            Ok(ReadOnlyVfs::singleton("<stdin>", src.as_bytes()))
+            stdin_vfs()
         } else {
-            let all_ignores = [self.ignore.as_slice(), extra_ignores].concat();
+            let all_ignores = [self.ignore.as_slice(), conf_file.ignore.as_slice()].concat();
             let ignore = dirs::build_ignore_set(&all_ignores, &self.target, self.unrestricted)?;
-            let files = dirs::walk_nix_files(ignore, &self.target)?;
-            vfs(files.collect::<Vec<_>>())
+            let follow_symlinks = self.follow_symlinks || conf_file.follow_symlinks;
+            let files = dirs::walk_nix_files(ignore, &self.target, follow_symlinks)?;
+            let max_file_size = match self.max_file_size {
+                Some(size) => Some(size),
+                None => conf_file.max_file_size()?,
+            };
+            vfs(files.collect::<Vec<_>>(), max_file_size)
         }
     }
 
@@ -147,6 +334,17 @@ impl Fix {
             FixOut::Write
         }
     }
+
+    pub fn session(&self, conf_file: &ConfFile) -> Result<SessionInfo, ConfigErr> {
+        conf_file.session(&self.target, self.nix_version)
+    }
+
+    pub fn mode(&self, conf_file: &ConfFile) -> Result<Option<Mode>, ConfigErr> {
+        match self.mode {
+            Some(m) => Ok(Some(m)),
+            None => conf_file.mode(),
+        }
+    }
 }
 
 #[derive(Parser, Debug)]
@@ -168,25 +366,29 @@ pub struct Single {
     pub streaming: bool,
 
     /// Path to statix.toml or its parent directory
-    #[clap(short = 'c', long = "config", default_value = ".")]
+    #[clap(short = 'c', long = "config", default_value = ".", value_hint = ValueHint::AnyPath)]
     pub conf_path: PathBuf,
+
+    /// Nix version to lint for, detected from the project's minver.nix
+    /// or the installed interpreter if omitted
+    #[clap(long = "nix-version", parse(try_from_str = parse_nix_version))]
+    pub nix_version: Option<Version>,
+
+    /// Kind of code to lint, enabling lints specific to it, one of:
+    /// plain, nixpkgs, nixos, flake. Detected per file if omitted
+    #[clap(long, parse(try_from_str = parse_mode))]
+    pub mode: Option<Mode>,
 }
 
 impl Single {
     pub fn vfs(&self) -> Result<ReadOnlyVfs, ConfigErr> {
         if self.streaming {
-            use std::io::{self, BufRead};
-            let src = io::stdin()
-                .lock()
-                .lines()
-                .map(|l| l.unwrap())
-                .collect::<Vec<String>>()
-                .join("\n");
-            Ok(ReadOnlyVfs::singleton("<stdin>", src.as_bytes()))
+            stdin_vfs()
         } else {
-            let src = std::fs::read_to_string(self.target.as_ref().unwrap())
-                .map_err(ConfigErr::InvalidPath)?;
-            Ok(ReadOnlyVfs::singleton("<stdin>", src.as_bytes()))
+            let src = fs::read(self.target.as_ref().unwrap()).map_err(ConfigErr::InvalidPath)?;
+            let mut vfs = ReadOnlyVfs::default();
+            vfs.read_file("<stdin>", &src);
+            Ok(vfs)
         }
     }
     pub fn out(&self) -> FixOut {
@@ -198,35 +400,164 @@ impl Single {
             FixOut::Write
         }
     }
+
+    pub fn session(&self, conf_file: &ConfFile) -> Result<SessionInfo, ConfigErr> {
+        let target = self.target.as_deref().unwrap_or_else(|| Path::new("."));
+        conf_file.session(target, self.nix_version)
+    }
+
+    pub fn mode(&self, conf_file: &ConfFile) -> Result<Option<Mode>, ConfigErr> {
+        match self.mode {
+            Some(m) => Ok(Some(m)),
+            None => conf_file.mode(),
+        }
+    }
 }
 
 #[derive(Parser, Debug)]
 pub struct Explain {
-    /// Warning code to explain
-    #[clap(parse(try_from_str = parse_warning_code))]
-    pub target: u32,
+    /// Warning code or lint name to explain
+    #[clap(parse(try_from_str = parse_explain_target))]
+    pub target: ExplainTarget,
+}
+
+#[derive(Debug, Clone)]
+pub enum ExplainTarget {
+    Code(u32),
+    Name(String),
+}
+
+#[derive(Parser, Debug)]
+pub struct Dump {
+    /// File to print the syntax tree of, a sample config is printed if
+    /// neither this nor --stdin is given
+    #[clap(parse(from_os_str), value_hint = ValueHint::FilePath)]
+    pub target: Option<PathBuf>,
+
+    /// Print the syntax tree of the file on stdin
+    #[clap(short, long = "stdin")]
+    pub streaming: bool,
+
+    /// Only print the node covering this position
+    #[clap(short, long, parse(try_from_str = parse_line_col))]
+    pub position: Option<(usize, usize)>,
+}
+
+impl Dump {
+    /// The file to print the syntax tree of, if any
+    pub fn vfs(&self) -> Result<Option<ReadOnlyVfs>, ConfigErr> {
+        if self.streaming {
+            stdin_vfs().map(Some)
+        } else if let Some(target) = &self.target {
+            let src = fs::read(target).map_err(ConfigErr::InvalidPath)?;
+            let mut vfs = ReadOnlyVfs::default();
+            vfs.read_file(target, &src);
+            Ok(Some(vfs))
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct List {
+    /// Only print lint names, one per line
+    #[clap(short, long)]
+    pub names: bool,
+
+    /// Print lint metadata as JSON
+    #[cfg(feature = "json")]
+    #[clap(short, long)]
+    pub json: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct Doc {
+    /// Directory to write one markdown file per lint to,
+    /// a single document is printed to stdout if omitted
+    #[clap(short, long, parse(from_os_str), value_hint = ValueHint::DirPath)]
+    pub out_dir: Option<PathBuf>,
+
+    /// Directory of example files named after lints (`<lint>.nix`),
+    /// used to render example fixes
+    #[clap(short, long, parse(from_os_str), value_hint = ValueHint::DirPath)]
+    pub examples: Option<PathBuf>,
+
+    /// Path to statix.toml or its parent directory
+    #[clap(short = 'c', long = "config", default_value = ".", value_hint = ValueHint::AnyPath)]
+    pub conf_path: PathBuf,
 }
 
 #[derive(Parser, Debug)]
-pub struct Dump {}
+pub struct Man {
+    /// Directory to write statix.1 and one page per subcommand to,
+    /// only statix.1 is printed to stdout if omitted
+    #[clap(short, long, parse(from_os_str), value_hint = ValueHint::DirPath)]
+    pub out_dir: Option<PathBuf>,
+}
 
 #[derive(Parser, Debug)]
-pub struct List {}
+pub struct Completions {
+    /// Shell to generate completions for
+    #[clap(arg_enum)]
+    pub shell: Shell,
+}
+
+/// How `check` groups its findings, see `group::group`
+#[derive(ArgEnum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum GroupBy {
+    File,
+    Lint,
+    Severity,
+}
+
+/// How `check` orders groups of findings, see `group::group`
+#[derive(ArgEnum, Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SortBy {
+    Path,
+    Severity,
+    Code,
+    Count,
+}
+
+/// How `check` shows the paths of files, see `PathStyle::display`
+#[derive(ArgEnum, Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum PathStyle {
+    #[default]
+    Relative,
+    Absolute,
+    FromRoot,
+}
+
+impl PathStyle {
+    /// `path` in this style, or as is if it does not exist, like `<stdin>`
+    pub fn display(&self, path: &Path) -> PathBuf {
+        let absolute = match fs::canonicalize(path) {
+            Ok(p) => p,
+            Err(_) => return path.to_owned(),
+        };
+        let base = match self {
+            Self::Absolute => return absolute,
+            Self::Relative => std::env::current_dir().and_then(fs::canonicalize).ok(),
+            Self::FromRoot => utils::repository_root(&absolute)
+                .map(Path::to_owned)
+                .or_else(|| std::env::current_dir().and_then(fs::canonicalize).ok()),
+        };
+        base.map(|b| utils::relative_to(&absolute, &b))
+            .unwrap_or(absolute)
+    }
+}
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Default)]
 pub enum OutFormat {
     #[cfg(feature = "json")]
     Json,
     Errfmt,
+    Nix,
+    #[default]
     StdErr,
 }
 
-impl Default for OutFormat {
-    fn default() -> Self {
-        OutFormat::StdErr
-    }
-}
-
 impl fmt::Display for OutFormat {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -236,6 +567,7 @@ impl fmt::Display for OutFormat {
                 #[cfg(feature = "json")]
                 Self::Json => "json",
                 Self::Errfmt => "errfmt",
+                Self::Nix => "nix",
                 Self::StdErr => "stderr",
             }
         )
@@ -252,8 +584,9 @@ impl FromStr for OutFormat {
             #[cfg(not(feature = "json"))]
             "json" => Err("statix was not compiled with the `json` feature flag"),
             "errfmt" => Ok(Self::Errfmt),
+            "nix" => Ok(Self::Nix),
             "stderr" => Ok(Self::StdErr),
-            _ => Err("unknown output format, try: json, errfmt"),
+            _ => Err("unknown output format, try: json, errfmt, nix"),
         }
     }
 }
@@ -265,8 +598,57 @@ pub struct ConfFile {
 
     nix_version: Option<String>,
 
+    nixpkgs_version: Option<String>,
+
+    mode: Option<String>,
+
+    #[serde(default = "Vec::new")]
+    roots: Vec<String>,
+
+    generated: Option<Vec<String>>,
+
     #[serde(default = "Vec::new")]
     pub ignore: Vec<String>,
+
+    #[serde(default)]
+    follow_symlinks: bool,
+
+    max_file_size: Option<String>,
+
+    #[serde(default)]
+    fix: FixConf,
+
+    #[serde(default)]
+    options: BTreeMap<String, BTreeMap<String, OptionValue>>,
+}
+
+/// The `[fix]` table of statix.toml
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct FixConf {
+    /// Lints that are reported but never fixed by `statix fix`
+    #[serde(default = "Vec::new")]
+    never: Vec<String>,
+}
+
+/// The value of an option in the `[options.<lint>]` tables of statix.toml
+#[derive(Serialize, Deserialize, Debug, Clone)]
+#[serde(untagged)]
+enum OptionValue {
+    Bool(bool),
+    Number(i64),
+    String(String),
+    List(Vec<String>),
+}
+
+impl OptionValue {
+    fn values(&self) -> Vec<String> {
+        match self {
+            Self::Bool(b) => vec![b.to_string()],
+            Self::Number(n) => vec![n.to_string()],
+            Self::String(s) => vec![s.clone()],
+            Self::List(l) => l.clone(),
+        }
+    }
 }
 
 impl Default for ConfFile {
@@ -274,10 +656,26 @@ impl Default for ConfFile {
         let disabled = Default::default();
         let ignore = Default::default();
         let nix_version = Default::default();
+        let nixpkgs_version = Default::default();
+        let mode = Default::default();
+        let roots = Default::default();
+        let generated = Default::default();
+        let follow_symlinks = Default::default();
+        let max_file_size = Default::default();
+        let fix = Default::default();
+        let options = Default::default();
         Self {
             disabled,
             nix_version,
+            nixpkgs_version,
+            mode,
+            roots,
+            generated,
             ignore,
+            follow_symlinks,
+            max_file_size,
+            fix,
+            options,
         }
     }
 }
@@ -310,31 +708,125 @@ impl ConfFile {
             Self {
                 disabled,
                 nix_version,
+                nixpkgs_version: None,
+                mode: None,
+                roots: vec![],
+                generated: None,
                 ignore,
+                follow_symlinks: false,
+                max_file_size: None,
+                fix: FixConf::default(),
+                options: BTreeMap::new(),
             }
         };
         toml::ser::to_string_pretty(&ideal_config).unwrap()
     }
     pub fn lints(&self) -> LintMap {
-        utils::lint_map_of(
-            (*LINTS)
-                .iter()
-                .filter(|l| !self.disabled.iter().any(|check| check == l.name()))
-                .cloned()
-                .collect::<Vec<_>>()
-                .as_slice(),
-        )
+        utils::lint_map_of(self.enabled().as_slice())
+    }
+    /// Lints applied by `statix fix`, the enabled ones not listed in
+    /// `[fix] never`
+    pub fn fix_lints(&self) -> LintMap {
+        let lints = self
+            .enabled()
+            .into_iter()
+            .filter(|l| !self.fix.never.iter().any(|name| name == l.name()))
+            .collect::<Vec<_>>();
+        utils::lint_map_of(&lints)
+    }
+    /// Entry points of the project, relative to the checked directory
+    pub fn roots(&self) -> Vec<String> {
+        if self.roots.is_empty() {
+            vec!["flake.nix".into(), "default.nix".into(), "shell.nix".into()]
+        } else {
+            self.roots.clone()
+        }
     }
-    pub fn version(&self) -> Result<Version, ConfigErr> {
+    /// Paths that need not exist when linting, as they are produced by
+    /// builds, `result` unless set
+    pub fn generated(&self) -> Vec<String> {
+        self.generated
+            .clone()
+            .unwrap_or_else(|| vec!["result".into()])
+    }
+    /// Options of individual lints, set in `[options.<lint>]` tables
+    pub fn options(&self) -> LintOptions {
+        self.options
+            .iter()
+            .map(|(lint, options)| {
+                let options = options
+                    .iter()
+                    .map(|(name, value)| (name.clone(), value.values()))
+                    .collect();
+                (lint.clone(), options)
+            })
+            .collect()
+    }
+    /// Size in bytes above which files are skipped, if any
+    pub fn max_file_size(&self) -> Result<Option<u64>, ConfigErr> {
+        self.max_file_size
+            .as_deref()
+            .map(parse_file_size)
+            .transpose()
+    }
+    /// Lints that are not disabled
+    pub fn enabled(&self) -> Vec<&'static dyn Lint> {
+        (*LINTS)
+            .iter()
+            .filter(|l| !self.disabled.iter().any(|check| check == l.name()))
+            .cloned()
+            .collect()
+    }
+    /// Nix version to lint for, in order of preference: `nix_version` from
+    /// statix.toml, the minimum version declared by the project at `target`,
+    /// the version of the installed interpreter, and finally a default
+    pub fn version(&self, target: &Path) -> Result<Version, ConfigErr> {
         if let Some(v) = &self.nix_version {
             v.parse::<Version>()
                 .map_err(|_| ConfigErr::ConfFileVersionParse(v.clone()))
+        } else if let Some(v) =
+            utils::get_minver_hint(target).and_then(|o| o.parse::<Version>().ok())
+        {
+            Ok(v)
         } else if let Some(v) = utils::get_version_info().and_then(|o| o.parse::<Version>().ok()) {
             Ok(v)
         } else {
             Ok(utils::default_nix_version().parse::<Version>().unwrap())
         }
     }
+    /// Nixpkgs version to pick fixes for: `nixpkgs_version` from statix.toml,
+    /// or the version of the nixpkgs checkout containing `target`
+    pub fn nixpkgs_version(&self, target: &Path) -> Result<Option<Version>, ConfigErr> {
+        if let Some(v) = &self.nixpkgs_version {
+            v.parse::<Version>()
+                .map(Some)
+                .map_err(|_| ConfigErr::ConfFileVersionParse(v.clone()))
+        } else {
+            Ok(utils::get_nixpkgs_version_hint(target).and_then(|o| o.parse::<Version>().ok()))
+        }
+    }
+    /// Session for linting `target`, `nix_version` overrides the detected version
+    pub fn session(
+        &self,
+        target: &Path,
+        nix_version: Option<Version>,
+    ) -> Result<SessionInfo, ConfigErr> {
+        let nix_version = match nix_version {
+            Some(v) => v,
+            None => self.version(target)?,
+        };
+        let session = SessionInfo::from_version(nix_version)
+            .with_generated(self.generated())
+            .with_options(self.options());
+        Ok(match self.nixpkgs_version(target)? {
+            Some(v) => session.with_nixpkgs_version(v),
+            None => session,
+        })
+    }
+    /// Mode set in statix.toml, if any, `None` means detecting it per file
+    pub fn mode(&self) -> Result<Option<Mode>, ConfigErr> {
+        self.mode.as_deref().map(parse_mode).transpose()
+    }
 }
 
 fn parse_line_col(src: &str) -> Result<(usize, usize), ConfigErr> {
@@ -353,6 +845,35 @@ fn parse_line_col(src: &str) -> Result<(usize, usize), ConfigErr> {
     }
 }
 
+fn parse_mode(src: &str) -> Result<Mode, ConfigErr> {
+    src.parse::<Mode>()
+        .map_err(|_| ConfigErr::InvalidMode(src.to_owned()))
+}
+
+fn parse_nix_version(src: &str) -> Result<Version, ConfigErr> {
+    src.parse::<Version>()
+        .map_err(|_| ConfigErr::ConfFileVersionParse(src.to_owned()))
+}
+
+fn parse_file_size(src: &str) -> Result<u64, ConfigErr> {
+    let (digits, unit) = match src.find(|c: char| !c.is_ascii_digit()) {
+        Some(idx) => src.split_at(idx),
+        None => (src, ""),
+    };
+    let unit = match unit.to_ascii_lowercase().as_str() {
+        "" => 1,
+        "k" => 1 << 10,
+        "m" => 1 << 20,
+        "g" => 1 << 30,
+        _ => return Err(ConfigErr::InvalidFileSize(src.to_owned())),
+    };
+    digits
+        .parse::<u64>()
+        .ok()
+        .and_then(|n| n.checked_mul(unit))
+        .ok_or_else(|| ConfigErr::InvalidFileSize(src.to_owned()))
+}
+
 fn parse_warning_code(src: &str) -> Result<u32, ConfigErr> {
     let mut char_stream = src.chars();
     let severity = char_stream
@@ -368,15 +889,64 @@ fn parse_warning_code(src: &str) -> Result<u32, ConfigErr> {
     }
 }
 
-fn vfs(files: Vec<PathBuf>) -> Result<ReadOnlyVfs, ConfigErr> {
+fn parse_explain_target(src: &str) -> Result<ExplainTarget, ConfigErr> {
+    let looks_like_code = src.len() > 1
+        && src.starts_with(|c: char| c.is_ascii_alphabetic())
+        && src[1..].chars().all(|c| c.is_ascii_digit());
+    if looks_like_code {
+        parse_warning_code(src).map(ExplainTarget::Code)
+    } else if !src.is_empty() && src.chars().all(|c| c.is_ascii_digit()) {
+        parse_warning_code(&format!("w{}", src)).map(ExplainTarget::Code)
+    } else if !src.is_empty() {
+        Ok(ExplainTarget::Name(src.to_owned()))
+    } else {
+        Err(ConfigErr::InvalidWarningCode(src.to_owned()))
+    }
+}
+
+fn vfs(mut files: Vec<PathBuf>, max_file_size: Option<u64>) -> Result<ReadOnlyVfs, ConfigErr> {
+    // in a stable order, whatever the order of directory entries
+    files.sort();
     let mut vfs = ReadOnlyVfs::default();
+    read_files(&mut vfs, files, max_file_size);
+    Ok(vfs)
+}
+
+// the directory to look for statix.toml from, for a file or directory
+fn search_dir(target: &Path) -> &Path {
+    match target.parent() {
+        Some(parent) if target.is_file() && !parent.as_os_str().is_empty() => parent,
+        Some(_) if target.is_file() => Path::new("."),
+        _ => target,
+    }
+}
+
+fn read_files(vfs: &mut ReadOnlyVfs, files: Vec<PathBuf>, max_file_size: Option<u64>) {
     for file in files.iter() {
-        if let Ok(data) = fs::read_to_string(file) {
-            let _id = vfs.alloc_file_id(file);
-            vfs.set_file_contents(file, data.as_bytes());
-        } else {
-            println!("`{}` contains non-utf8 content", file.display());
+        if let Some(max) = max_file_size {
+            if matches!(fs::metadata(file), Ok(m) if m.len() > max) {
+                eprintln!(
+                    "`{}` is larger than {} bytes, skipping it",
+                    file.display(),
+                    max
+                );
+                continue;
+            }
+        }
+        match fs::read(file) {
+            Ok(data) => {
+                vfs.read_file(file, &data);
+            }
+            Err(e) => eprintln!("`{}` could not be read: {}", file.display(), e),
         };
     }
+}
+
+fn stdin_vfs() -> Result<ReadOnlyVfs, ConfigErr> {
+    use std::io::{self, Read};
+    let mut src = Vec::new();
+    io::stdin().lock().read_to_end(&mut src)?;
+    let mut vfs = ReadOnlyVfs::default();
+    vfs.read_file("<stdin>", &src);
     Ok(vfs)
 }
@@ -1,4 +1,5 @@
 use std::{
+    collections::HashSet,
     fs,
     io::{self, Error, ErrorKind},
     path::{Path, PathBuf},
@@ -16,10 +17,19 @@ pub struct Walker {
     dirs: Vec<PathBuf>,
     files: Vec<PathBuf>,
     ignore: Gitignore,
+    follow_symlinks: bool,
+    // directories walked so far, canonicalized, to not loop through symlinks
+    visited: HashSet<PathBuf>,
 }
 
 impl Walker {
-    pub fn new<P: AsRef<Path>>(target: P, ignore: Gitignore) -> io::Result<Self> {
+    /// Walk `target`, which is followed even if it is a symlink. Other
+    /// symlinks are skipped unless `follow_symlinks` is set
+    pub fn new<P: AsRef<Path>>(
+        target: P,
+        ignore: Gitignore,
+        follow_symlinks: bool,
+    ) -> io::Result<Self> {
         let target = target.as_ref().to_path_buf();
         if !target.exists() {
             Err(Error::new(
@@ -31,12 +41,16 @@ impl Walker {
                 dirs: vec![target],
                 files: vec![],
                 ignore,
+                follow_symlinks,
+                visited: HashSet::new(),
             })
         } else {
             Ok(Self {
                 dirs: vec![],
                 files: vec![target],
                 ignore,
+                follow_symlinks,
+                visited: HashSet::new(),
             })
         }
     }
@@ -48,11 +62,23 @@ impl Iterator for Walker {
         self.files.pop().or_else(|| {
             while let Some(dir) = self.dirs.pop() {
                 if dir.is_dir() {
+                    // a directory seen before is reached again through a symlink
+                    let seen = self.follow_symlinks
+                        && !fs::canonicalize(&dir)
+                            .map(|canonical| self.visited.insert(canonical))
+                            .unwrap_or(false);
+                    if seen {
+                        continue;
+                    }
                     if let Match::None | Match::Whitelist(_) = self.ignore.matched(&dir, true) {
                         let mut found = false;
                         for entry in fs::read_dir(&dir).ok()? {
                             let entry = entry.ok()?;
                             let path = entry.path();
+                            let is_symlink = matches!(entry.file_type(), Ok(t) if t.is_symlink());
+                            if is_symlink && !self.follow_symlinks {
+                                continue;
+                            }
                             if path.is_dir() {
                                 self.dirs.push(path);
                             } else if path.is_file() {
@@ -107,7 +133,20 @@ pub fn build_ignore_set<P: AsRef<Path>>(
 pub fn walk_nix_files<P: AsRef<Path>>(
     ignore: Gitignore,
     target: P,
+    follow_symlinks: bool,
+) -> Result<impl Iterator<Item = PathBuf>, io::Error> {
+    walk_files(ignore, target, follow_symlinks, &["nix"])
+}
+
+/// Walk the files of `target` with one of `extensions`
+pub fn walk_files<P: AsRef<Path>>(
+    ignore: Gitignore,
+    target: P,
+    follow_symlinks: bool,
+    extensions: &'static [&'static str],
 ) -> Result<impl Iterator<Item = PathBuf>, io::Error> {
-    let walker = dirs::Walker::new(target, ignore)?;
-    Ok(walker.filter(|path: &PathBuf| matches!(path.extension(), Some(e) if e == "nix")))
+    let walker = dirs::Walker::new(target, ignore, follow_symlinks)?;
+    Ok(walker.filter(move |path: &PathBuf| {
+        matches!(path.extension().and_then(|e| e.to_str()), Some(e) if extensions.contains(&e))
+    }))
 }
@@ -0,0 +1,147 @@
+use std::{fs, path::Path};
+
+use crate::{explain, fix, utils};
+
+use lib::{session::SessionInfo, Lint, LINTS};
+use similar::TextDiff;
+
+fn lints() -> Vec<&'static dyn Lint> {
+    let mut lints = (*LINTS).clone();
+    lints.as_mut_slice().sort_by_key(|a| a.code());
+    lints
+}
+
+fn example_diff(lint: &'static dyn Lint, examples: &Path, sess: &SessionInfo) -> Option<String> {
+    let path = examples.join(format!("{}.nix", lint.name()));
+    let src = fs::read_to_string(&path).ok()?;
+    let lints = utils::lint_map_of(&[lint]);
+    // render example fixes even if the targeted nix version skips this lint
+    let sess = if lint.applies_to(sess) {
+        SessionInfo::from_version(*sess.version())
+    } else {
+        SessionInfo::from_version(lint.min_nix_version().or(lint.max_nix_version())?)
+    };
+    let sess = match lint.modes().first() {
+        Some(mode) if !lint.applies_to(&sess) => sess.with_mode(*mode),
+        _ => sess,
+    };
+    let fixed = fix::all_with(&src, &lints, &sess)?.src;
+    let diff = TextDiff::from_lines(src.as_str(), &fixed)
+        .unified_diff()
+        .context_radius(1)
+        .header(
+            &format!("{}.nix", lint.name()),
+            &format!("{}.nix [fixed]", lint.name()),
+        )
+        .to_string();
+    Some(diff)
+}
+
+fn page(
+    lint: &'static dyn Lint,
+    level: usize,
+    examples: Option<&Path>,
+    sess: &SessionInfo,
+) -> String {
+    let kinds = match lint.match_kind().as_slice() {
+        [] => "whole project".to_owned(),
+        kinds => kinds
+            .iter()
+            .map(|k| format!("`{:?}`", k))
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+    let versions = match (lint.min_nix_version(), lint.max_nix_version()) {
+        (Some(min), Some(max)) => format!("{} to {}", min, max),
+        (Some(min), None) => format!("{} and later", min),
+        (None, Some(max)) => format!("up to {}", max),
+        (None, None) => "any".to_owned(),
+    };
+    let modes = if lint.modes().is_empty() {
+        "any".to_owned()
+    } else {
+        lint.modes()
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut out = format!(
+        "{heading} {name}\n\n> {note}\n\n| Code | Severity | Nix version | Mode | Matches |\n|------|----------|-------------|------|---------|\n| `W{code:02}` | {severity} | {versions} | {modes} | {kinds} |\n\n{explanation}\n",
+        heading = "#".repeat(level),
+        name = lint.name(),
+        note = lint.note(),
+        code = lint.code(),
+        severity = lint.severity(),
+        versions = versions,
+        modes = modes,
+        kinds = kinds,
+        explanation = explain::render(&lint.sections(), level + 1),
+    );
+    if let Some(diff) = examples.and_then(|e| example_diff(lint, e, sess)) {
+        out.push_str(&format!(
+            "\n{} Fixes\n\n```diff\n{}```\n",
+            "#".repeat(level + 1),
+            diff
+        ));
+    }
+    out
+}
+
+/// Render documentation for all lints as a single markdown document
+pub fn book(examples: Option<&Path>, sess: &SessionInfo) -> String {
+    let lints = lints();
+    let mut out = String::from("# Lints\n\n");
+    for lint in lints.iter() {
+        out.push_str(&format!(
+            "- [{0}](#{0}) (W{1:02})\n",
+            lint.name(),
+            lint.code()
+        ));
+    }
+    for lint in lints {
+        out.push('\n');
+        out.push_str(&page(lint, 2, examples, sess));
+    }
+    out
+}
+
+/// Render documentation for all lints as one markdown file per lint,
+/// along with a `SUMMARY.md` index
+pub fn pages(examples: Option<&Path>, sess: &SessionInfo) -> Vec<(String, String)> {
+    let lints = lints();
+    let mut summary = String::from("# Summary\n\n");
+    let mut out = Vec::new();
+    for lint in lints {
+        let file_name = format!("{}.md", lint.name());
+        summary.push_str(&format!("- [{}]({})\n", lint.name(), file_name));
+        out.push((file_name, page(lint, 1, examples, sess)));
+    }
+    out.push(("SUMMARY.md".into(), summary));
+    out
+}
+
+pub mod main {
+    use std::fs;
+
+    use crate::{
+        config::{ConfFile, Doc as DocConfig},
+        err::{DocErr, StatixErr},
+    };
+
+    pub fn main(doc_config: DocConfig) -> Result<(), StatixErr> {
+        let conf_file = ConfFile::discover(&doc_config.conf_path)?;
+        let session = conf_file.session(&doc_config.conf_path, None)?;
+        let examples = doc_config.examples.as_deref();
+        match &doc_config.out_dir {
+            Some(out_dir) => {
+                fs::create_dir_all(out_dir).map_err(DocErr::InvalidPath)?;
+                for (file_name, contents) in super::pages(examples, &session) {
+                    fs::write(out_dir.join(file_name), contents).map_err(DocErr::InvalidPath)?;
+                }
+            }
+            None => print!("{}", super::book(examples, &session)),
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,93 @@
+use std::fmt::Write;
+
+use rnix::{NodeOrToken, SyntaxNode, TextSize, WalkEvent};
+
+/// The syntax tree under `node`, one element per line with its kind and
+/// range, and the text of tokens
+pub fn tree(node: &SyntaxNode) -> String {
+    let mut out = String::new();
+    let mut depth = 0;
+    for event in node.preorder_with_tokens() {
+        match event {
+            WalkEvent::Enter(element) => {
+                let indent = "  ".repeat(depth);
+                match element {
+                    NodeOrToken::Node(n) => {
+                        writeln!(out, "{}{:?}@{:?}", indent, n.kind(), n.text_range()).unwrap();
+                        depth += 1;
+                    }
+                    NodeOrToken::Token(t) => {
+                        let (kind, range) = (t.kind(), t.text_range());
+                        writeln!(out, "{}{:?}@{:?} {:?}", indent, kind, range, t.text()).unwrap();
+                    }
+                }
+            }
+            WalkEvent::Leave(NodeOrToken::Node(_)) => depth -= 1,
+            WalkEvent::Leave(_) => (),
+        }
+    }
+    out
+}
+
+/// The smallest node containing the token at `offset`
+pub fn covering(root: &SyntaxNode, offset: TextSize) -> SyntaxNode {
+    root.token_at_offset(offset)
+        .right_biased()
+        .map(|t| t.parent())
+        .unwrap_or_else(|| root.clone())
+}
+
 pub mod main {
-    use crate::{config::ConfFile, err::StatixErr};
-    pub fn main() -> Result<(), StatixErr> {
-        println!("{}", ConfFile::dump(&ConfFile::default()));
+    use crate::{
+        config::{ConfFile, Dump as DumpConfig},
+        err::{DumpErr, StatixErr},
+        utils,
+    };
+
+    pub fn main(dump_config: DumpConfig) -> Result<(), StatixErr> {
+        let vfs = match dump_config.vfs()? {
+            Some(vfs) => vfs,
+            None => {
+                println!("{}", ConfFile::dump(&ConfFile::default()));
+                return Ok(());
+            }
+        };
+        let entry = vfs.iter().next().unwrap();
+        let root = rnix::parse(entry.contents).node();
+        let node = match dump_config.position {
+            Some((line, col)) => {
+                let offset =
+                    utils::pos_to_byte(line, col, entry.contents).map_err(DumpErr::from)?;
+                super::covering(&root, offset)
+            }
+            None => root,
+        };
+        print!("{}", super::tree(&node));
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn let_in() {
+        let root = rnix::parse("let a = 1; in a").node();
+        let binding = covering(&root, 6.into());
+        assert_eq!(
+            tree(&binding),
+            "NODE_KEY_VALUE@4..10
+  NODE_KEY@4..5
+    NODE_IDENT@4..5
+      TOKEN_IDENT@4..5 \"a\"
+  TOKEN_WHITESPACE@5..6 \" \"
+  TOKEN_ASSIGN@6..7 \"=\"
+  TOKEN_WHITESPACE@7..8 \" \"
+  NODE_LITERAL@8..9
+    TOKEN_INTEGER@8..9 \"1\"
+  TOKEN_SEMICOLON@9..10 \";\"
+"
+        );
+    }
+}
@@ -0,0 +1,211 @@
+use std::{
+    borrow::Cow,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use globset::GlobBuilder;
+use lib::session::{EndOfLine, Indent, Style};
+
+const FILE_NAME: &str = ".editorconfig";
+
+struct Section {
+    glob: String,
+    properties: Vec<(String, String)>,
+}
+
+struct EditorConfig {
+    dir: PathBuf,
+    root: bool,
+    sections: Vec<Section>,
+}
+
+fn parse(dir: &Path, src: &str) -> EditorConfig {
+    let mut root = false;
+    let mut sections: Vec<Section> = Vec::new();
+    for line in src.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+            continue;
+        }
+        if let Some(glob) = line.strip_prefix('[').and_then(|l| l.strip_suffix(']')) {
+            sections.push(Section {
+                glob: glob.to_owned(),
+                properties: Vec::new(),
+            });
+        } else if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim().to_ascii_lowercase();
+            let value = value.trim().to_ascii_lowercase();
+            match sections.last_mut() {
+                Some(section) => section.properties.push((key, value)),
+                None if key == "root" => root = value == "true",
+                None => (),
+            }
+        }
+    }
+    EditorConfig {
+        dir: dir.to_owned(),
+        root,
+        sections,
+    }
+}
+
+// globs without a `/` match files in any subdirectory, the others are
+// relative to the directory of the `.editorconfig`
+fn matches(glob: &str, relative: &Path) -> bool {
+    let glob = match glob.strip_prefix('/') {
+        Some(anchored) => anchored.to_owned(),
+        None if glob.contains('/') => glob.to_owned(),
+        None => format!("**/{}", glob),
+    };
+    GlobBuilder::new(&glob)
+        .literal_separator(true)
+        .build()
+        .map(|g| g.compile_matcher().is_match(relative))
+        .unwrap_or(false)
+}
+
+// `.editorconfig` files that apply to `path`, outermost first
+fn discover(path: &Path) -> Vec<EditorConfig> {
+    let mut configs = Vec::new();
+    let path = match fs::canonicalize(path) {
+        Ok(p) => p,
+        Err(_) => return configs,
+    };
+    for dir in path.ancestors().skip(1) {
+        if let Ok(src) = fs::read_to_string(dir.join(FILE_NAME)) {
+            let config = parse(dir, &src);
+            let root = config.root;
+            configs.push(config);
+            if root {
+                break;
+            }
+        }
+    }
+    configs.reverse();
+    configs
+}
+
+fn style_of(path: &Path, configs: &[EditorConfig]) -> Style {
+    let mut style = Style::default();
+    let (mut indent_style, mut indent_size, mut tab_width) = (None, None, None);
+    for config in configs {
+        let relative = match path.strip_prefix(&config.dir) {
+            Ok(r) => r,
+            Err(_) => continue,
+        };
+        for section in config
+            .sections
+            .iter()
+            .filter(|s| matches(&s.glob, relative))
+        {
+            for (key, value) in &section.properties {
+                match (key.as_str(), value.as_str()) {
+                    ("indent_style", v) => indent_style = Some(v.to_owned()),
+                    ("indent_size", v) => indent_size = Some(v.to_owned()),
+                    ("tab_width", v) => tab_width = v.parse::<usize>().ok(),
+                    ("end_of_line", "lf") => style.end_of_line = EndOfLine::Lf,
+                    ("end_of_line", "crlf") => style.end_of_line = EndOfLine::CrLf,
+                    ("end_of_line", "cr") => style.end_of_line = EndOfLine::Cr,
+                    ("insert_final_newline", "true") => style.final_newline = Some(true),
+                    ("insert_final_newline", "false") => style.final_newline = Some(false),
+                    ("insert_final_newline", "unset") => style.final_newline = None,
+                    _ => (),
+                }
+            }
+        }
+    }
+    let size = match indent_size.as_deref() {
+        Some("tab") => tab_width,
+        Some(n) => n.parse::<usize>().ok(),
+        None => None,
+    };
+    match (indent_style.as_deref(), size) {
+        (Some("tab"), _) => style.indent = Indent::Tab,
+        (_, Some(n)) => style.indent = Indent::Spaces(n),
+        _ => (),
+    }
+    style
+}
+
+/// Whitespace conventions for the file at `path`, as configured by
+/// the `.editorconfig` files in its parent directories
+pub fn style_for(path: &Path) -> Style {
+    let configs = discover(path);
+    match fs::canonicalize(path) {
+        Ok(path) => style_of(&path, &configs),
+        Err(_) => Style::default(),
+    }
+}
+
+/// Bring fixed source in line with `style`: line breaks introduced by
+/// fixes use its line terminator, and the final newline is added or
+/// removed as configured
+pub fn normalize<'a>(src: Cow<'a, str>, style: &Style) -> Cow<'a, str> {
+    let eol = style.eol();
+    let mut src = src;
+    if style.end_of_line != EndOfLine::Lf {
+        let mut normalized = String::with_capacity(src.len());
+        let mut lines = src.split('\n').peekable();
+        while let Some(line) = lines.next() {
+            let line = line.strip_suffix('\r').unwrap_or(line);
+            normalized.push_str(line);
+            if lines.peek().is_some() {
+                normalized.push_str(eol);
+            }
+        }
+        if normalized != src {
+            src = normalized.into();
+        }
+    }
+    match style.final_newline {
+        Some(true) if !src.ends_with(eol) => src.to_mut().push_str(eol),
+        Some(false) if src.ends_with(eol) => {
+            let trimmed = src.trim_end_matches(eol).len();
+            src.to_mut().truncate(trimmed);
+        }
+        _ => (),
+    }
+    src
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nearest_section_wins() {
+        let configs = [
+            parse(
+                Path::new("/repo"),
+                "root = true\n[*]\nindent_style = space\nindent_size = 4\nend_of_line = crlf\n",
+            ),
+            parse(
+                Path::new("/repo/pkgs"),
+                "[*.{nix,toml}]\nindent_style = tab\ninsert_final_newline = true\n",
+            ),
+        ];
+        assert!(configs[0].root);
+        let style = style_of(Path::new("/repo/pkgs/foo/default.nix"), &configs);
+        assert_eq!(style.indent, Indent::Tab);
+        assert_eq!(style.end_of_line, EndOfLine::CrLf);
+        assert_eq!(style.final_newline, Some(true));
+        let style = style_of(Path::new("/repo/default.nix"), &configs);
+        assert_eq!(style.indent, Indent::Spaces(4));
+        assert_eq!(style.final_newline, None);
+    }
+
+    #[test]
+    fn normalizes_fixed_source() {
+        let style = Style {
+            end_of_line: EndOfLine::CrLf,
+            final_newline: Some(true),
+            ..Style::default()
+        };
+        assert_eq!(normalize("a\r\nb\nc".into(), &style), "a\r\nb\r\nc\r\n");
+        let style = Style {
+            final_newline: Some(false),
+            ..Style::default()
+        };
+        assert_eq!(normalize("a\n\n".into(), &style), "a");
+    }
+}
@@ -1,4 +1,4 @@
-use std::io;
+use std::{io, path::PathBuf};
 
 use thiserror::Error;
 
@@ -16,6 +16,12 @@ pub enum ConfigErr {
     ConfFileParse(toml::de::Error),
     #[error("unable to parse nix version: `{0}`")]
     ConfFileVersionParse(String),
+    #[error("unknown mode `{0}`, try: plain, nixpkgs, nixos, flake")]
+    InvalidMode(String),
+    #[error("unable to parse `{0}` as a file size, try: 1048576, 512K, 1M")]
+    InvalidFileSize(String),
+    #[error("unable to run shellcheck: {0}")]
+    ShellCheck(io::Error),
 }
 
 // #[derive(Error, Debug)]
@@ -30,6 +36,10 @@ pub enum FixErr {
     // Parse(PathBuf, ParseError),
     #[error("path error: {0}")]
     InvalidPath(#[from] io::Error),
+    #[error("formatter `{0}` failed: {1}")]
+    Formatter(String, String),
+    #[error("`{0}` is not valid UTF-8, not fixing it")]
+    InvalidUtf8(PathBuf),
 }
 
 #[derive(Error, Debug)]
@@ -42,12 +52,34 @@ pub enum SingleFixErr {
     Conversion(usize),
     #[error("nothing to fix")]
     NoOp,
+    #[error("file is not valid UTF-8")]
+    InvalidUtf8,
 }
 
 #[derive(Error, Debug)]
 pub enum ExplainErr {
     #[error("lint with code `{0}` not found")]
     LintNotFound(u32),
+    #[error("lint with name `{0}` not found{}", .1.map(|s| format!(", did you mean `{}`?", s)).unwrap_or_default())]
+    LintNameNotFound(String, Option<&'static str>),
+}
+
+#[derive(Error, Debug)]
+pub enum DumpErr {
+    #[error("{0}")]
+    Position(#[from] SingleFixErr),
+}
+
+#[derive(Error, Debug)]
+pub enum DocErr {
+    #[error("path error: {0}")]
+    InvalidPath(#[from] io::Error),
+}
+
+#[derive(Error, Debug)]
+pub enum ManErr {
+    #[error("path error: {0}")]
+    InvalidPath(#[from] io::Error),
 }
 
 #[derive(Error, Debug)]
@@ -62,4 +94,10 @@ pub enum StatixErr {
     Config(#[from] ConfigErr),
     #[error("explain error: {0}")]
     Explain(#[from] ExplainErr),
+    #[error("dump error: {0}")]
+    Dump(#[from] DumpErr),
+    #[error("doc error: {0}")]
+    Doc(#[from] DocErr),
+    #[error("man error: {0}")]
+    Man(#[from] ManErr),
 }
@@ -1,15 +1,69 @@
-use crate::{err::ExplainErr, utils};
+use crate::{config::ExplainTarget, err::ExplainErr, shellcheck, utils};
 
-pub fn explain(code: u32) -> Result<&'static str, ExplainErr> {
+use lib::{utils::edit_distance, Explanation, Lint, LINTS};
+
+pub fn lint(code: u32) -> Result<&'static dyn Lint, ExplainErr> {
     let lints = utils::lint_map();
-    match code {
-        0 => Ok("syntax error"),
-        _ => lints
-            .values()
-            .flatten()
-            .find(|l| l.code() == code)
-            .map(|l| l.explanation())
-            .ok_or(ExplainErr::LintNotFound(code)),
+    lints
+        .values()
+        .flatten()
+        .find(|l| l.code() == code)
+        .copied()
+        .ok_or(ExplainErr::LintNotFound(code))
+}
+
+pub fn lint_by_name(name: &str) -> Result<&'static dyn Lint, ExplainErr> {
+    let normalized = name.trim().to_ascii_lowercase().replace('-', "_");
+    if let Some(lint) = LINTS.iter().find(|l| l.name() == normalized) {
+        return Ok(*lint);
+    }
+    // suggest the closest lint name, if it is reasonably close
+    let suggestion = LINTS
+        .iter()
+        .map(|l| (edit_distance(&normalized, l.name()), l.name()))
+        .filter(|(distance, candidate)| *distance <= (candidate.len() / 3).max(2))
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate);
+    Err(ExplainErr::LintNameNotFound(name.to_owned(), suggestion))
+}
+
+/// Render the sections of an explanation as markdown, with headings at `level`
+pub fn render(sections: &Explanation, level: usize) -> String {
+    let heading = "#".repeat(level);
+    let mut out = format!(
+        "{h} What it does\n{}\n\n{h} Why is this bad?\n{}\n",
+        sections.summary,
+        sections.rationale,
+        h = heading
+    );
+    if let Some(bad) = sections.bad_example {
+        out.push_str(&format!("\n{} Example\n\n```nix\n{}\n```\n", heading, bad));
+    }
+    if let Some(good) = sections.good_example {
+        out.push_str(&format!("\nInstead, prefer:\n\n```nix\n{}\n```\n", good));
+    }
+    out
+}
+
+pub fn explain(target: &ExplainTarget) -> Result<String, ExplainErr> {
+    match target {
+        ExplainTarget::Code(0) => Ok("syntax error".to_owned()),
+        ExplainTarget::Code(shellcheck::CODE) => Ok(
+            "issue found by shellcheck in an embedded shell script, see `check --shellcheck`"
+                .to_owned(),
+        ),
+        _ => {
+            let lint = match target {
+                ExplainTarget::Code(code) => lint(*code)?,
+                ExplainTarget::Name(name) => lint_by_name(name)?,
+            };
+            let sections = lint.sections();
+            if sections.summary.is_empty() {
+                Ok(lint.explanation().to_owned())
+            } else {
+                Ok(render(&sections, 2))
+            }
+        }
     }
 }
 
@@ -18,7 +72,7 @@ pub mod main {
     use crate::{config::Explain as ExplainConfig, err::StatixErr};
 
     pub fn main(explain_config: ExplainConfig) -> Result<(), StatixErr> {
-        let explanation = super::explain(explain_config.target)?;
+        let explanation = super::explain(&explain_config.target)?;
         println!("{}", explanation);
         Ok(())
     }
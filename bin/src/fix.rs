@@ -6,11 +6,14 @@ use lib::session::SessionInfo;
 use rnix::TextRange;
 
 mod all;
-use all::all_with;
+pub use all::all_with;
 
 mod single;
 use single::single;
 
+mod format;
+pub use format::format_with;
+
 type Source<'a> = Cow<'a, str>;
 
 pub struct FixResult<'a> {
@@ -38,32 +41,52 @@ impl<'a> FixResult<'a> {
 }
 
 pub mod main {
-    use std::borrow::Cow;
+    use std::{
+        borrow::Cow,
+        io::{self, Write},
+    };
 
     use crate::{
         config::{
             FixOut, Single as SingleConfig, {ConfFile, Fix as FixConfig},
         },
-        err::{FixErr, StatixErr},
+        editorconfig,
+        err::{FixErr, SingleFixErr, StatixErr},
+        utils,
     };
 
-    use lib::session::SessionInfo;
     use similar::TextDiff;
 
     pub fn all(fix_config: FixConfig) -> Result<(), StatixErr> {
         let conf_file = ConfFile::discover(&fix_config.conf_path)?;
-        let vfs = fix_config.vfs(conf_file.ignore.as_slice())?;
+        let vfs = fix_config.vfs(&conf_file)?;
 
-        let lints = conf_file.lints();
-        let version = conf_file.version()?;
-
-        let session = SessionInfo::from_version(version);
+        let lints = conf_file.fix_lints();
+        let session = fix_config.session(&conf_file)?;
+        let mode = fix_config.mode(&conf_file)?;
 
         for entry in vfs.iter() {
-            match (
-                fix_config.out(),
-                super::all_with(entry.contents, &lints, &session),
-            ) {
+            // writing back a lossily decoded file would replace the invalid parts for good
+            if entry.encoding.lossy {
+                eprintln!("{}", FixErr::InvalidUtf8(entry.file_path.to_owned()));
+                continue;
+            }
+            let mode = mode.unwrap_or_else(|| utils::detect_mode(entry.file_path));
+            let style = editorconfig::style_for(entry.file_path);
+            let session = session
+                .clone()
+                .with_mode(mode)
+                .with_style(style)
+                .with_file(entry.file_path);
+            let mut fix_result = super::all_with(entry.contents, &lints, &session);
+            if let Some(fix_result) = &mut fix_result {
+                let src = std::mem::take(&mut fix_result.src);
+                fix_result.src = editorconfig::normalize(src, &style);
+            }
+            if let (Some(formatter), Some(fix_result)) = (&fix_config.formatter, &mut fix_result) {
+                fix_result.src = super::format_with(formatter, &fix_result.src)?.into();
+            }
+            match (fix_config.out(), fix_result) {
                 (FixOut::Diff, fix_result) => {
                     let src = fix_result
                         .map(|r| r.src)
@@ -83,11 +106,14 @@ pub mod main {
                     let src = fix_result
                         .map(|r| r.src)
                         .unwrap_or(Cow::Borrowed(entry.contents));
-                    println!("{}", &src)
+                    io::stdout()
+                        .write_all(&entry.encoding.encode(&src))
+                        .map_err(FixErr::InvalidPath)?;
                 }
                 (FixOut::Write, Some(fix_result)) => {
                     let path = entry.file_path;
-                    std::fs::write(path, &*fix_result.src).map_err(FixErr::InvalidPath)?;
+                    let src = entry.encoding.encode(&fix_result.src);
+                    std::fs::write(path, src).map_err(FixErr::InvalidPath)?;
                 }
                 _ => (),
             };
@@ -101,17 +127,35 @@ pub mod main {
         let path = entry.file_path.display().to_string();
         let original_src = entry.contents;
         let (line, col) = single_config.position;
+        if entry.encoding.lossy {
+            return Err(SingleFixErr::InvalidUtf8.into());
+        }
 
         let conf_file = ConfFile::discover(&single_config.conf_path)?;
 
-        let version = conf_file.version()?;
+        let session = single_config.session(&conf_file)?;
+        let mode = single_config
+            .mode(&conf_file)?
+            .or_else(|| single_config.target.as_deref().map(utils::detect_mode))
+            .unwrap_or_default();
+
+        let style = single_config
+            .target
+            .as_deref()
+            .map(editorconfig::style_for)
+            .unwrap_or_default();
+        let session = match single_config.target.as_deref() {
+            Some(target) => session.with_file(target),
+            None => session,
+        };
+        let session = session.with_mode(mode).with_style(style);
 
-        let session = SessionInfo::from_version(version);
+        let single_result = super::single(line, col, original_src, &session).map(|mut r| {
+            r.src = editorconfig::normalize(r.src, &style);
+            r
+        });
 
-        match (
-            single_config.out(),
-            super::single(line, col, original_src, &session),
-        ) {
+        match (single_config.out(), single_result) {
             (FixOut::Diff, single_result) => {
                 let fixed_src = single_result
                     .map(|r| r.src)
@@ -131,11 +175,14 @@ pub mod main {
                 let src = single_result
                     .map(|r| r.src)
                     .unwrap_or(Cow::Borrowed(original_src));
-                println!("{}", &src)
+                io::stdout()
+                    .write_all(&entry.encoding.encode(&src))
+                    .map_err(FixErr::InvalidPath)?;
             }
             (FixOut::Write, Ok(single_result)) => {
                 let path = entry.file_path;
-                std::fs::write(path, &*single_result.src).map_err(FixErr::InvalidPath)?;
+                let src = entry.encoding.encode(&single_result.src);
+                std::fs::write(path, src).map_err(FixErr::InvalidPath)?;
             }
             (_, Err(e)) => return Err(e.into()),
         };
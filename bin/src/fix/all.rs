@@ -22,6 +22,7 @@ fn collect_fixes(
             WalkEvent::Enter(child) => lints.get(&child.kind()).map(|rules| {
                 rules
                     .iter()
+                    .filter(|rule| rule.applies_to(sess))
                     .filter_map(|rule| rule.validate(&child, sess))
                     .filter(|report| report.total_suggestion_range().is_some())
                     .collect::<Vec<_>>()
@@ -0,0 +1,51 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::{config::Formatter, err::FixErr};
+
+/// Run `src` through an external formatter, reading the result from its stdout
+pub fn format_with(formatter: &Formatter, src: &str) -> Result<String, FixErr> {
+    let failed = |reason: String| FixErr::Formatter(formatter.program.clone(), reason);
+    let mut child = Command::new(&formatter.program)
+        .args(&formatter.args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| failed(e.to_string()))?;
+    // the formatter may not read all of stdin before failing, so write from
+    // a separate thread to avoid blocking on a full pipe
+    let mut stdin = child.stdin.take().unwrap();
+    let input = src.to_owned();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child
+        .wait_with_output()
+        .map_err(|e| failed(e.to_string()))?;
+    let written = writer.join().unwrap();
+    if !output.status.success() {
+        return Err(failed(
+            String::from_utf8_lossy(&output.stderr).trim().to_owned(),
+        ));
+    }
+    written.map_err(|e| failed(e.to_string()))?;
+    String::from_utf8(output.stdout).map_err(|e| failed(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pipes_through_command() {
+        let formatter = Formatter::from("tr a-z A-Z");
+        assert_eq!(format_with(&formatter, "{ a = 1; }").unwrap(), "{ A = 1; }");
+    }
+
+    #[test]
+    fn reports_failure() {
+        let formatter = Formatter::from("false");
+        assert!(format_with(&formatter, "{ }").is_err());
+    }
+}
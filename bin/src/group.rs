@@ -0,0 +1,222 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use crate::{
+    config::{GroupBy, SortBy},
+    lint::LintResult,
+};
+
+use lib::Severity;
+use vfs::ReadOnlyVfs;
+
+/// Findings shown together, see `check --group-by`
+pub struct Group {
+    /// Heading of the group, `None` when grouping by file
+    pub title: Option<String>,
+    pub results: Vec<LintResult>,
+}
+
+impl Group {
+    fn count(&self) -> usize {
+        self.results
+            .iter()
+            .flat_map(|r| &r.reports)
+            .map(|r| r.diagnostics.len())
+            .sum()
+    }
+
+    fn severity(&self) -> u8 {
+        self.results
+            .iter()
+            .flat_map(|r| &r.reports)
+            .map(|r| rank(r.severity))
+            .min()
+            .unwrap_or(u8::MAX)
+    }
+
+    fn code(&self) -> u32 {
+        self.results
+            .iter()
+            .flat_map(|r| &r.reports)
+            .map(|r| r.code)
+            .min()
+            .unwrap_or(u32::MAX)
+    }
+}
+
+// errors first
+fn rank(severity: Severity) -> u8 {
+    match severity {
+        Severity::Error => 0,
+        Severity::Warn => 1,
+        Severity::Hint => 2,
+    }
+}
+
+fn letter(severity: Severity) -> char {
+    match severity {
+        Severity::Error => 'E',
+        Severity::Warn => 'W',
+        Severity::Hint => 'I',
+    }
+}
+
+/// Group the results of `check`. The order does not depend on how files
+/// were walked or linted: files within a group are ordered by path, and
+/// the reports of a file by range, then code
+pub fn group(
+    mut results: Vec<LintResult>,
+    vfs: &ReadOnlyVfs,
+    by: GroupBy,
+    sort: SortBy,
+) -> Vec<Group> {
+    results.sort_by(|a, b| vfs.file_path(a.file_id).cmp(vfs.file_path(b.file_id)));
+    for result in &mut results {
+        for report in &mut result.reports {
+            report
+                .diagnostics
+                .sort_by_key(|d| (d.at.start(), d.at.end()));
+        }
+        result.reports.sort_by_key(|r| {
+            let at = r.diagnostics.first().map(|d| (d.at.start(), d.at.end()));
+            (at, r.code)
+        });
+    }
+    let mut groups = match by {
+        GroupBy::File => results
+            .into_iter()
+            .map(|r| Group {
+                title: None,
+                results: vec![r],
+            })
+            .collect(),
+        GroupBy::Lint => split(results, |r| r.code)
+            .into_iter()
+            .map(|(code, results)| {
+                let report = &results[0].reports[0];
+                let title = format!("[{}{:02}] {}", letter(report.severity), code, report.note);
+                (title, results)
+            })
+            .map(titled)
+            .collect(),
+        GroupBy::Severity => split(results, |r| rank(r.severity))
+            .into_values()
+            .map(|results| {
+                let title = match results[0].reports[0].severity {
+                    Severity::Error => "Errors",
+                    Severity::Warn => "Warnings",
+                    Severity::Hint => "Hints",
+                };
+                (title.to_owned(), results)
+            })
+            .map(titled)
+            .collect::<Vec<_>>(),
+    };
+    // stable sorts, groups are already in their natural order
+    match sort {
+        SortBy::Path => (),
+        SortBy::Severity => groups.sort_by_key(Group::severity),
+        SortBy::Code => groups.sort_by_key(Group::code),
+        SortBy::Count => groups.sort_by_key(|g| Reverse(g.count())),
+    }
+    groups
+}
+
+fn titled((title, results): (String, Vec<LintResult>)) -> Group {
+    let group = Group {
+        title: None,
+        results,
+    };
+    let title = format!("{}: {} found", title, group.count());
+    Group {
+        title: Some(title),
+        ..group
+    }
+}
+
+// split the reports of every file by `key`, in the order of the keys
+fn split<K: Ord>(
+    results: Vec<LintResult>,
+    key: impl Fn(&lib::Report) -> K,
+) -> BTreeMap<K, Vec<LintResult>> {
+    let mut groups = BTreeMap::<K, Vec<LintResult>>::new();
+    for result in results {
+        let (file_id, partial) = (result.file_id, result.partial);
+        for report in result.reports {
+            let results = groups.entry(key(&report)).or_default();
+            match results.last_mut() {
+                Some(last) if last.file_id == file_id => last.reports.push(report),
+                _ => results.push(LintResult {
+                    file_id,
+                    reports: vec![report],
+                    partial,
+                }),
+            }
+        }
+    }
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint;
+
+    use lib::session::SessionInfo;
+
+    #[test]
+    fn ordering() {
+        let vfs = ReadOnlyVfs::singleton("a.nix", b"{ }");
+        let at = |start: u32, end: u32| rnix::TextRange::new(start.into(), end.into());
+        let reports = vec![
+            lib::Report::new("b", 2).diagnostic(at(1, 2), "b"),
+            lib::Report::new("c", 3)
+                .diagnostic(at(2, 3), "c2")
+                .diagnostic(at(0, 1), "c1"),
+            lib::Report::new("a", 1).diagnostic(at(1, 2), "a"),
+        ];
+        let results = vec![LintResult {
+            file_id: vfs.iter().next().unwrap().file_id,
+            reports,
+            partial: false,
+        }];
+        let groups = group(results, &vfs, GroupBy::File, SortBy::Path);
+        let messages = groups[0].results[0]
+            .reports
+            .iter()
+            .flat_map(|r| &r.diagnostics)
+            .map(|d| d.message.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(messages, vec!["c1", "c2", "a", "b"]);
+    }
+
+    #[test]
+    fn by_lint() {
+        let mut vfs = ReadOnlyVfs::default();
+        vfs.set_file_contents("b.nix", b"[ (let in x) (y == true) ]");
+        vfs.set_file_contents("a.nix", b"[ (x == true) ]");
+        let sess = SessionInfo::from_version("2.6".parse().unwrap());
+        let results = vfs.iter().map(|e| lint::lint(e, &sess)).collect();
+
+        let groups = group(results, &vfs, GroupBy::Lint, SortBy::Count);
+        let summary = groups
+            .iter()
+            .map(|g| {
+                let files = g.results.iter().map(|r| vfs.file_path(r.file_id));
+                (g.title.clone().unwrap(), files.collect::<Vec<_>>())
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "[W01] Unnecessary comparison with boolean: 2 found".to_owned(),
+                    vec![std::path::Path::new("a.nix"), std::path::Path::new("b.nix")]
+                ),
+                (
+                    "[W02] Useless let-in expression: 1 found".to_owned(),
+                    vec![std::path::Path::new("b.nix")]
+                ),
+            ]
+        );
+    }
+}
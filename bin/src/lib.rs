@@ -1,12 +1,19 @@
+pub mod completions;
 pub mod config;
 pub mod dirs;
+pub mod doc;
 pub mod dump;
+mod editorconfig;
 pub mod err;
 pub mod explain;
 pub mod fix;
+pub mod group;
 pub mod lint;
 pub mod list;
+pub mod man;
+pub mod markdown;
 pub mod session;
+pub mod shellcheck;
 pub mod traits;
 
 mod utils;
@@ -16,4 +23,4 @@ use std::collections::HashMap;
 use lib::Lint;
 use rnix::SyntaxKind;
 
-pub type LintMap = HashMap<SyntaxKind, Vec<&'static Box<dyn Lint>>>;
+pub type LintMap = HashMap<SyntaxKind, Vec<&'static dyn Lint>>;
@@ -1,13 +1,22 @@
+use std::path::PathBuf;
+
 use crate::{utils, LintMap};
 
-use lib::{session::SessionInfo, Report};
-use rnix::WalkEvent;
+use lib::{
+    project::Project,
+    session::{Mode, SessionInfo},
+    Lint, Report,
+};
+use rnix::{NodeOrToken, SyntaxElement, SyntaxKind, TextRange, TextSize, WalkEvent};
 use vfs::{FileId, VfsEntry};
 
 #[derive(Debug)]
 pub struct LintResult {
     pub file_id: FileId,
     pub reports: Vec<Report>,
+    /// Whether the file has syntax errors, the parts of it that could not
+    /// be parsed were not linted
+    pub partial: bool,
 }
 
 pub fn lint_with(vfs_entry: VfsEntry, lints: &LintMap, sess: &SessionInfo) -> LintResult {
@@ -15,66 +24,194 @@ pub fn lint_with(vfs_entry: VfsEntry, lints: &LintMap, sess: &SessionInfo) -> Li
     let source = vfs_entry.contents;
     let parsed = rnix::parse(source);
 
-    let error_reports = parsed.errors().into_iter().map(Report::from_parse_err);
+    let errors = parsed.errors();
+    let encoding_report = vfs_entry.encoding.lossy.then(|| {
+        let start = source.find('\u{fffd}').unwrap_or_default();
+        let at = TextRange::at(TextSize::from(start as u32), TextSize::of('\u{fffd}'));
+        Report::from_invalid_utf8(at)
+    });
+    let partial = !errors.is_empty();
+    let error_reports = Report::from_parse_errs(errors, parsed.node().text_range().end());
     let reports = parsed
         .node()
         .preorder_with_tokens()
         .filter_map(|event| match event {
-            WalkEvent::Enter(child) => lints.get(&child.kind()).map(|rules| {
-                rules
-                    .iter()
-                    .filter_map(|rule| rule.validate(&child, sess))
-                    .collect::<Vec<_>>()
-            }),
+            WalkEvent::Enter(child) if !(partial && in_error(&child)) => {
+                lints.get(&child.kind()).map(|rules| {
+                    rules
+                        .iter()
+                        .filter(|rule| rule.applies_to(sess))
+                        .filter_map(|rule| rule.validate(&child, sess))
+                        .collect::<Vec<_>>()
+                })
+            }
             _ => None,
         })
         .flatten()
         .chain(error_reports)
+        .chain(encoding_report)
         .collect();
+    let reports = lib::subsume(reports);
 
-    LintResult { file_id, reports }
+    LintResult {
+        file_id,
+        reports,
+        partial,
+    }
+}
+
+// whether an element is part of a node the parser could not make sense of
+fn in_error(element: &SyntaxElement) -> bool {
+    let node = match element {
+        NodeOrToken::Node(node) => node.clone(),
+        NodeOrToken::Token(token) => token.parent(),
+    };
+    element.kind() == SyntaxKind::TOKEN_ERROR
+        || node.ancestors().any(|n| n.kind() == SyntaxKind::NODE_ERROR)
 }
 
 pub fn lint(vfs_entry: VfsEntry, sess: &SessionInfo) -> LintResult {
     lint_with(vfs_entry, &utils::lint_map(), sess)
 }
 
+/// Run the lints that look at several files at once over every file of
+/// the project, see `Rule::check_project`. Returns a result per file.
+pub fn lint_project<'a>(
+    vfs_entries: impl Iterator<Item = VfsEntry<'a>>,
+    roots: &[PathBuf],
+    lints: &[&'static dyn Lint],
+    sess: &SessionInfo,
+    mode: Option<Mode>,
+) -> Vec<LintResult> {
+    let mut vfs_entries = vfs_entries.collect::<Vec<_>>();
+    vfs_entries.sort_by_key(|entry| entry.file_path);
+    let mut results = Vec::new();
+    let project = Project::new(vfs_entries.into_iter().map(|entry| {
+        results.push(LintResult {
+            file_id: entry.file_id,
+            reports: Vec::new(),
+            partial: false,
+        });
+        let mode = mode.unwrap_or_else(|| utils::detect_mode(entry.file_path));
+        (
+            entry.file_path.to_owned(),
+            entry.contents,
+            sess.clone().with_mode(mode).with_file(entry.file_path),
+        )
+    }))
+    .with_roots(roots.iter().cloned());
+    for lint in lints.iter().filter(|rule| rule.applies_to(sess)) {
+        for project_report in lint.check_project(&project, sess) {
+            results[project_report.file]
+                .reports
+                .push(project_report.report);
+        }
+    }
+    results
+}
+
 pub mod main {
     use std::io;
 
-    use super::lint_with;
+    use super::{lint_project, lint_with};
     use crate::{
-        config::{Check as CheckConfig, ConfFile},
-        err::StatixErr,
+        config::{Check as CheckConfig, OutFormat},
+        err::{ConfigErr, StatixErr},
+        group, markdown, shellcheck,
         traits::WriteDiagnostic,
+        utils,
     };
 
-    use lib::session::SessionInfo;
     use rayon::prelude::*;
+    use vfs::VfsEntry;
 
     pub fn main(check_config: CheckConfig) -> Result<(), StatixErr> {
-        let conf_file = ConfFile::discover(&check_config.conf_path)?;
-        let lints = conf_file.lints();
-        let version = conf_file.version()?;
-        let session = SessionInfo::from_version(version);
+        let targets = check_config.targets()?;
+        let contexts = targets
+            .iter()
+            .map(|target| {
+                let conf_file = &target.conf_file;
+                let session = check_config.session(target)?;
+                Ok((conf_file.lints(), session, check_config.mode(conf_file)?))
+            })
+            .collect::<Result<Vec<_>, ConfigErr>>()?;
 
-        let vfs = check_config.vfs(conf_file.ignore.as_slice())?;
+        let (vfs, owners) = check_config.vfs(&targets)?;
+        if check_config.shellcheck {
+            shellcheck::available().map_err(ConfigErr::ShellCheck)?;
+        }
 
         let mut stdout = io::stdout();
-        let lint = |vfs_entry| lint_with(vfs_entry, &lints, &session);
-        let results = vfs
-            .par_iter()
-            .map(lint)
-            .filter(|lr| !lr.reports.is_empty())
-            .collect::<Vec<_>>();
-
-        if !results.is_empty() {
-            for r in &results {
-                stdout.write(r, &vfs, check_config.format).unwrap();
+        let lint = |vfs_entry: VfsEntry| {
+            let (lints, session, mode) = &contexts[owners[vfs_entry.file_id.0 as usize]];
+            let mode = mode.unwrap_or_else(|| utils::detect_mode(vfs_entry.file_path));
+            let session = session
+                .clone()
+                .with_mode(mode)
+                .with_file(vfs_entry.file_path);
+            let contents = vfs_entry.contents;
+            if markdown::is_markdown(vfs_entry.file_path) {
+                return markdown::lint_blocks(vfs_entry, lints, &session);
+            }
+            let mut result = lint_with(vfs_entry, lints, &session);
+            if check_config.shellcheck {
+                let root = rnix::parse(contents).node();
+                result.reports.extend(shellcheck::check(&root));
+            }
+            result
+        };
+        let mut results = vfs.par_iter().map(lint).collect::<Vec<_>>();
+
+        if check_config.project {
+            for (idx, target) in targets.iter().enumerate() {
+                let (_, session, mode) = &contexts[idx];
+                let session = session.clone().with_mode(mode.unwrap_or_default());
+                let entries = vfs
+                    .iter()
+                    .filter(|entry| owners[entry.file_id.0 as usize] == idx)
+                    .filter(|entry| !markdown::is_markdown(entry.file_path));
+                for project_result in lint_project(
+                    entries,
+                    &check_config.roots(target),
+                    &target.conf_file.enabled(),
+                    &session,
+                    *mode,
+                ) {
+                    if let Some(result) = results
+                        .iter_mut()
+                        .find(|r| r.file_id == project_result.file_id)
+                    {
+                        result.reports.extend(project_result.reports);
+                    }
+                }
             }
-            std::process::exit(1);
         }
 
-        std::process::exit(0);
+        results.retain(|lr| !lr.reports.is_empty());
+
+        // findings are a single list, empty if there are none
+        let is_nix = matches!(check_config.format, OutFormat::Nix);
+        if is_nix {
+            println!("[");
+        }
+        let found = !results.is_empty();
+        if found {
+            let groups = group::group(results, &vfs, check_config.group_by, check_config.sort);
+            for group in &groups {
+                if let (Some(title), OutFormat::StdErr) = (&group.title, check_config.format) {
+                    println!("{}\n", title);
+                }
+                for r in &group.results {
+                    stdout
+                        .write(r, &vfs, check_config.format, check_config.path_style)
+                        .unwrap();
+                }
+            }
+        }
+        if is_nix {
+            println!("]");
+        }
+
+        std::process::exit(if found { 1 } else { 0 });
     }
 }
@@ -1,14 +1,74 @@
 pub mod main {
-    use crate::err::StatixErr;
+    use crate::{config::List as ListConfig, err::StatixErr};
 
     use lib::LINTS;
 
-    pub fn main() -> Result<(), StatixErr> {
+    pub fn main(list_config: ListConfig) -> Result<(), StatixErr> {
         let mut lints = (*LINTS).clone();
         lints.as_mut_slice().sort_by_key(|a| a.code());
+
+        if list_config.names {
+            for l in lints {
+                println!("{}", l.name());
+            }
+            return Ok(());
+        }
+
+        #[cfg(feature = "json")]
+        if list_config.json {
+            println!("{}", json::to_string(&lints));
+            return Ok(());
+        }
+
+        let name_width = lints.iter().map(|l| l.name().len()).max().unwrap_or(0);
         for l in lints {
-            println!("W{:02} {}", l.code(), l.name());
+            let kinds = match l.match_kind().as_slice() {
+                [] => "project".to_owned(),
+                kinds => kinds
+                    .iter()
+                    .map(|k| format!("{:?}", k))
+                    .collect::<Vec<_>>()
+                    .join(","),
+            };
+            println!(
+                "W{:02} {:name_width$} {:5} {} ({})",
+                l.code(),
+                l.name(),
+                l.severity(),
+                l.note(),
+                kinds,
+                name_width = name_width
+            );
         }
         Ok(())
     }
+
+    #[cfg(feature = "json")]
+    mod json {
+        use lib::{Lint, Severity};
+        use serde::Serialize;
+
+        #[derive(Serialize)]
+        struct JsonLint {
+            code: u32,
+            name: &'static str,
+            severity: Severity,
+            note: &'static str,
+            match_kind: Vec<String>,
+        }
+
+        pub fn to_string(lints: &[&'static dyn Lint]) -> String {
+            let out = lints
+                .iter()
+                .map(|l| JsonLint {
+                    code: l.code(),
+                    name: l.name(),
+                    severity: l.severity(),
+                    note: l.note(),
+                    match_kind: l.match_kind().iter().map(|k| format!("{:?}", k)).collect(),
+                })
+                .collect::<Vec<_>>();
+            serde_json::to_string_pretty(&out).unwrap()
+        }
+    }
 }
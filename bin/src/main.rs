@@ -4,7 +4,7 @@ use clap::Parser;
 use statix::{
     config::{Opts, SubCommand},
     err::StatixErr,
-    lint, fix, explain, dump, list,
+    lint, fix, explain, dump, list, doc, completions, man,
 };
 
 fn _main() -> Result<(), StatixErr> {
@@ -14,8 +14,11 @@ fn _main() -> Result<(), StatixErr> {
         SubCommand::Fix(config) => fix::main::all(config),
         SubCommand::Single(config) => fix::main::single(config),
         SubCommand::Explain(config) => explain::main::main(config),
-        SubCommand::Dump(_) => dump::main::main(),
-        SubCommand::List(_) => list::main::main(),
+        SubCommand::Dump(config) => dump::main::main(config),
+        SubCommand::List(config) => list::main::main(config),
+        SubCommand::Doc(config) => doc::main::main(config),
+        SubCommand::Completions(config) => completions::main::main(config),
+        SubCommand::Man(config) => man::main::main(config),
     }
 }
 
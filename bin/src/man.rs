@@ -0,0 +1,246 @@
+use std::fmt::Write;
+
+use crate::config::Opts;
+
+use clap::{App, Arg, ArgSettings, IntoApp};
+use lib::LINTS;
+
+const BIN: &str = "statix";
+
+fn escape(s: &str) -> String {
+    let s = s.replace('\\', "\\e").replace('-', "\\-");
+    if s.starts_with('.') || s.starts_with('\'') {
+        format!("\\&{}", s)
+    } else {
+        s
+    }
+}
+
+fn text(s: Option<&str>) -> String {
+    escape(
+        &s.unwrap_or_default()
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" "),
+    )
+}
+
+fn bold(s: &str) -> String {
+    format!("\\fB{}\\fR", escape(s))
+}
+
+fn italic(s: &str) -> String {
+    format!("\\fI{}\\fR", escape(s))
+}
+
+fn title(out: &mut String, name: &str, about: Option<&str>) {
+    writeln!(
+        out,
+        ".TH {} 1 \"\" \"{} {}\" \"User Commands\"",
+        escape(&name.to_uppercase()),
+        BIN,
+        env!("CARGO_PKG_VERSION")
+    )
+    .unwrap();
+    writeln!(out, ".SH NAME").unwrap();
+    writeln!(out, "{} \\- {}", escape(name), text(about)).unwrap();
+}
+
+fn value_name(arg: &Arg) -> String {
+    arg.get_value_names()
+        .and_then(|names| names.first())
+        .map(|n| n.to_string())
+        .unwrap_or_else(|| arg.get_name().to_uppercase())
+}
+
+fn args<'a, 'help>(app: &'a App<'help>) -> impl Iterator<Item = &'a Arg<'help>> {
+    app.get_arguments()
+        .filter(|a| !a.is_set(ArgSettings::Hidden))
+        .filter(|a| !matches!(a.get_name(), "help" | "version"))
+}
+
+fn synopsis(out: &mut String, app: &App) {
+    writeln!(out, ".SH SYNOPSIS").unwrap();
+    write!(out, "{} {}", bold(BIN), bold(app.get_name())).unwrap();
+    if args(app).any(|a| !a.is_positional()) {
+        write!(out, " [{}]", italic("OPTIONS")).unwrap();
+    }
+    for arg in args(app).filter(|a| a.is_positional()) {
+        if arg.is_set(ArgSettings::Required) {
+            write!(out, " <{}>", italic(&value_name(arg))).unwrap();
+        } else {
+            write!(out, " [{}]", italic(&value_name(arg))).unwrap();
+        }
+    }
+    writeln!(out).unwrap();
+}
+
+fn options(out: &mut String, app: &App) {
+    let (positionals, opts): (Vec<_>, Vec<_>) = args(app).partition(|a| a.is_positional());
+    if !positionals.is_empty() {
+        writeln!(out, ".SH ARGUMENTS").unwrap();
+        for arg in positionals {
+            writeln!(out, ".TP").unwrap();
+            writeln!(out, "{}", italic(&value_name(arg))).unwrap();
+            describe(out, arg);
+        }
+    }
+    writeln!(out, ".SH OPTIONS").unwrap();
+    for arg in opts {
+        let flags = arg
+            .get_short()
+            .map(|s| bold(&format!("-{}", s)))
+            .into_iter()
+            .chain(arg.get_long().map(|l| bold(&format!("--{}", l))))
+            .collect::<Vec<_>>()
+            .join(", ");
+        writeln!(out, ".TP").unwrap();
+        if arg.is_set(ArgSettings::TakesValue) {
+            writeln!(out, "{} {}", flags, italic(&value_name(arg))).unwrap();
+        } else {
+            writeln!(out, "{}", flags).unwrap();
+        }
+        describe(out, arg);
+    }
+    writeln!(out, ".TP").unwrap();
+    writeln!(out, "{}, {}", bold("-h"), bold("--help")).unwrap();
+    writeln!(out, "Print help information").unwrap();
+}
+
+fn describe(out: &mut String, arg: &Arg) {
+    writeln!(out, "{}", text(arg.get_long_help().or(arg.get_help()))).unwrap();
+    if let Some(values) = arg.get_possible_values() {
+        let values = values
+            .iter()
+            .filter(|v| !v.is_hidden())
+            .map(|v| v.get_name())
+            .collect::<Vec<_>>();
+        writeln!(out, ".br").unwrap();
+        writeln!(out, "Possible values: {}", escape(&values.join(", "))).unwrap();
+    }
+    let defaults = arg.get_default_values();
+    if !defaults.is_empty() {
+        let defaults = defaults
+            .iter()
+            .map(|d| d.to_string_lossy())
+            .collect::<Vec<_>>();
+        writeln!(out, ".br").unwrap();
+        writeln!(out, "Default: {}", escape(&defaults.join(", "))).unwrap();
+    }
+}
+
+fn lints(out: &mut String) {
+    let mut lints = (*LINTS).clone();
+    lints.as_mut_slice().sort_by_key(|l| l.code());
+    writeln!(out, ".SH LINTS").unwrap();
+    writeln!(
+        out,
+        "Lints are enabled by default, disable them by name in \\fIstatix.toml\\fR. Run {} for a detailed explanation of a lint.",
+        bold("statix explain <code or name>")
+    )
+    .unwrap();
+    for lint in lints {
+        writeln!(out, ".TP").unwrap();
+        writeln!(
+            out,
+            "{} {} ({})",
+            bold(&format!("W{:02}", lint.code())),
+            bold(lint.name()),
+            lint.severity()
+        )
+        .unwrap();
+        writeln!(out, "{}", text(Some(lint.note()))).unwrap();
+    }
+}
+
+fn see_also(out: &mut String, pages: &[String]) {
+    writeln!(out, ".SH SEE ALSO").unwrap();
+    let refs = pages
+        .iter()
+        .map(|p| format!("{}(1)", bold(p)))
+        .collect::<Vec<_>>();
+    writeln!(out, "{}", refs.join(", ")).unwrap();
+}
+
+/// The top-level `statix(1)` page, listing subcommands and lints
+pub fn page() -> String {
+    let app = Opts::into_app();
+    let mut out = String::new();
+    title(&mut out, BIN, app.get_about());
+    writeln!(out, ".SH SYNOPSIS").unwrap();
+    writeln!(out, "{} <{}>", bold(BIN), italic("SUBCOMMAND")).unwrap();
+    writeln!(out, ".SH COMMANDS").unwrap();
+    for sub in app.get_subcommands() {
+        writeln!(out, ".TP").unwrap();
+        writeln!(out, "{}(1)", bold(&format!("{}-{}", BIN, sub.get_name()))).unwrap();
+        writeln!(out, "{}", text(sub.get_about())).unwrap();
+    }
+    writeln!(out, ".SH OPTIONS").unwrap();
+    writeln!(out, ".TP").unwrap();
+    writeln!(out, "{}, {}", bold("-h"), bold("--help")).unwrap();
+    writeln!(out, "Print help information").unwrap();
+    writeln!(out, ".TP").unwrap();
+    writeln!(out, "{}, {}", bold("-V"), bold("--version")).unwrap();
+    writeln!(out, "Print version information").unwrap();
+    lints(&mut out);
+    writeln!(out, ".SH AUTHORS").unwrap();
+    writeln!(out, "{}", escape(env!("CARGO_PKG_AUTHORS"))).unwrap();
+    out
+}
+
+/// `statix(1)` followed by one page per subcommand, as (file name, contents)
+pub fn pages() -> Vec<(String, String)> {
+    let app = Opts::into_app();
+    let mut pages = vec![(format!("{}.1", BIN), page())];
+    for sub in app.get_subcommands() {
+        let name = format!("{}-{}", BIN, sub.get_name());
+        let mut out = String::new();
+        title(&mut out, &name, sub.get_about());
+        synopsis(&mut out, sub);
+        options(&mut out, sub);
+        see_also(&mut out, &[BIN.to_owned()]);
+        pages.push((format!("{}.1", name), out));
+    }
+    pages
+}
+
+pub mod main {
+    use std::fs;
+
+    use crate::{
+        config::Man as ManConfig,
+        err::{ManErr, StatixErr},
+    };
+
+    pub fn main(man_config: ManConfig) -> Result<(), StatixErr> {
+        match &man_config.out_dir {
+            Some(out_dir) => {
+                fs::create_dir_all(out_dir).map_err(ManErr::InvalidPath)?;
+                for (file_name, contents) in super::pages() {
+                    fs::write(out_dir.join(file_name), contents).map_err(ManErr::InvalidPath)?;
+                }
+            }
+            None => print!("{}", super::page()),
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_roff() {
+        assert_eq!(escape("statix-check"), "statix\\-check");
+        assert_eq!(escape(".gitignore"), "\\&.gitignore");
+    }
+
+    #[test]
+    fn one_page_per_subcommand() {
+        let pages = pages();
+        assert_eq!(pages[0].0, "statix.1");
+        assert!(pages.iter().any(|(name, _)| name == "statix-check.1"));
+        assert!(LINTS.iter().all(|l| pages[0].1.contains(&escape(l.name()))));
+    }
+}
@@ -0,0 +1,125 @@
+use std::{ops::Range, path::Path};
+
+use crate::{
+    lint::{lint_with, LintResult},
+    LintMap,
+};
+
+use lib::session::SessionInfo;
+use vfs::{Encoding, VfsEntry};
+
+/// Whether a file is linted for its ```` ```nix ```` blocks, see `check --markdown`
+pub fn is_markdown(path: &Path) -> bool {
+    matches!(path.extension(), Some(e) if e == "md")
+}
+
+/// Lint every nix block of a markdown file on its own, the diagnostics
+/// point into the markdown file
+pub fn lint_blocks(vfs_entry: VfsEntry, lints: &LintMap, sess: &SessionInfo) -> LintResult {
+    let mut result = LintResult {
+        file_id: vfs_entry.file_id,
+        reports: Vec::new(),
+        partial: false,
+    };
+    for block in nix_blocks(vfs_entry.contents) {
+        // blank out everything but the block, so that offsets in it are
+        // offsets in the markdown file
+        let contents = mask(vfs_entry.contents, block);
+        let entry = VfsEntry {
+            contents: &contents,
+            encoding: Encoding::default(),
+            ..vfs_entry
+        };
+        let block_result = lint_with(entry, lints, sess);
+        result.reports.extend(block_result.reports);
+        result.partial |= block_result.partial;
+    }
+    result
+}
+
+// the contents of fenced code blocks tagged `nix`
+fn nix_blocks(src: &str) -> Vec<Range<usize>> {
+    let mut blocks = Vec::new();
+    // the fence of the open block, and where its contents start
+    let mut open: Option<(&str, usize, bool)> = None;
+    let mut offset = 0;
+    for line in src.split_inclusive('\n') {
+        let trimmed = line.trim();
+        match open {
+            Some((fence, start, is_nix)) => {
+                if trimmed.starts_with(fence) && trimmed.trim_start_matches(&fence[..1]).is_empty()
+                {
+                    if is_nix {
+                        blocks.push(start..offset);
+                    }
+                    open = None;
+                }
+            }
+            None => {
+                let marker = match trimmed.chars().next() {
+                    Some(c @ '`') | Some(c @ '~') => c,
+                    _ => {
+                        offset += line.len();
+                        continue;
+                    }
+                };
+                let len = trimmed.len() - trimmed.trim_start_matches(marker).len();
+                if len >= 3 {
+                    let info = trimmed[len..].trim_start();
+                    let lang = info
+                        .split(|c: char| c.is_whitespace() || c == '{' || c == ',')
+                        .next()
+                        .unwrap_or_default();
+                    open = Some((&trimmed[..len], offset + line.len(), lang == "nix"));
+                }
+            }
+        }
+        offset += line.len();
+    }
+    if let Some((_, start, true)) = open {
+        blocks.push(start..src.len());
+    }
+    blocks
+}
+
+fn mask(src: &str, keep: Range<usize>) -> String {
+    src.char_indices()
+        .flat_map(|(idx, c)| {
+            let (c, n) = match c {
+                _ if keep.contains(&idx) => (c, 1),
+                '\n' => ('\n', 1),
+                _ => (' ', c.len_utf8()),
+            };
+            (0..n).map(move |_| c)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils;
+
+    use std::path::Path;
+    use vfs::FileId;
+
+    #[test]
+    fn lints_nix_blocks() {
+        let src = "# Usage\n\n```nix\nlet in 1\n```\n\n```sh\nlet in 1\n```\n\n~~~nix\n[ (x == true) ]\n~~~\n";
+        let entry = VfsEntry {
+            file_id: FileId(0),
+            file_path: Path::new("README.md"),
+            contents: src,
+            encoding: Encoding::default(),
+        };
+        let sess = SessionInfo::from_version("2.6".parse().unwrap());
+        let result = lint_blocks(entry, &utils::lint_map(), &sess);
+        let found = result
+            .reports
+            .iter()
+            .flat_map(|r| &r.diagnostics)
+            .map(|d| &src[d.at])
+            .collect::<Vec<_>>();
+        assert_eq!(found, vec!["let in 1", "x == true"]);
+    }
+}
@@ -0,0 +1,406 @@
+use std::{
+    io::{self, Write},
+    process::{Command, Stdio},
+};
+
+use lib::{
+    utils::{call, function_name},
+    Report, Severity,
+};
+use rnix::{
+    types::{Apply, AttrSet, KeyValue, Paren, TypedNode},
+    NodeOrToken, SyntaxKind, SyntaxNode, TextRange, TextSize,
+};
+
+/// Code of the findings of shellcheck, apart from the codes of lints
+/// and the 0 of syntax errors
+pub const CODE: u32 = 100;
+
+// attributes of a derivation holding shell, phases and their hooks
+static SHELL_ATTRS: &[&str] = &[
+    "buildPhase",
+    "checkPhase",
+    "configurePhase",
+    "distPhase",
+    "fixupPhase",
+    "installCheckPhase",
+    "installPhase",
+    "patchPhase",
+    "unpackPhase",
+    "preBuild",
+    "postBuild",
+    "preCheck",
+    "postCheck",
+    "preConfigure",
+    "postConfigure",
+    "preDist",
+    "postDist",
+    "preFixup",
+    "postFixup",
+    "preInstall",
+    "postInstall",
+    "preInstallCheck",
+    "postInstallCheck",
+    "prePatch",
+    "postPatch",
+    "preUnpack",
+    "postUnpack",
+    "shellHook",
+];
+
+// functions whose last argument is a shell script
+static SHELL_FUNCTIONS: &[&str] = &[
+    "runCommand",
+    "runCommandCC",
+    "runCommandLocal",
+    "writeShellScript",
+    "writeShellScriptBin",
+];
+
+// stands in for `${...}` in the script handed to shellcheck
+const PLACEHOLDER: &str = "__nix__";
+
+// variables set by stdenv, and variables set for it
+const EXCLUDE: &str = "SC2034,SC2154";
+
+/// Whether shellcheck can be run, see `check --shellcheck`
+pub fn available() -> io::Result<()> {
+    Command::new("shellcheck")
+        .arg("--version")
+        .stdout(Stdio::null())
+        .status()
+        .map(|_| ())
+}
+
+/// Run shellcheck over the shell scripts embedded in `root`, reporting
+/// its findings at their place in the nix file
+pub fn check(root: &SyntaxNode) -> Vec<Report> {
+    let mut reports = Vec::<Report>::new();
+    for script in root.descendants().filter_map(|node| Script::new(&node)) {
+        let output = match run(&script.text) {
+            Some(o) => o,
+            None => continue,
+        };
+        for finding in output.lines().filter_map(parse_line) {
+            let severity = match finding.level {
+                "error" => Severity::Error,
+                "warning" => Severity::Warn,
+                _ => Severity::Hint,
+            };
+            let at = match script.range(finding.line, finding.column) {
+                Some(at) => at,
+                None => continue,
+            };
+            let message = format!("{} [{}]", finding.message, finding.code);
+            match reports.iter_mut().find(|r| r.severity == severity) {
+                Some(report) => report.diagnostics.push(lib::Diagnostic::new(at, message)),
+                None => reports.push(
+                    Report::new("Found an issue in embedded shell", CODE)
+                        .severity(severity)
+                        .diagnostic(at, message),
+                ),
+            }
+        }
+    }
+    reports
+}
+
+fn run(text: &str) -> Option<String> {
+    let mut child = Command::new("shellcheck")
+        .args(["--shell=bash", "--format=gcc", "--exclude", EXCLUDE, "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    let mut stdin = child.stdin.take().unwrap();
+    let input = text.to_owned();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output().ok()?;
+    writer.join().unwrap().ok()?;
+    String::from_utf8(output.stdout).ok()
+}
+
+struct Finding<'a> {
+    line: usize,
+    column: usize,
+    level: &'a str,
+    message: &'a str,
+    code: &'a str,
+}
+
+// `-:3:5: warning: Double quote to prevent globbing and word splitting. [SC2086]`
+fn parse_line(line: &str) -> Option<Finding<'_>> {
+    let mut parts = line.strip_prefix("-:")?.splitn(4, ':');
+    let line = parts.next()?.parse().ok()?;
+    let column = parts.next()?.parse().ok()?;
+    let level = parts.next()?.trim();
+    let rest = parts.next()?.trim();
+    let (message, code) = rest.rsplit_once(" [")?;
+    Some(Finding {
+        line,
+        column,
+        level,
+        message,
+        code: code.strip_suffix(']')?,
+    })
+}
+
+/// The contents of a nix string as a shell script, with the place of
+/// each of its parts in the nix file
+struct Script {
+    text: String,
+    // offset in `text`, offset in the file, and whether the part was
+    // copied verbatim or stands in for an escape or an interpolation
+    parts: Vec<(usize, TextSize, bool)>,
+}
+
+impl Script {
+    fn new(node: &SyntaxNode) -> Option<Self> {
+        if node.kind() != SyntaxKind::NODE_STRING || !holds_shell(node) {
+            return None;
+        }
+        let indented = node
+            .first_token()
+            .is_some_and(|t| t.kind() == SyntaxKind::TOKEN_STRING_START && t.text() == "''");
+        let mut script = Self {
+            text: String::new(),
+            parts: Vec::new(),
+        };
+        for child in node.children_with_tokens() {
+            match child {
+                NodeOrToken::Token(token) if token.kind() == SyntaxKind::TOKEN_STRING_CONTENT => {
+                    script.push_content(token.text(), token.text_range().start(), indented);
+                }
+                NodeOrToken::Node(node) if node.kind() == SyntaxKind::NODE_STRING_INTERPOL => {
+                    let start = script.text.len();
+                    script.parts.push((start, node.text_range().start(), false));
+                    script.text.push_str(PLACEHOLDER);
+                }
+                _ => (),
+            }
+        }
+        Some(script)
+    }
+
+    // the text of a string as the shell sees it, `''$` as `$` and `\"`
+    // as `"`
+    fn push_content(&mut self, raw: &str, at: TextSize, indented: bool) {
+        let mut offset = 0;
+        while offset < raw.len() {
+            let rest = &raw[offset..];
+            let next = if indented {
+                rest.find("''")
+            } else {
+                rest.find('\\')
+            };
+            let verbatim = &rest[..next.unwrap_or(rest.len())];
+            if !verbatim.is_empty() {
+                let in_file = at + TextSize::from(offset as u32);
+                self.parts.push((self.text.len(), in_file, true));
+                self.text.push_str(verbatim);
+                offset += verbatim.len();
+            }
+            if next.is_some() {
+                let (len, unescaped) = unescape(&raw[offset..], indented);
+                let in_file = at + TextSize::from(offset as u32);
+                self.parts.push((self.text.len(), in_file, false));
+                self.text.push_str(&unescaped);
+                offset += len;
+            }
+        }
+    }
+
+    // offset in the file of an offset in the script
+    fn offset(&self, at: usize) -> Option<TextSize> {
+        let &(start, in_file, verbatim) = self.parts.iter().rev().find(|p| p.0 <= at)?;
+        if verbatim {
+            Some(in_file + TextSize::from((at - start) as u32))
+        } else {
+            Some(in_file)
+        }
+    }
+
+    // the word starting at a line and column reported by shellcheck,
+    // both 1-based, tabs counted up to the next multiple of 8
+    fn range(&self, line: usize, column: usize) -> Option<TextRange> {
+        let line_start = self
+            .text
+            .split_inclusive('\n')
+            .take(line - 1)
+            .map(str::len)
+            .sum::<usize>();
+        let mut width = 1;
+        let mut start = None;
+        for (idx, c) in self.text[line_start..].char_indices() {
+            if width >= column || c == '\n' {
+                start = Some(line_start + idx);
+                break;
+            }
+            width = if c == '\t' {
+                width.div_ceil(8) * 8 + 1
+            } else {
+                width + 1
+            };
+        }
+        let start = start.unwrap_or(self.text.len());
+        let len = self.text[start..]
+            .find(char::is_whitespace)
+            .unwrap_or(self.text.len() - start);
+        let (from, to) = (self.offset(start)?, self.offset(start + len)?);
+        Some(TextRange::new(from, to.max(from)))
+    }
+}
+
+// the length of the escape `escape` starts with, and the text it stands
+// for: `''$`, `'''` and `''\n` in indented strings, `\"` and `\n` in
+// others
+fn unescape(escape: &str, indented: bool) -> (usize, String) {
+    let (prefix, rest) = if indented {
+        match escape.strip_prefix("''") {
+            Some(rest) if rest.starts_with('$') => return (3, "$".to_owned()),
+            Some(rest) if rest.starts_with('\'') => return (3, "''".to_owned()),
+            Some(rest) => match rest.strip_prefix('\\') {
+                Some(rest) => (3, rest),
+                None => return (2, "''".to_owned()),
+            },
+            None => return (escape.len(), escape.to_owned()),
+        }
+    } else {
+        match escape.strip_prefix('\\') {
+            Some(rest) => (1, rest),
+            None => return (escape.len(), escape.to_owned()),
+        }
+    };
+    match rest.chars().next() {
+        Some('n') => (prefix + 1, "\n".to_owned()),
+        Some('r') => (prefix + 1, "\r".to_owned()),
+        Some('t') => (prefix + 1, "\t".to_owned()),
+        Some(c) => (prefix + c.len_utf8(), c.to_string()),
+        None => (prefix, String::new()),
+    }
+}
+
+// whether a string is the value of a phase, or the script of
+// `writeShellScript` and friends
+fn holds_shell(string: &SyntaxNode) -> bool {
+    let mut value = string.clone();
+    while let Some(paren) = value.parent().and_then(Paren::cast) {
+        value = paren.node().clone();
+    }
+    let parent = match value.parent() {
+        Some(p) => p,
+        None => return false,
+    };
+    if let Some(kv) = KeyValue::cast(parent.clone()) {
+        let key = kv
+            .key()
+            .and_then(|k| k.path().last())
+            .map(|k| k.to_string());
+        return match key.as_deref() {
+            Some(key) if SHELL_ATTRS.contains(&key) => true,
+            // `writeShellApplication { text = ''...''; }`
+            Some("text") => parent
+                .parent()
+                .and_then(AttrSet::cast)
+                .and_then(|set| set.node().parent())
+                .and_then(Apply::cast)
+                .and_then(|apply| apply.lambda())
+                .is_some_and(|f| function_name(&f).as_deref() == Some("writeShellApplication")),
+            _ => false,
+        };
+    }
+    // the last argument of `writeShellScript "name" ''...''`
+    let apply = match Apply::cast(parent) {
+        Some(a) => a,
+        None => return false,
+    };
+    let is_argument = apply.value().as_ref() == Some(&value);
+    let is_last = apply.node().parent().and_then(Apply::cast).is_none();
+    is_argument
+        && is_last
+        && apply
+            .lambda()
+            .and_then(|f| function_name(&call(&f).0))
+            .is_some_and(|name| SHELL_FUNCTIONS.contains(&name.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scripts(src: &str) -> Vec<Script> {
+        let root = rnix::parse(src).node();
+        root.descendants().filter_map(|n| Script::new(&n)).collect()
+    }
+
+    #[test]
+    fn finds_scripts() {
+        let src = r#"{
+          installPhase = ''
+            cp ${foo}/bin $out
+          '';
+          name = "not shell";
+          a = writeShellScript "name" "echo $1";
+          b = writeShellApplication { name = "x"; text = "ls"; };
+        }"#;
+        let texts = scripts(src).into_iter().map(|s| s.text).collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec![
+                "\n            cp __nix__/bin $out\n          ",
+                "echo $1",
+                "ls"
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_positions() {
+        let src = "{ buildPhase = ''\n\tmake ${flags} $x\n''; }";
+        let script = scripts(src).pop().unwrap();
+        // `$x`, after a tab, the interpolation and a space
+        let at = script.range(2, 22).unwrap();
+        assert_eq!(&src[at], "$x");
+        // the interpolation
+        let at = script.range(2, 14).unwrap();
+        assert_eq!(usize::from(at.start()), src.find("${").unwrap());
+    }
+
+    #[test]
+    fn unescapes_strings() {
+        let src = r#"{
+          installPhase = ''
+            echo ''${HOME} '''quoted''' ''\t.
+          '';
+          a = writeShellScript "name" "echo \"$1\" \${x}";
+        }"#;
+        let texts = scripts(src).into_iter().map(|s| s.text).collect::<Vec<_>>();
+        assert_eq!(
+            texts,
+            vec![
+                "\n            echo ${HOME} ''quoted'' \t.\n          ",
+                "echo \"$1\" ${x}"
+            ]
+        );
+
+        // `$y`, after the escaped quotes around `$x`
+        let src = r#"{ buildPhase = "echo \"$x\" $y"; }"#;
+        let script = scripts(src).pop().unwrap();
+        let at = script.range(1, 11).unwrap();
+        assert_eq!(&src[at], "$y");
+        let at = script.range(1, 6).unwrap();
+        assert_eq!(&src[at], r#"\"$x\""#);
+    }
+
+    #[test]
+    fn parses_gcc_format() {
+        let line = "-:3:5: warning: Double quote to prevent globbing. [SC2086]";
+        let finding = parse_line(line).unwrap();
+        assert_eq!(
+            (finding.line, finding.column, finding.level, finding.code),
+            (3, 5, "warning", "SC2086")
+        );
+        assert_eq!(finding.message, "Double quote to prevent globbing.");
+    }
+}
@@ -1,15 +1,19 @@
 use std::{
     io::{self, Write},
+    path::Path,
     str,
 };
 
-use crate::{config::OutFormat, lint::LintResult};
+use crate::{
+    config::{OutFormat, PathStyle},
+    lint::LintResult,
+};
 
 use ariadne::{
     CharSet, Color, Config as CliConfig, Fmt, Label, LabelAttach, Report as CliReport,
     ReportKind as CliReportKind, Source,
 };
-use lib::Severity;
+use lib::{Related, Severity};
 use rnix::{TextRange, TextSize};
 use vfs::ReadOnlyVfs;
 
@@ -19,6 +23,7 @@ pub trait WriteDiagnostic {
         report: &LintResult,
         vfs: &ReadOnlyVfs,
         format: OutFormat,
+        path_style: PathStyle,
     ) -> io::Result<()>;
 }
 
@@ -31,12 +36,15 @@ where
         lint_result: &LintResult,
         vfs: &ReadOnlyVfs,
         format: OutFormat,
+        path_style: PathStyle,
     ) -> io::Result<()> {
+        let path = path_style.display(vfs.file_path(lint_result.file_id));
         match format {
             #[cfg(feature = "json")]
-            OutFormat::Json => json::write_json(self, lint_result, vfs),
-            OutFormat::StdErr => write_stderr(self, lint_result, vfs),
-            OutFormat::Errfmt => write_errfmt(self, lint_result, vfs),
+            OutFormat::Json => json::write_json(self, lint_result, vfs, &path),
+            OutFormat::StdErr => write_stderr(self, lint_result, vfs, &path),
+            OutFormat::Errfmt => write_errfmt(self, lint_result, vfs, &path),
+            OutFormat::Nix => write_nix(self, lint_result, vfs, &path),
         }
     }
 }
@@ -45,10 +53,10 @@ fn write_stderr<T: Write>(
     writer: &mut T,
     lint_result: &LintResult,
     vfs: &ReadOnlyVfs,
+    path: &Path,
 ) -> io::Result<()> {
     let file_id = lint_result.file_id;
     let src = str::from_utf8(vfs.get(file_id)).unwrap();
-    let path = vfs.file_path(file_id);
     let range = |at: TextRange| at.start().into()..at.end().into();
     let src_id = path.to_str().unwrap_or("<unknown>");
     for report in lint_result.reports.iter() {
@@ -58,35 +66,68 @@ fn write_stderr<T: Write>(
             .map(|d| d.at.start().into())
             .min()
             .unwrap_or(0usize);
+        // places several diagnostics refer to are labelled once
+        let mut related = Vec::<&Related>::new();
+        for r in report.diagnostics.iter().flat_map(|d| &d.related) {
+            if !related
+                .iter()
+                .any(|s| s.at == r.at && s.message == r.message)
+            {
+                related.push(r);
+            }
+        }
         let report_kind = match report.severity {
             Severity::Warn => CliReportKind::Warning,
             Severity::Error => CliReportKind::Error,
             Severity::Hint => CliReportKind::Advice,
         };
-        report
-            .diagnostics
-            .iter()
-            .fold(
-                CliReport::build(report_kind, src_id, offset)
-                    .with_config(
-                        CliConfig::default()
-                            .with_cross_gap(true)
-                            .with_multiline_arrows(false)
-                            .with_label_attach(LabelAttach::Middle)
-                            .with_char_set(CharSet::Unicode),
-                    )
-                    .with_message(report.note)
-                    .with_code(report.code),
-                |cli_report, diagnostic| {
-                    cli_report.with_label(
-                        Label::new((src_id, range(diagnostic.at)))
-                            .with_message(&colorize(&diagnostic.message))
-                            .with_color(Color::Magenta),
-                    )
-                },
-            )
+        let cli_report = report.diagnostics.iter().fold(
+            CliReport::build(report_kind, src_id, offset)
+                .with_config(
+                    CliConfig::default()
+                        .with_cross_gap(true)
+                        .with_multiline_arrows(false)
+                        .with_label_attach(LabelAttach::Middle)
+                        .with_char_set(CharSet::Unicode),
+                )
+                .with_message(report.note)
+                .with_code(report.code),
+            |cli_report, diagnostic| {
+                cli_report.with_label(
+                    Label::new((src_id, range(diagnostic.at)))
+                        .with_message(colorize(&diagnostic.message))
+                        .with_color(Color::Magenta),
+                )
+            },
+        );
+        related
+            .into_iter()
+            .fold(cli_report, |cli_report, related| {
+                cli_report.with_label(
+                    Label::new((src_id, range(related.at)))
+                        .with_message(colorize(&related.message))
+                        .with_color(Color::Blue),
+                )
+            })
             .finish()
             .write((src_id, Source::from(src)), &mut *writer)?;
+        for description in report
+            .diagnostics
+            .iter()
+            .filter_map(|d| d.suggestion.as_ref()?.description.as_ref())
+        {
+            writeln!(writer, "    = fix: {}", description)?;
+        }
+        if let Some(docs_url) = report.docs_url {
+            writeln!(writer, "    = see {}", docs_url)?;
+        }
+    }
+    if lint_result.partial {
+        writeln!(
+            writer,
+            "note: {} has syntax errors, the parts that could not be parsed were not linted",
+            src_id
+        )?;
     }
     Ok(())
 }
@@ -95,12 +136,26 @@ fn write_errfmt<T: Write>(
     writer: &mut T,
     lint_result: &LintResult,
     vfs: &ReadOnlyVfs,
+    path: &Path,
 ) -> io::Result<()> {
     let file_id = lint_result.file_id;
     let src = str::from_utf8(vfs.get(file_id)).unwrap();
-    let path = vfs.file_path(file_id);
     for report in lint_result.reports.iter() {
         for diagnostic in report.diagnostics.iter() {
+            let mut message = match diagnostic
+                .suggestion
+                .as_ref()
+                .and_then(|s| s.description.as_ref())
+            {
+                Some(description) => format!("{} (fix: {})", diagnostic.message, description),
+                None => diagnostic.message.clone(),
+            };
+            // the other places it refers to, as `(line:column: message)`
+            for related in diagnostic.related.iter() {
+                let start = related.at.start();
+                let (line, col) = (line(start, src), column(start, src));
+                message.push_str(&format!(" ({}:{}: {})", line, col, related.message));
+            }
             let line = line(diagnostic.at.start(), src);
             let col = column(diagnostic.at.start(), src);
             writeln!(
@@ -115,18 +170,90 @@ fn write_errfmt<T: Write>(
                     Severity::Hint => "I", /* "info" message */
                 },
                 errornumber = report.code,
-                errormessage = diagnostic.message
+                errormessage = message
             )?;
         }
     }
     Ok(())
 }
 
+// every diagnostic as an element of a nix list, the list itself is
+// opened and closed by `check`
+fn write_nix<T: Write>(
+    writer: &mut T,
+    lint_result: &LintResult,
+    vfs: &ReadOnlyVfs,
+    path: &Path,
+) -> io::Result<()> {
+    let src = vfs.get_str(lint_result.file_id);
+    let file = nix_string(path.to_str().unwrap_or("<unknown>"));
+    for report in lint_result.reports.iter() {
+        let severity = report.severity.to_string();
+        for diagnostic in report.diagnostics.iter() {
+            let (start, end) = (diagnostic.at.start(), diagnostic.at.end());
+            let (fix, description) = match &diagnostic.suggestion {
+                Some(s) => (
+                    nix_string(&s.fix.to_string()),
+                    s.description
+                        .as_deref()
+                        .map_or_else(|| "null".to_owned(), nix_string),
+                ),
+                None => ("null".to_owned(), "null".to_owned()),
+            };
+            writeln!(writer, "  {{")?;
+            writeln!(writer, "    file = {};", file)?;
+            writeln!(writer, "    line = {};", line(start, src))?;
+            writeln!(writer, "    column = {};", column(start, src))?;
+            writeln!(writer, "    endLine = {};", line(end, src))?;
+            writeln!(writer, "    endColumn = {};", column(end, src))?;
+            writeln!(writer, "    code = {};", report.code)?;
+            writeln!(writer, "    severity = \"{}\";", severity)?;
+            writeln!(writer, "    note = {};", nix_string(report.note))?;
+            writeln!(writer, "    message = {};", nix_string(&diagnostic.message))?;
+            writeln!(writer, "    fix = {};", fix)?;
+            writeln!(writer, "    fixDescription = {};", description)?;
+            writeln!(writer, "    related = [")?;
+            for related in diagnostic.related.iter() {
+                let (start, end) = (related.at.start(), related.at.end());
+                writeln!(writer, "      {{")?;
+                writeln!(writer, "        line = {};", line(start, src))?;
+                writeln!(writer, "        column = {};", column(start, src))?;
+                writeln!(writer, "        endLine = {};", line(end, src))?;
+                writeln!(writer, "        endColumn = {};", column(end, src))?;
+                writeln!(
+                    writer,
+                    "        message = {};",
+                    nix_string(&related.message)
+                )?;
+                writeln!(writer, "      }}")?;
+            }
+            writeln!(writer, "    ];")?;
+            writeln!(writer, "    partial = {};", lint_result.partial)?;
+            writeln!(writer, "  }}")?;
+        }
+    }
+    Ok(())
+}
+
+fn nix_string(s: &str) -> String {
+    let escaped = s
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace("${", "\\${")
+        .replace('\n', "\\n")
+        .replace('\r', "\\r")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
 #[cfg(feature = "json")]
 mod json {
     use crate::lint::LintResult;
 
-    use std::io::{self, Write};
+    use std::{
+        io::{self, Write},
+        path::Path,
+    };
 
     use lib::Severity;
     use rnix::TextRange;
@@ -138,27 +265,45 @@ mod json {
         #[serde(rename = "file")]
         path: &'μ std::path::Path,
         report: Vec<JsonReport<'μ>>,
+        partial: bool,
     }
 
     #[derive(Serialize)]
     struct JsonReport<'μ> {
         note: &'static str,
         code: u32,
+        #[serde(rename = "codeDescription", skip_serializing_if = "Option::is_none")]
+        code_description: Option<CodeDescription>,
         severity: &'μ Severity,
         diagnostics: Vec<JsonDiagnostic<'μ>>,
     }
 
+    #[derive(Serialize)]
+    struct CodeDescription {
+        href: &'static str,
+    }
+
     #[derive(Serialize)]
     struct JsonDiagnostic<'μ> {
         at: JsonSpan,
         message: &'μ String,
-        suggestion: Option<JsonSuggestion>,
+        suggestion: Option<JsonSuggestion<'μ>>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        related: Vec<JsonRelated<'μ>>,
     }
 
     #[derive(Serialize)]
-    struct JsonSuggestion {
+    struct JsonRelated<'μ> {
+        at: JsonSpan,
+        message: &'μ String,
+    }
+
+    #[derive(Serialize)]
+    struct JsonSuggestion<'μ> {
         at: JsonSpan,
         fix: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        description: Option<&'μ String>,
     }
 
     #[derive(Serialize)]
@@ -193,9 +338,9 @@ mod json {
         writer: &mut T,
         lint_result: &LintResult,
         vfs: &ReadOnlyVfs,
+        path: &Path,
     ) -> io::Result<()> {
         let file_id = lint_result.file_id;
-        let path = vfs.file_path(file_id);
         let src = vfs.get_str(file_id);
         let report = lint_result
             .reports
@@ -203,6 +348,7 @@ mod json {
             .map(|r| {
                 let note = r.note;
                 let code = r.code;
+                let code_description = r.docs_url.map(|href| CodeDescription { href });
                 let severity = &r.severity;
                 let diagnostics = r
                     .diagnostics
@@ -213,12 +359,22 @@ mod json {
                         suggestion: d.suggestion.as_ref().map(|s| JsonSuggestion {
                             at: JsonSpan::from_textrange(s.at, src),
                             fix: s.fix.to_string(),
+                            description: s.description.as_ref(),
                         }),
+                        related: d
+                            .related
+                            .iter()
+                            .map(|r| JsonRelated {
+                                at: JsonSpan::from_textrange(r.at, src),
+                                message: &r.message,
+                            })
+                            .collect(),
                     })
                     .collect::<Vec<_>>();
                 JsonReport {
                     note,
                     code,
+                    code_description,
                     severity,
                     diagnostics,
                 }
@@ -227,7 +383,12 @@ mod json {
         writeln!(
             writer,
             "{}",
-            serde_json::to_string_pretty(&Out { path, report }).unwrap()
+            serde_json::to_string_pretty(&Out {
+                path,
+                report,
+                partial: lint_result.partial
+            })
+            .unwrap()
         )?;
         Ok(())
     }
@@ -258,3 +419,42 @@ fn colorize(message: &str) -> String {
         .collect::<Vec<_>>()
         .join("")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lint;
+
+    use lib::session::SessionInfo;
+
+    #[test]
+    fn nix_output() {
+        let vfs = ReadOnlyVfs::singleton("a.nix", b"[ (x == true) ]");
+        let sess = SessionInfo::from_version("2.6".parse().unwrap());
+        let mut buffer = Vec::new();
+        for entry in vfs.iter() {
+            let result = lint::lint(entry, &sess);
+            write_nix(&mut buffer, &result, &vfs, Path::new("a.nix")).unwrap();
+        }
+        let out = str::from_utf8(&buffer).unwrap();
+        assert!(out.contains("    column = 4;\n    endLine = 1;\n    endColumn = 13;\n"));
+        assert!(out.contains("    fix = \"x\";\n    fixDescription = \"replace with `x`\";\n"));
+        assert_eq!(nix_string("a \"${b}\"\n"), r#""a \"\${b}\"\n""#);
+    }
+
+    #[test]
+    fn related_locations() {
+        let vfs = ReadOnlyVfs::singleton("a.nix", b"{ a = 1; a = 2; }");
+        let sess = SessionInfo::from_version("2.6".parse().unwrap());
+        let mut buffer = Vec::new();
+        for entry in vfs.iter() {
+            let result = lint::lint(entry, &sess);
+            write_errfmt(&mut buffer, &result, &vfs, Path::new("a.nix")).unwrap();
+        }
+        let out = str::from_utf8(&buffer).unwrap();
+        assert_eq!(
+            out,
+            "a.nix>1:10:E:35:`a` is defined again here (1:3: `a` is first defined here)\n"
+        );
+    }
+}
@@ -1,11 +1,16 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    convert::TryFrom,
+    fs,
+    path::{Component, Path, PathBuf},
+};
 
-use lib::{Lint, LINTS};
-use rnix::SyntaxKind;
+use lib::{session::Mode, Lint, LINTS};
+use rnix::{SyntaxKind, TextSize};
 
-pub fn lint_map_of(
-    lints: &[&'static Box<dyn Lint>],
-) -> HashMap<SyntaxKind, Vec<&'static Box<dyn Lint>>> {
+use crate::err::SingleFixErr;
+
+pub fn lint_map_of(lints: &[&'static dyn Lint]) -> HashMap<SyntaxKind, Vec<&'static dyn Lint>> {
     let mut map = HashMap::new();
     for lint in lints.iter() {
         let lint = *lint;
@@ -19,20 +24,147 @@ pub fn lint_map_of(
     map
 }
 
-pub fn lint_map() -> HashMap<SyntaxKind, Vec<&'static Box<dyn Lint>>> {
+pub fn lint_map() -> HashMap<SyntaxKind, Vec<&'static dyn Lint>> {
     lint_map_of(&LINTS)
 }
 
 pub fn get_version_info() -> Option<String> {
     use std::process::Command;
     let program = Command::new("nix").arg("--version").output().ok()?;
+    // the version comes last: `nix (Nix) 2.18.1`, `nix (Lix, like Nix) 2.90.0`
     std::str::from_utf8(&program.stdout)
         .ok()?
-        .split(' ')
-        .nth(2)
+        .split_whitespace()
+        .last()
         .map(ToOwned::to_owned)
 }
 
+/// Minimum Nix version declared by the project containing `path`,
+/// read from a `minver.nix` or `lib/minver.nix` (as in nixpkgs)
+/// in `path` or its parents, up to the repository root. The
+/// `nixConfig` of `flake.nix` has no setting for it, and is not read.
+pub fn get_minver_hint(path: &Path) -> Option<String> {
+    let path = fs::canonicalize(path).ok()?;
+    for dir in path.ancestors().filter(|p| p.is_dir()) {
+        for candidate in [dir.join("minver.nix"), dir.join("lib").join("minver.nix")] {
+            if let Ok(src) = fs::read_to_string(&candidate) {
+                return parse_minver(&src);
+            }
+        }
+        if dir.join(".git").exists() {
+            break;
+        }
+    }
+    None
+}
+
+/// Version of the nixpkgs checkout containing `path`, read from the
+/// `.version` file next to `lib/minver.nix` at its root
+pub fn get_nixpkgs_version_hint(path: &Path) -> Option<String> {
+    let path = fs::canonicalize(path).ok()?;
+    path.ancestors()
+        .filter(|p| p.join("lib").join("minver.nix").is_file())
+        .find_map(|p| fs::read_to_string(p.join(".version")).ok())
+        .map(|v| v.trim().to_owned())
+}
+
+// minver.nix is a single string literal, possibly preceded by comments
+fn parse_minver(src: &str) -> Option<String> {
+    src.lines()
+        .map(str::trim)
+        .filter(|l| !l.starts_with('#'))
+        .find_map(|l| l.strip_prefix('"')?.split('"').next())
+        .map(ToOwned::to_owned)
+}
+
+/// Offset of a line (starting at 1) and column (starting at 0) in `src`
+pub fn pos_to_byte(line: usize, col: usize, src: &str) -> Result<TextSize, SingleFixErr> {
+    let mut byte: TextSize = TextSize::of("");
+    for (l, _) in src
+        .split_inclusive('\n')
+        .zip(1..)
+        .take_while(|(_, i)| *i < line)
+    {
+        byte += TextSize::of(l);
+    }
+    byte += TextSize::try_from(col).map_err(|_| SingleFixErr::Conversion(col))?;
+
+    if usize::from(byte) >= src.len() {
+        Err(SingleFixErr::OutOfBounds(line, col))
+    } else {
+        Ok(byte)
+    }
+}
+
+/// Closest directory containing `path` that is the root of a git repository
+pub fn repository_root(path: &Path) -> Option<&Path> {
+    path.ancestors().find(|p| p.join(".git").exists())
+}
+
+/// `path` relative to the directory `base`, both absolute
+pub fn relative_to(path: &Path, base: &Path) -> PathBuf {
+    let path = path.components().collect::<Vec<_>>();
+    let base = base.components().collect::<Vec<_>>();
+    let common = path.iter().zip(&base).take_while(|(a, b)| a == b).count();
+    (common..base.len())
+        .map(|_| Component::ParentDir)
+        .chain(path[common..].iter().copied())
+        .collect()
+}
+
+/// Guess the kind of code in the file at `path` from its location
+pub fn detect_mode(path: &Path) -> Mode {
+    let components = path
+        .components()
+        .filter_map(|c| c.as_os_str().to_str())
+        .collect::<Vec<_>>();
+    let file_name = components.last().copied();
+    if file_name == Some("flake.nix") {
+        Mode::Flake
+    } else if components.windows(2).any(|w| w == ["nixos", "modules"])
+        || matches!(
+            file_name,
+            Some("configuration.nix") | Some("hardware-configuration.nix")
+        )
+    {
+        Mode::NixosModule
+    } else if components.contains(&"pkgs") {
+        Mode::Nixpkgs
+    } else {
+        Mode::Plain
+    }
+}
+
 pub fn default_nix_version() -> String {
     String::from("2.4")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_modes() {
+        let mode = |p: &str| detect_mode(Path::new(p));
+        assert_eq!(mode("flake.nix"), Mode::Flake);
+        assert_eq!(mode("./nixos/modules/services/foo.nix"), Mode::NixosModule);
+        assert_eq!(mode("/etc/nixos/configuration.nix"), Mode::NixosModule);
+        assert_eq!(mode("pkgs/tools/misc/foo/default.nix"), Mode::Nixpkgs);
+        assert_eq!(mode("lib/strings.nix"), Mode::Plain);
+        assert_eq!(mode("<stdin>"), Mode::Plain);
+    }
+
+    #[test]
+    fn relative_paths() {
+        let rel = |p: &str, b: &str| relative_to(Path::new(p), Path::new(b));
+        assert_eq!(rel("/repo/lib/a.nix", "/repo"), Path::new("lib/a.nix"));
+        assert_eq!(
+            rel("/repo/lib/a.nix", "/repo/pkgs"),
+            Path::new("../lib/a.nix")
+        );
+        assert_eq!(
+            rel("/repo/a.nix", "/other/dir"),
+            Path::new("../../repo/a.nix")
+        );
+    }
+}
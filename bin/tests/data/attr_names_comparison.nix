@@ -0,0 +1,13 @@
+{ lib, overrides, config }:
+{
+  plain = builtins.attrNames overrides == [ ];
+  overridden = lib.attrNames overrides != [ ];
+  configured = [ ] != builtins.attrValues config.settings;
+  merged = lib.optional (builtins.attrNames (overrides // config) == [ ]) "default";
+  empty = ((lib.attrValues overrides)) == [ ];
+
+  # left alone
+  single = builtins.attrNames overrides == [ "foo" ];
+  names = builtins.attrNames overrides;
+  other = lib.strings.attrNames overrides == [ ];
+}
@@ -0,0 +1,9 @@
+[
+  (if cfg.enable then true else false)
+  (if cfg.enable then false else true)
+  (if a == b then false else true)
+  (if f x then false else true)
+  (if a ? b then false else true)
+  (if x then true else true)
+  (if x then 1 else false)
+]
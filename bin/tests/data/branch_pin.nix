@@ -0,0 +1,15 @@
+[
+  (fetchFromGitHub {
+    owner = "NixOS";
+    repo = "nix";
+    rev = "master";
+    hash = "sha256-AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+  })
+  (builtins.fetchGit { url = "https://github.com/NixOS/nix"; ref = "main"; })
+  (pkgs.fetchgit { url = "https://example.org/repo.git"; rev = "refs/heads/develop"; hash = ""; })
+
+  # pinned
+  (builtins.fetchGit { url = "https://github.com/NixOS/nix"; ref = "master"; rev = "a3f9625818ecb0c8a3c22c191340dac5a2120b4c"; })
+  (fetchFromGitHub { owner = "NixOS"; repo = "nix"; rev = "2.17.0"; hash = ""; })
+  (fetchFromGitHub { owner = "NixOS"; repo = "nix"; rev = version; hash = ""; })
+]
@@ -0,0 +1,12 @@
+{ pkgs, lib }:
+{
+  nested = with pkgs; with lib; optionals stdenv.isLinux [ hello ];
+  long = with lib.strings; [
+    (concatStrings [ "a" "b" ])
+    "c"
+    "d"
+    "e"
+    "f"
+  ];
+  short = with pkgs; [ hello ];
+}
@@ -0,0 +1,10 @@
+{ pkgs }:
+with pkgs;
+[
+  hello
+  cowsay
+  lolcat
+  figlet
+  fortune
+  sl
+]
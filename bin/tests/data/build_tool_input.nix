@@ -0,0 +1,51 @@
+{ lib, stdenv, cmake, pkg-config, makeWrapper, zlib, openssl, ninja, python3Packages, extraTools }:
+{
+  hello = stdenv.mkDerivation {
+    pname = "hello";
+    version = "2.12";
+    meta = { description = "An example"; license = lib.licenses.mit; };
+    buildInputs = [ cmake pkg-config ];
+  };
+  world = stdenv.mkDerivation {
+    pname = "world";
+    version = "1.0";
+    meta = { description = "An example"; license = lib.licenses.mit; };
+    buildInputs = [
+      zlib
+      cmake
+      openssl
+    ];
+  };
+  greeter = stdenv.mkDerivation {
+    pname = "greeter";
+    version = "1.0";
+    meta = { description = "An example"; license = lib.licenses.mit; };
+    nativeBuildInputs = [ pkg-config ];
+    buildInputs = [ makeWrapper zlib ];
+  };
+  server = python3Packages.buildPythonApplication {
+    pname = "server";
+    version = "1.0";
+    meta = { description = "An example"; license = lib.licenses.mit; };
+    nativeBuildInputs = [
+      pkg-config
+    ];
+    buildInputs = [ ninja ];
+  };
+  client = stdenv.mkDerivation {
+    pname = "client";
+    version = "1.0";
+    meta = { description = "An example"; license = lib.licenses.mit; };
+    nativeBuildInputs = extraTools;
+    buildInputs = [ cmake openssl ];
+  };
+
+  # left alone
+  library = stdenv.mkDerivation {
+    pname = "library";
+    version = "1.0";
+    meta = { description = "An example"; license = lib.licenses.mit; };
+    nativeBuildInputs = [ cmake ];
+    buildInputs = [ zlib ];
+  };
+}
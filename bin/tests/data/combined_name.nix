@@ -0,0 +1,28 @@
+{ stdenv, python3Packages }:
+{
+  hello = stdenv.mkDerivation {
+    name = "hello-2.12";
+    src = ./.;
+  };
+  gnu-hello = stdenv.mkDerivation rec {
+    version = "2.12";
+    name = "gnu-hello-${version}";
+  };
+  world = stdenv.mkDerivation { name = "world-1.0-rc1"; };
+  requests = python3Packages.buildPythonPackage (finalAttrs: {
+    version = "2.31.0";
+    name = "requests-${finalAttrs.version}";
+  });
+  pip = python3Packages.buildPythonPackage (finalAttrs: {
+    version = "23.3";
+    name = "pip-${toString finalAttrs.version}";
+  });
+
+  # left alone
+  greeter = stdenv.mkDerivation {
+    pname = "greeter";
+    version = "1.0";
+    name = "greeter-1.0";
+  };
+  env = stdenv.mkDerivation { name = "build-env"; };
+}
@@ -0,0 +1,15 @@
+let
+  a = if true then 1 else 2;
+  b = if false then [ "--verbose" ] else [ ];
+  c = x + (if true then y * 2 else z);
+  d = assert true; a;
+  e = assert false; a;
+  g = if "x86_64" == "x86_64" then a else b;
+  h = assert (1 != 1); a;
+  i = assert null == null; a;
+  # not a literal, or not the same literal
+  f = if a == 1 then b else c;
+  j = if "${a}" == "${a}" then b else c;
+  k = assert 1 == 1.0; a;
+in
+[ a b c d e f g h i j k ]
@@ -0,0 +1,15 @@
+{
+  description = "current system";
+
+  outputs = { nixpkgs, flake-utils, ... }:
+    let
+      pkgs = nixpkgs.legacyPackages.${builtins.currentSystem};
+    in
+    flake-utils.lib.eachDefaultSystem (system: {
+      packages.default = nixpkgs.legacyPackages.${builtins.currentSystem}.hello;
+    }) // {
+      inherit pkgs;
+      hello = let system = builtins.currentSystem; in nixpkgs.legacyPackages.${system}.hello;
+      forSystem = { system ? builtins.currentSystem }: nixpkgs.legacyPackages.${system};
+    };
+}
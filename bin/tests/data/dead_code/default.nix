@@ -0,0 +1,9 @@
+let
+  lib = import ./lib.nix;
+  # never used
+  unused = 1;
+in
+{
+  four = lib.double 2;
+  inherit (lib) half;
+}
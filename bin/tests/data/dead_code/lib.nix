@@ -0,0 +1,6 @@
+{
+  double = x: x * 2;
+  half = x: x / 2;
+  # not selected by ./default.nix
+  triple = x: x * 3;
+}
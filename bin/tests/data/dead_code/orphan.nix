@@ -0,0 +1,2 @@
+# not imported from ./default.nix
+{ }
@@ -0,0 +1,13 @@
+{ lib, version, pkgs }:
+[
+  (builtins.trace "version: ${version}" version)
+  (lib.traceVal pkgs.hello)
+  (lib.traceIf (version == "1") "old" pkgs.hello.meta)
+  (lib.debug.traceValFn (x: x.name) pkgs.hello)
+  (builtins.trace "applied" lib.id version)
+  (lib.traceSeq version 1 + 2)
+  # allowed
+  (builtins.trace "warning: this is deprecated" version)
+  # partially applied
+  (map (lib.traceValFn toString) [ 1 2 ])
+]
@@ -0,0 +1,16 @@
+[
+  (builtins.unsafeDiscardStringContext "${pkgs.hello}/bin/hello")
+  (builtins.unsafeDiscardStringContext (toString pkgs.hello))
+  (fetchTarball {
+    url = "https://example.org/repo.tar.gz";
+    sha256 = "sha256-AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=";
+  })
+  (builtins.hashString "sha256" "hello")
+
+  # deprecated after the targeted version
+  (builtins.getFlake "nixpkgs")
+  # deprecated by default, but not in the configured list
+  (builtins.fetchMercurial { url = "https://example.org/repo"; })
+  # no derivation in the string
+  (builtins.unsafeDiscardStringContext name)
+]
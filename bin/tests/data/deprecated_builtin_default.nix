@@ -0,0 +1,5 @@
+[
+  (builtins.fetchMercurial { url = "https://example.org/repo"; })
+  (fetchMercurial { url = "https://example.org/repo"; })
+  (builtins.hashString "sha256" "hello")
+]
@@ -0,0 +1,11 @@
+{ lib, ... }:
+with lib;
+{
+  options.services.hello = {
+    greeting = mkOption { type = types.string; };
+    users = lib.mkOption { type = lib.types.loaOf (types.submodule { }); };
+    name = mkOption { type = types.str; };
+    # not an option type
+    other = mkOption { default = pkgs.string; };
+  };
+}
@@ -0,0 +1,23 @@
+[
+  ({ stdenv, fetchurl }: stdenv.mkDerivation {
+    src = fetchurl { };
+    meta.license = stdenv.lib.licenses.mit;
+  })
+  ({ lib, stdenv }: with stdenv.lib; [ lib stdenv ])
+  ({ pkgs }: pkgs.stdenv.lib.licenses.mit)
+  (
+    { stdenv
+    , fetchurl
+    }:
+    [ fetchurl stdenv stdenv.lib.platforms.all ]
+  )
+  (
+    {
+      fetchurl,
+      stdenv,
+    }:
+    [ fetchurl stdenv stdenv.lib.maintainers ]
+  )
+  # nothing to add `lib` to
+  (stdenv: stdenv.lib)
+]
@@ -0,0 +1,11 @@
+[
+  (!!x)
+  (!(!x))
+  (!((!(a || b))))
+  (!(a != b))
+  (!(a != b) == c)
+  (!(!(a && b)) || c)
+  (!(!(a || b)) && c)
+  # a single negation
+  (!(a == b))
+]
@@ -0,0 +1,22 @@
+let
+  a = 1;
+  inherit (builtins) a;
+in
+{
+  x.y = 1;
+  x = {
+    y = 2;
+    z = 3;
+  };
+  # merged with `x`
+  x.w = 4;
+  x.z.v = 5;
+  "p" = 1;
+  p = 2;
+  q = 1;
+  q.r = 2;
+  s = ({ t = 1; });
+  s.t = 2;
+  ${dynamic} = 1;
+  ${dynamic} = 2;
+}
@@ -13,4 +13,15 @@
 
   # multiple empties
   ([] ++ [] ++ [])
+
+  # parenthesized empty list
+  (xs ++ ([ ]))
+
+  # single list
+  (lib.concatLists [ xs ])
+  (builtins.concatLists [ (f x) ])
+  (concatLists [ [ 1 ] ])
+
+  # no match
+  (lib.concatLists [ xs ys ])
 ]
\ No newline at end of file
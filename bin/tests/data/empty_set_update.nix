@@ -0,0 +1,13 @@
+[
+  (attrs // { })
+  ({ } // attrs)
+  (attrs // ({ }))
+  (attrs // lib.optionalAttrs false { a = 1; })
+  (attrs // optionalAttrs cond { })
+  (a // b // { })
+  (f x // { })
+
+  # not empty
+  (attrs // lib.optionalAttrs cond { a = 1; })
+  (attrs // { inherit a; })
+]
@@ -0,0 +1,14 @@
+[
+  ("" + x)
+  (x + "")
+  ("" + "${x}" + "")
+  (x + (""))
+  ('''' + x)
+  ("" + a + b)
+  ("" + toString x)
+  ("" + ./file)
+
+  # not empty
+  (" " + x)
+  ("${y}" + x)
+]
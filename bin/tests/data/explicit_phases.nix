@@ -0,0 +1,23 @@
+{ stdenv, stdenvNoCC }:
+{
+  binary = stdenv.mkDerivation {
+    pname = "hello";
+    phases = [ "installPhase" "fixupPhase" ];
+  };
+  checked = stdenv.mkDerivation (finalAttrs: {
+    pname = "hello";
+    name = "${finalAttrs.pname}-1.0";
+    phases = "unpackPhase patchPhase configurePhase buildPhase checkPhase installPhase fixupPhase";
+  });
+  defaults = stdenvNoCC.mkDerivation { pname = "hello"; phases = [ "unpackPhase" "patchPhase" "configurePhase" "buildPhase" "installPhase" "fixupPhase" ]; };
+  inline = stdenvNoCC.mkDerivation { pname = "hello"; phases = [ "unpackPhase" "installPhase" ]; };
+  # custom phases, or phases from variables
+  custom = stdenv.mkDerivation {
+    pname = "hello";
+    phases = [ "unpackPhase" "myPhase" ];
+  };
+  prefixed = stdenv.mkDerivation {
+    pname = "hello";
+    phases = [ "$prePhases" "installPhase" ];
+  };
+}
@@ -0,0 +1,19 @@
+[
+  (builtins.fetchTarball "https://github.com/NixOS/nixpkgs/archive/nixos-23.05.tar.gz")
+  (fetchTarball {
+    url = "https://github.com/nix-community/home-manager/archive/master.tar.gz";
+  })
+  (builtins.fetchurl { url = "https://example.org/file"; })
+  (builtins.fetchGit { url = "https://github.com/NixOS/nix"; ref = "master"; })
+  (builtins.fetchGit "https://github.com/NixOS/nix")
+  ({ lib }: lib.id (builtins.fetchurl { url = "https://example.org/file"; }))
+
+  # pinned
+  (fetchTarball {
+    url = "https://example.org/archive.tar.gz";
+    sha256 = "0000000000000000000000000000000000000000000000000000";
+  })
+  (builtins.fetchGit { url = "https://github.com/NixOS/nix"; rev = "a3f9625818ecb0c8a3c22c191340dac5a2120b4c"; })
+  # nixpkgs requires a hash
+  (fetchurl { url = "https://example.org/file"; })
+]
@@ -0,0 +1,21 @@
+{
+  description = "inputs";
+
+  inputs = {
+    nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+    # same url as nixpkgs
+    unstable.url = "github:NixOS/nixpkgs/nixos-unstable/";
+    home-manager = {
+      url = "github:nix-community/home-manager";
+      # typo
+      inputs.nixpkgs.follows = "nixpks";
+    };
+    # never used
+    flake-utils.url = "github:numtide/flake-utils";
+  };
+
+  outputs = { self, nixpkgs, ... }@inputs: {
+    homeConfigurations.me = inputs.home-manager.lib.homeManagerConfiguration { };
+    packages.x86_64-linux.default = nixpkgs.legacyPackages.x86_64-linux.hello;
+  };
+}
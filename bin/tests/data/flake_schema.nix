@@ -0,0 +1,13 @@
+{
+  inputs.nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+
+  outputs = { nixpkgs }: {
+    packgaes.x86_64-linx.default = nixpkgs.legacyPackages.x86_64-linux.hello;
+    devShells = {
+      "aarch64-darwn" = { };
+      x86_64-linux = { };
+    };
+    # custom outputs are fine
+    myHelpers = { };
+  };
+}
@@ -0,0 +1,7 @@
+{
+  services.example = {
+    passphrase = "correct horse battery staple";
+    password = "hunter2";
+    adminPassphrase = "@PASSPHRASE@";
+  };
+}
@@ -0,0 +1,12 @@
+{
+  shell = "/nix/store/9krlzvny65gdc8s7kpb6lkx8cd02c25b-bash-5.2-p15/bin/bash";
+  script = ''
+    exec /nix/store/3h8k2dx4l3v5zwrxgyaz9i0w9yf56lfv-hello-2.12.1/bin/hello "$@"
+  '';
+  path = /nix/store/3h8k2dx4l3v5zwrxgyaz9i0w9yf56lfv-hello-2.12.1;
+  src = "/nix/store/0c7jbsdvsfc49qagvl94cqcpf8m1zkq8-source";
+
+  # not a store path
+  store = "/nix/store";
+  short = "/nix/store/abc-hello";
+}
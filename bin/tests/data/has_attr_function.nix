@@ -0,0 +1,12 @@
+{ lib, cfg, pkgs, name }:
+{
+  enabled = builtins.hasAttr "enable" cfg;
+  hasHello = lib.hasAttr "hello" pkgs;
+  quoted = builtins.hasAttr "x86_64-linux.default" cfg.packages;
+  merged = builtins.hasAttr "foo" (cfg // pkgs);
+  negated = !builtins.hasAttr "enable" cfg;
+
+  # left alone
+  dynamic = builtins.hasAttr name cfg;
+  partial = builtins.hasAttr "enable";
+}
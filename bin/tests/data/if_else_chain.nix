@@ -0,0 +1,15 @@
+{ system, name }:
+{
+  arch =
+    if system == "x86_64-linux" then "amd64"
+    else if system == "aarch64-linux" then "arm64"
+    else if "i686-linux" == system then "386"
+    else throw "unsupported system";
+  quoted = if name == "a b" then 1 else if name == "rec" then 2 else if name == "c" then 3 else null;
+  # too short
+  short = if name == "a" then 1 else if name == "b" then 2 else 3;
+  # not the same value
+  mixed = if name == "a" then 1 else if system == "b" then 2 else if name == "c" then 3 else 4;
+  # the first case wins
+  repeated = if name == "a" then 1 else if name == "b" then 2 else if name == "a" then 3 else 4;
+}
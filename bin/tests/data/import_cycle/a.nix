@@ -0,0 +1,4 @@
+{
+  b = import ./b.nix;
+  x = 1;
+}
@@ -0,0 +1,3 @@
+{
+  y = (import ./a.nix).x + 1;
+}
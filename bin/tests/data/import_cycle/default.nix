@@ -0,0 +1,3 @@
+{
+  a = import ./a.nix;
+}
@@ -0,0 +1,27 @@
+{ pkgs, lib, ... }:
+let
+  generated = pkgs.runCommand "generated.nix" { } ''
+    ${pkgs.gen}/bin/gen > $out
+  '';
+  src = pkgs.fetchFromGitHub {
+    owner = "owner";
+    repo = "repo";
+    rev = "v1.0.0";
+    hash = "";
+  };
+  pinned = builtins.fetchGit {
+    url = "https://example.com/repo.git";
+    rev = "0000000000000000000000000000000000000000";
+  };
+in
+{
+  generated = import generated;
+  version = lib.fileContents "${src}/VERSION";
+  readme = builtins.readFile (src + "/README.md");
+  package = pkgs.callPackage "${src}/package.nix" { };
+  script = import (pkgs.writeText "script.nix" "{ }");
+
+  # fetched without building
+  pinned = import pinned;
+  lib' = import "${pkgs.path}/lib";
+}
@@ -0,0 +1,9 @@
+{
+  user = builtins.getEnv "USER";
+  home = builtins.getEnv "HOME"; # evaluated with --impure
+  # needs --impure
+  token = builtins.getEnv "TOKEN";
+
+  # some other comment
+  path = builtins.getEnv "PATH";
+}
@@ -0,0 +1,29 @@
+{ lib, stdenv, python3Packages }:
+{
+  none = stdenv.mkDerivation {
+    pname = "hello";
+  };
+  nested = stdenv.mkDerivation (finalAttrs: {
+    pname = "hello";
+    meta.description = "A program that greets, version ${finalAttrs.pname}";
+  });
+  with_lib = python3Packages.buildPythonPackage {
+    pname = "hello";
+    meta = with lib; {
+      license = licenses.mit;
+      maintainers = [ maintainers.hello ];
+    };
+  };
+  complete = stdenv.mkDerivation {
+    pname = "hello";
+    meta = {
+      description = "A program that greets";
+      license = lib.licenses.gpl3Plus;
+    };
+  };
+  # not written out
+  inherited = stdenv.mkDerivation {
+    pname = "hello";
+    meta = builtins.removeAttrs lib.hello.meta [ "broken" ];
+  };
+}
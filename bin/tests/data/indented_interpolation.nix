@@ -0,0 +1,43 @@
+{ lib, stdenv, hello }:
+let
+  version = "1.0";
+in
+{
+  setup = ''
+    mkdir -p ${HOME}/.config
+    cp ${hello}/bin/hello ${PREFIX}/bin
+  '';
+  stamp = ''
+    echo ''${version} > $out/VERSION
+  '';
+  tools = with lib; stdenv.mkDerivation {
+    pname = "tools";
+    inherit version;
+    postInstall = ''
+      cp -r ${src}/share ${out}/share
+    '';
+  };
+  env = with lib; stdenv.mkDerivation {
+    pname = "env";
+    inherit version;
+    buildCommand = ''
+      echo ${TMPDIR}
+    '';
+  };
+
+  # left alone
+  shell = ''
+    echo ''${HOME} ${version} '''${version}
+  '';
+  build = stdenv.mkDerivation {
+    pname = "hello";
+    inherit version;
+    installPhase = ''
+      mkdir -p ''${out}/bin
+    '';
+  };
+  quoted = "${HOME}/.config";
+  config = with lib; ''
+    path = ${out}/share
+  '';
+}
@@ -0,0 +1,14 @@
+{
+  a = "${lib.concatStringsSep ", " names}";
+  b = ''${toString port}'';
+  c = "${"prefix-" + name}";
+  d = "${name}";
+  e = "${f x}";
+  "${name}" = 1;
+
+  # more than the interpolation
+  f = "${name}-${version}";
+  g = "v${version}";
+  # paths are copied to the store
+  h = "${./foo}";
+}
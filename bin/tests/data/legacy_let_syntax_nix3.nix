@@ -0,0 +1,5 @@
+let {
+  body = x + y;
+  x = "hello,";
+  y = " world!";
+}
@@ -0,0 +1,14 @@
+{ lib, patches, flags, inputs }:
+{
+  unpatched = builtins.length patches == 0;
+  patched = lib.length patches != 0;
+  hasFlags = 0 < builtins.length flags;
+  noInputs = lib.lists.length (inputs ++ flags) < 1;
+  names = lib.optional (builtins.length patches >= 1) "patched";
+  empty = ((builtins.length patches)) == 0;
+
+  # left alone
+  single = builtins.length patches == 1;
+  count = builtins.length patches;
+  other = lib.strings.length flags == 0;
+}
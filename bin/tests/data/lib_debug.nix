@@ -0,0 +1,8 @@
+{ lib, pkgs }:
+{
+  checks = lib.debug.runTests { testId = { expr = lib.id 1; expected = 1; }; };
+  all = lib.testAllTrue [ true ];
+  context = pkgs.lib.debug.addErrorContextToAttrs;
+  # left to debug_trace
+  traced = lib.debug.traceVal 1;
+}
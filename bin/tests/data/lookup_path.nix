@@ -0,0 +1,7 @@
+{
+  pkgs ? import <nixpkgs> { },
+  lib ? import <nixpkgs/lib>,
+  # allowed in the options
+  config ? import <nixos-config>,
+}:
+pkgs.hello
@@ -0,0 +1,16 @@
+{
+  inputs.nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+
+  outputs = { self, nixpkgs }@inputs:
+    let
+      system = "x86_64-linux";
+      pkgs = import <nixpkgs> { inherit system; };
+      lib = import <nixpkgs/lib>;
+    in
+    {
+      packages.x86_64-linux.default = (import <nixpkgs> { }).hello;
+      packages.aarch64-linux.default = (import <nixpkgs> { system = "aarch64-linux"; }).hello;
+      legacyPackages.x86_64-linux = import <nixpkgs> { config.allowUnfree = true; };
+      checks.x86_64-linux = { inherit pkgs lib inputs self; };
+    };
+}
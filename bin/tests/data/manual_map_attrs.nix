@@ -0,0 +1,9 @@
+{ lib, set, prefix }:
+[
+  (builtins.listToAttrs (map (name: { inherit name; value = toString set.${name}; }) (builtins.attrNames set)))
+  (lib.listToAttrs (map (n: lib.nameValuePair n (set.${n} + 1)) (lib.attrNames set)))
+  (lib.genAttrs (builtins.attrNames set) (name: { inherit name; }))
+  (builtins.listToAttrs (map (n: { name = "${prefix}-${n}"; value = set.${n}; }) (builtins.attrNames set)))
+  # not the names of a set
+  (builtins.listToAttrs (map (n: { name = n; value = 1; }) [ "a" ]))
+]
@@ -0,0 +1,16 @@
+{ lib, version, python }:
+{
+  atLeast = builtins.compareVersions version "2.0" >= 0;
+  older = lib.optional (builtins.compareVersions python.version "3.11" < 0) "tomli";
+  newer = 0 > lib.compareVersions "1.0" version;
+  notOlder = builtins.compareVersions version "2.0" != -1;
+  legacy = version < "1.10";
+  current = "2.4.1" <= version;
+  nested = !(version >= "2.0");
+  wrapped = ((builtins.compareVersions version "2.0")) >= 0;
+
+  # left alone
+  equal = builtins.compareVersions version "2.0" == 0;
+  named = version < "unstable";
+  constant = "1.0" < "1.10";
+}
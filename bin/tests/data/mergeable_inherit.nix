@@ -0,0 +1,15 @@
+let
+  inherit (pkgs) hello;
+  inherit (pkgs) cowsay figlet;
+  inherit (pkgs.lib) mkIf;
+  inherit (pkgs . lib) mkOption;
+in
+{
+  inherit (pkgs) hello;
+  # not next to each other
+  x = 1;
+  inherit (pkgs) cowsay;
+  inherit (pkgs) "figlet";
+  inherit hello;
+  inherit cowsay;
+}
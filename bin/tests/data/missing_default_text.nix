@@ -0,0 +1,22 @@
+{ lib, pkgs, config, ... }:
+{
+  options.services.hello = {
+    package = lib.mkOption {
+      type = lib.types.package;
+      default = pkgs.hello;
+      description = "The package to use.";
+    };
+    dataDir = lib.mkOption { default = "${config.users.users.hello.home}/data"; description = "Where data is kept."; };
+
+    # literals
+    port = lib.mkOption { default = 8080; description = "The port."; };
+    flags = lib.mkOption { default = [ "--quiet" ]; description = "Flags."; };
+    settings = lib.mkOption { default = { greeting = "hi"; }; description = "Settings."; };
+    # documented
+    user = lib.mkOption {
+      default = config.users.users.hello.name;
+      defaultText = lib.literalExpression "config.users.users.hello.name";
+      description = "The user.";
+    };
+  };
+}
@@ -0,0 +1,9 @@
+{
+  # exists
+  a = import ./bool_comparison.nix;
+  # missing
+  b = import ./missing.nix;
+  imports = [ ./nope.nix ];
+  # generated
+  c = ./result/bin/hello;
+}
@@ -0,0 +1,8 @@
+{ ... }: {
+  imports = [ ./nixos/modules/hello.nix ];
+
+  services.hello.enabel = true;
+  services.hello.greeting = "hi";
+  services.hello.colour = "red";
+  services.openssh.enable = true;
+}
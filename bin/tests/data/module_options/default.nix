@@ -0,0 +1 @@
+import ./configuration.nix
@@ -0,0 +1,22 @@
+{ config, lib, pkgs, ... }:
+let
+  cfg = config.services.hello;
+in
+{
+  options.services.hello = {
+    enable = lib.mkEnableOption "hello";
+    greeting = lib.mkOption {
+      type = lib.types.str;
+      default = "hello";
+    };
+    package = lib.mkPackageOption pkgs "hello" { };
+  };
+
+  options.programs.hello.enable = lib.mkEnableOption "hello program";
+
+  config = lib.mkIf cfg.enable {
+    environment.systemPackages = [ cfg.package ];
+    environment.etc.greeting.text = cfg.greeting;
+    services.hello.greting = "hi";
+  };
+}
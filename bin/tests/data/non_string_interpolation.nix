@@ -0,0 +1,14 @@
+{ port, hello }:
+{
+  listen = "127.0.0.1:${8080}";
+  ratio = "ratio=${ 0.5 }";
+  enabled = "ENABLE=${true}";
+  debug = ''
+    DEBUG=${false}
+  '';
+  flags = "--with ${[ "a" "b" ]}";
+
+  # left alone
+  address = "127.0.0.1:${toString port}";
+  bin = "${hello}/bin/hello";
+}
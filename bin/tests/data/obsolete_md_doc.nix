@@ -0,0 +1,13 @@
+{ lib, ... }:
+with lib;
+{
+  options.services.hello = {
+    enable = mkEnableOption (lib.mdDoc "the `hello` service");
+    greeting = mkOption {
+      type = types.str;
+      description = mdDoc ''
+        The greeting, such as `hello`.
+      '';
+    };
+  };
+}
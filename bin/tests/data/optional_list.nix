@@ -0,0 +1,11 @@
+{ lib, withGui, withDocs, gtk3, qt5, sphinx }:
+{
+  single = if withGui then [ gtk3 ] else [ ];
+  several = if withGui && withDocs then [ gtk3 qt5 ] else [ ];
+  negated = if withGui then [ ] else [ qt5.qtbase ];
+  applied = if withGui then [ (lib.getDev gtk3) ] else [ ];
+  nested = lib.optional withDocs [ sphinx ];
+  # fine
+  optional = lib.optional withDocs sphinx;
+  both = if withGui then [ ] else [ ];
+}
@@ -0,0 +1,10 @@
+{ lib, withDocs, stdenv }:
+{
+  target = "make ${if withDocs then "docs" else ""}";
+  flags = if stdenv.isDarwin || withDocs then "--darwin" else "";
+  negated = if withDocs then '''' else "--no-docs";
+  version = lib.version;
+  # both or neither empty
+  both = if withDocs then "" else "";
+  neither = if withDocs then "a" else "b";
+}
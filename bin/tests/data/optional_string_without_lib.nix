@@ -0,0 +1,2 @@
+{ withDocs }:
+"make ${if withDocs then "docs" else ""}"
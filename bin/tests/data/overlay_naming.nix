@@ -0,0 +1,16 @@
+{ pkgs, prev }:
+{
+  nixpkgs.overlays = [
+    (self: super: {
+      hello = super.hello.override { inherit (self) stdenv; };
+      greeting = self: self.hello;
+    })
+  ];
+  overlays.default = self: super: { cowsay = super.cowsay.override { inherit (self) fortune; }; };
+  scope = pkgs.python3Packages.overrideScope (self: super: { requests = super.requests.override { inherit (self) idna; }; });
+  # renaming is not safe
+  conflict = pkgs.extend (self: super: { hello = super.hello; old = prev; new = self.hello; });
+  inherits = pkgs.extend (self: super: { inherit super; hello = self.hello; });
+  # not overlays
+  other = map (self: super: self + super) [ ];
+}
@@ -0,0 +1,5 @@
+{
+  a = if x == true then 1 else 2;
+  b = { c = ; };
+  d = let in 3;
+}
@@ -0,0 +1,14 @@
+{ lib, name, ... }:
+{
+  src = ./. + "/src";
+  snapshots = [
+    (../snapshots + "/main__path_concatenation.snap")
+    (/. + "/etc/hosts")
+  ];
+  config = ../data + "/${name}.nix";
+  module = ./. + "/default.nix/";
+  suffixed = ../data + ".nix";
+
+  # not a path literal
+  prefix = lib.optionalString true "/tmp" + "/file";
+}
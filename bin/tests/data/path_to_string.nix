@@ -0,0 +1,11 @@
+{
+  src = toString ./src;
+  imports = [ "${./module.nix}" (builtins.toString ./other.nix) ];
+  patches = [ "${./fix.patch}" ];
+  root = toString ./.;
+  config = toString ./config/${name};
+
+  # not a lone path
+  script = "${./bin}/run";
+  nixPath = toString <nixpkgs>;
+}
@@ -0,0 +1,73 @@
+{ lib, stdenv, python3Packages, fixBuildPatch }:
+{
+  hello = stdenv.mkDerivation {
+    pname = "hello";
+    version = "2.12";
+    meta = { description = "An example"; license = lib.licenses.mit; };
+    patchPhase = ''
+      runHook prePatch
+
+      substituteInPlace Makefile --replace-fail gcc cc
+
+      runHook postPatch
+    '';
+    installPhase = ''
+      runHook preInstall
+      make install
+      installManPage hello.1
+      runHook postInstall
+    '';
+  };
+  world = stdenv.mkDerivation {
+    pname = "world";
+    version = "1.0";
+    meta = { description = "An example"; license = lib.licenses.mit; };
+    buildPhase = ''
+      runHook preBuild
+      make
+      runHook postBuild
+    '';
+    installPhase = ''
+      mkdir -p $out/bin
+      cp world $out/bin
+    '';
+    checkPhase = ''
+      runHook preCheck
+      ./world --test
+    '';
+  };
+  greeter = python3Packages.buildPythonApplication {
+    pname = "greeter";
+    version = "1.0";
+    meta = { description = "An example"; license = lib.licenses.mit; };
+    configurePhase = "cp config.h.in config.h";
+  };
+
+  client = stdenv.mkDerivation {
+    pname = "client";
+    version = "1.0";
+    meta = { description = "An example"; license = lib.licenses.mit; };
+    postInstall = "rm -r $out/share/doc";
+    installPhase = ''
+      runHook preInstall
+      make install
+      cp server.service $out/lib
+      runHook postInstall
+    '';
+  };
+
+  # left alone
+  server = stdenv.mkDerivation {
+    pname = "server";
+    version = "1.0";
+    meta = { description = "An example"; license = lib.licenses.mit; };
+    patches = [ fixBuildPatch ];
+    patchPhase = ''
+      runHook prePatch
+      sed -i 's/-Werror//' Makefile
+      runHook postPatch
+    '';
+    dontConfigure = true;
+    buildPhase = "true";
+  };
+}
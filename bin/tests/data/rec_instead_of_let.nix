@@ -0,0 +1,18 @@
+{
+  urls = rec {
+    base = "https://example.org";
+    api = "${base}/api";
+    docs = "${base}/docs";
+  };
+  sets = [
+    rec { a = 1; b = a; }
+  ];
+  # the usual way to write a derivation
+  package = stdenv.mkDerivation rec {
+    pname = "hello";
+    version = "2.12";
+    name = "${pname}-${version}";
+  };
+  # bound through an attribute path
+  paths = rec { a.b = 1; c = a.b; };
+}
@@ -0,0 +1,17 @@
+{ config, lib, pkgs, ... }:
+{
+  services.nginx.enable = lib.mkForce (lib.mkDefault true);
+  services.nginx.user = lib.mkDefault (lib.mkDefault "nginx");
+  boot.kernelPackages = lib.mkOverride 50 (lib.mkForce pkgs.linuxPackages_latest);
+  environment.systemPackages = lib.mkMerge [ [ pkgs.git ] ];
+  networking.firewall = lib.mkIf true {
+    allowedTCPPorts = [ 80 ];
+  };
+
+  # left alone
+  programs.git.enable = lib.mkForce config.services.nginx.enable;
+  environment.variables = lib.mkMerge [
+    { EDITOR = "vim"; }
+    (lib.mkIf config.services.nginx.enable { NGINX = "1"; })
+  ];
+}
@@ -18,7 +18,7 @@
     foo.again = 1;
   }
 
-  # more than 3, omit the extra
+  # more than 3
   {
     foo.baz.bar1 = 1;
     foo.baz.bar2 = 2;
@@ -26,4 +26,11 @@
     foo.baz.bar4 = 4;
     foo.baz.bar5 = 5;
   }
+
+  # not all assigned through a path, no fix
+  {
+    foo.a = 1;
+    foo.b = 2;
+    foo = { c = 3; };
+  }
 ]
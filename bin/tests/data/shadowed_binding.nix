@@ -0,0 +1,13 @@
+{ lib, pkgs, ... }:
+let
+  inherit lib;
+  version = "1.0";
+  f = version: version;
+  g = { pkgs }: pkgs;
+  _unused = _: 1;
+in
+rec {
+  lib = 1;
+  h = x: x: x;
+  i = _lib: let lib = 1; in lib;
+}
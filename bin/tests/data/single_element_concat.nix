@@ -0,0 +1,10 @@
+{ lib, pname, version, drv }:
+[
+  (lib.concatStrings [ "${pname}-${version}" ])
+  (builtins.concatStringsSep ", " [ (lib.toUpper pname) ])
+  ("prefix-" + lib.concatStringsSep "," [ ("a" + version) ])
+  # may not be a string
+  (lib.concatStrings [ drv ])
+  # several elements
+  (lib.concatStringsSep "-" [ pname version ])
+]
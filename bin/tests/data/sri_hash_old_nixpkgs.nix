@@ -0,0 +1,19 @@
+{ fetchurl, fetchFromGitHub }:
+[
+  (fetchurl {
+    url = "https://example.org/hello.tar.gz";
+    sha256 = "sha256-jZkUKv2SV28wsM18tCqNxoCZmLxdYH2Idh9RLibH2yA=";
+  })
+  (fetchFromGitHub {
+    owner = "NixOS";
+    repo = "nix";
+    rev = "2.17.0";
+    sha256 = "0q52sbgqz2jl0cd8kc1ycgfxkagimrq2jhakdz7xv1qyqf0x1w9j";
+  })
+
+  # already `hash`
+  (fetchurl {
+    url = "https://example.org/hello.tar.gz";
+    hash = "sha256-jZkUKv2SV28wsM18tCqNxoCZmLxdYH2Idh9RLibH2yA=";
+  })
+]
@@ -0,0 +1,25 @@
+{ lib, stdenv }:
+{
+  hello = stdenv.mkDerivation {
+    pname = "hello";
+    version = "1.0";
+    meta = {
+      description = "Prints a greeting";
+      license = "MIT";
+    };
+  };
+  world = stdenv.mkDerivation {
+    pname = "world";
+    version = "1.0";
+    meta = with lib; {
+      description = "Greets the world";
+      license = [ "apache-2.0" "GPL" licenses.bsd3 ];
+    };
+  };
+  greeter = stdenv.mkDerivation {
+    pname = "greeter";
+    version = "1.0";
+    meta.description = "Greets";
+    meta.license = "Apache";
+  };
+}
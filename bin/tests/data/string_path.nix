@@ -0,0 +1,11 @@
+{
+  overlays = import "./overlays/default.nix";
+  hello = callPackage "../pkgs/hello" { };
+  src = "./src";
+  imports = [ "./module.nix" ];
+  spaced = import "./with space.nix";
+
+  # not where a path is expected
+  description = "./src";
+  absolute = import "/etc/nixos/configuration.nix";
+}
@@ -0,0 +1,8 @@
+{
+  # reported once, by `empty_let_in` only
+  a = let in (x);
+  # reported once, by `empty_list_concat` only
+  b = [] ++ (y);
+  # reported, no lint with a higher priority contains it
+  c = if (z) then 1 else 2;
+}
@@ -0,0 +1,18 @@
+{ config, lib, pkgs, ... }:
+with lib;
+let
+  cfg = config.services.hello;
+in
+{
+  options.services.hello = {
+    enable = mkEnableOption "hello";
+    names = mkOption {
+      type = types.listOf types.str;
+      default = [ ];
+      description = "Names to greet, ${toString (length cfg.names)} so far.";
+    };
+  };
+  config = mkIf cfg.enable {
+    environment.systemPackages = map (name: pkgs.${name}) cfg.names;
+  };
+}
@@ -0,0 +1,12 @@
+{ config, lib, pkgs, ... }:
+let
+  cfg = config.services.hello;
+in
+with lib;
+{
+  options.services.hello.enable = mkEnableOption "hello";
+  config = mkIf cfg.enable {
+    environment.systemPackages = with pkgs; [ hello ];
+    users.users.hello = { inherit (cfg) enable; };
+  };
+}
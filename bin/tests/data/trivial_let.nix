@@ -0,0 +1,15 @@
+{ pkgs, name }:
+[
+  (let drv = pkgs.callPackage ./hello.nix { }; in drv)
+  (let
+    version = "1.0";
+    src = pkgs.fetchurl { url = "https://example.org/hello-1.0.tar.gz"; };
+  in
+  src)
+  # refers to itself, or to the other bindings
+  (let xs = [ 1 ] ++ xs; in xs)
+  (let version = "1.0"; drv = pkgs.hello.override { inherit version; }; in drv)
+  # used more than the body
+  (let x = name; in [ x x ])
+  (let greeting = "hello"; in "${greeting}, ${name}")
+]
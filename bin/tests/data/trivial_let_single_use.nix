@@ -0,0 +1,14 @@
+{ pkgs, name }:
+[
+  (let greeting = "hello"; in "${greeting}, ${name}")
+  (let greeting = "hello"; in ''${greeting}, ${name}'')
+  (let drv = pkgs.hello; in pkgs.symlinkJoin { paths = [ drv ]; })
+  (let x = a: a; in (x 1) + 2)
+  # evaluated on each call
+  (let drv = pkgs.hello; in map (n: drv.${n}) [ name ])
+  # the variables of the value are bound otherwise
+  (let greeting = name; in { text = let name = "world"; in greeting + name; })
+  # used twice, or not a single binding
+  (let x = name; in [ x x ])
+  (let x = name; y = name; in x + y)
+]
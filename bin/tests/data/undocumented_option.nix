@@ -0,0 +1,18 @@
+{ lib, ... }:
+{
+  options.services.hello = {
+    greeting = lib.mkOption { type = lib.types.str; };
+    port = lib.mkOption { description = "The port to listen on."; };
+    users = lib.mkOption { default = [ ]; };
+
+    # documented
+    enable = lib.mkOption {
+      type = lib.types.bool;
+      description = "Whether to enable hello.";
+    };
+    # internal
+    state = lib.mkOption { internal = true; };
+    # allowed
+    extra = lib.mkOption { };
+  };
+}
@@ -0,0 +1,9 @@
+{ callPackage, fetchgit }:
+{
+  # `fetchgit` is not accepted by ./hello.nix
+  hello = callPackage ./hello.nix { inherit fetchgit; };
+  # fine
+  hello' = callPackage ./hello.nix { stdenv = null; };
+  # ./world.nix accepts anything
+  world = import ./world.nix { extra = true; };
+}
@@ -0,0 +1,5 @@
+{ stdenv, fetchurl }:
+stdenv.mkDerivation {
+  pname = "hello";
+  src = fetchurl { url = "https://example.com"; };
+}
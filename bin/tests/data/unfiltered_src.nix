@@ -0,0 +1,33 @@
+{ lib, stdenv, buildGoModule }:
+{
+  hello = stdenv.mkDerivation {
+    pname = "hello";
+    version = "1.0";
+    src = ./.;
+    meta = {
+      description = "Prints a greeting";
+      license = lib.licenses.mit;
+    };
+  };
+  world = buildGoModule (finalAttrs: {
+    pname = "world";
+    version = "1.0";
+    src = ./.;
+    vendorHash = null;
+    meta = {
+      description = "Greets the world, version ${finalAttrs.version}";
+      license = lib.licenses.mit;
+    };
+  });
+
+  # filtered
+  filtered = stdenv.mkDerivation {
+    pname = "filtered";
+    version = "1.0";
+    src = lib.cleanSource ./.;
+    meta = {
+      description = "Prints a greeting";
+      license = lib.licenses.mit;
+    };
+  };
+}
@@ -0,0 +1,11 @@
+{
+  description = "Hello";
+
+  outputs = { self, nixpkgs }: {
+    packages.x86_64-linux.default = nixpkgs.legacyPackages.x86_64-linux.stdenv.mkDerivation {
+      pname = "hello";
+      version = "1.0";
+      src = self;
+    };
+  };
+}
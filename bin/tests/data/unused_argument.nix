@@ -0,0 +1,18 @@
+[
+  (x: 1)
+  (_x: 1)
+  ({ a, b, c }: a)
+  ({ a, b, c, ... }: a)
+  ({ a, b ? a, ... }: b)
+  ({ stdenv
+   , lib
+   , fetchurl
+   }: stdenv)
+  ({
+    config,
+    pkgs,
+    ...
+  }: config)
+  (args @ { a, ... }: args)
+  (x: y: x)
+]
@@ -0,0 +1,10 @@
+{
+  description = "unused arguments of outputs are left alone";
+
+  inputs.nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+
+  outputs = { self, nixpkgs, ... }: {
+    packages.x86_64-linux.default = nixpkgs.legacyPackages.x86_64-linux.hello;
+    lib = { x, y, ... }: x;
+  };
+}
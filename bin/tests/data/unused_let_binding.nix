@@ -0,0 +1,18 @@
+let
+  used = 1;
+  unused = 2;
+  # still used by `used2`
+  helper = 3;
+  used2 = helper;
+  a.b = 1;
+  a.c = 2;
+  inherit (builtins) map filter;
+  inherit (lib) unusedToo;
+in
+[
+  used
+  used2
+  map
+  # shadowed, the outer `unused` is not used here
+  (unused: unused)
+]
@@ -0,0 +1,15 @@
+[
+  (args @ { a, b }: a + b)
+  ({ a, ... } @ args: a)
+  ({ stdenv, lib, ... } @ args: args.stdenv.mkDerivation { meta = args.lib.licenses.mit; })
+  # `args` is passed on
+  ({ a, ... } @ args: f args)
+  # `b` is not in the pattern
+  ({ a, ... } @ args: args.b)
+  # `a` has a default, that `args.a` does not see
+  ({ a ? 1, ... } @ args: args.a)
+  # `a` is shadowed
+  ({ a, ... } @ args: let a = 2; in args.a + a)
+  (_args @ { a }: a)
+  (args @ { ... }: 1)
+]
@@ -7,4 +7,14 @@
   # complex body
   (if x ? a then x.a else if b then c else d)
   (if x ? a then x.a else b.c)
+
+  # guarded selects
+  (x ? a && x.a)
+  (x ? a.b && x.a.b == "foo")
+  (x ? a && 1 == x.a)
+  (x ? a && x.a == "b" || y)
+
+  # a missing attribute would compare equal
+  (x ? a && x.a == null)
+  (x ? a && x.a == y)
 ]
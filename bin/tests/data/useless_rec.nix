@@ -0,0 +1,17 @@
+[
+  rec {
+    pname = "hello";
+    version = "2.12";
+  }
+  rec {
+    pname = "hello";
+    name = "${pname}-2.12";
+  }
+  # `y` refers to the inherited `x`
+  rec {
+    inherit x;
+    y = x;
+  }
+  rec { a = { b = a; }; }
+  rec { a = 1; b = { a = 2; }.a; }
+]
@@ -0,0 +1,12 @@
+{ version, lib }:
+let
+  release = { version = "1.0"; date = "2022-01-01"; };
+  defaults = { name = "hello"; inherit version; };
+in
+[
+  (with release; "${version} (${date})")
+  (with defaults; let name = "world"; in "${name} ${version}")
+  (with { lib = null; date = 1; }; [ lib date ])
+  # the set is not known
+  (with lib; version)
+]
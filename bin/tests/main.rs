@@ -1,4 +1,4 @@
-use lib::session::{SessionInfo, Version};
+use lib::session::{Mode, SessionInfo, Version};
 
 macro_rules! session_info {
     ($version:expr) => {{
@@ -7,6 +7,20 @@ macro_rules! session_info {
     }};
 }
 
+// lint options as statix.toml sets them, `options!("lint" => { "key" => ["value"] })`
+macro_rules! options {
+    ($lint:literal => { $($key:literal => [$($value:literal),*]),* $(,)? }) => {
+        vec![(
+            $lint.to_owned(),
+            vec![$(($key.to_owned(), vec![$($value.to_owned()),*])),*]
+                .into_iter()
+                .collect(),
+        )]
+        .into_iter()
+        .collect()
+    };
+}
+
 mod util {
     #[macro_export]
     macro_rules! test_lint {
@@ -24,7 +38,7 @@ mod util {
         ($tname:ident => $sess:expr) => {
             #[test]
             fn $tname() {
-                use statix::{config::OutFormat, traits::WriteDiagnostic, lint};
+                use statix::{config::{OutFormat, PathStyle}, traits::WriteDiagnostic, lint};
                 use vfs::ReadOnlyVfs;
 
                 let file_path = concat!("data/", stringify!($tname), ".nix");
@@ -36,7 +50,7 @@ mod util {
 
                 let mut buffer = Vec::new();
                 vfs.iter().map(|entry| lint::lint(entry, &session)).for_each(|r| {
-                    buffer.write(&r, &vfs, OutFormat::StdErr).unwrap();
+                    buffer.write(&r, &vfs, OutFormat::StdErr, PathStyle::Relative).unwrap();
                 });
 
                 let stripped = strip_ansi_escapes::strip(&buffer).unwrap();
@@ -45,6 +59,41 @@ mod util {
             }
         };
     }
+
+    #[macro_export]
+    macro_rules! test_project {
+        ($($tname:ident => [$($file:literal),*]),* $(,)?) => {
+            $(
+                #[test]
+                fn $tname() {
+                    use statix::{config::{OutFormat, PathStyle}, traits::WriteDiagnostic, lint};
+                    use vfs::ReadOnlyVfs;
+
+                    let mut vfs = ReadOnlyVfs::default();
+                    $(
+                        vfs.set_file_contents(
+                            concat!("data/", stringify!($tname), "/", $file),
+                            include_bytes!(concat!("data/", stringify!($tname), "/", $file)),
+                        );
+                    )*
+
+                    let session = session_info!("2.6");
+                    let roots = [concat!("data/", stringify!($tname), "/default.nix").into()];
+                    let mut results = lint::lint_project(vfs.iter(), &roots, &lib::LINTS, &session, None);
+                    results.sort_by_key(|r| r.file_id);
+
+                    let mut buffer = Vec::new();
+                    results.iter().for_each(|r| {
+                        buffer.write(r, &vfs, OutFormat::StdErr, PathStyle::Relative).unwrap();
+                    });
+
+                    let stripped = strip_ansi_escapes::strip(&buffer).unwrap();
+                    let out =  std::str::from_utf8(&stripped).unwrap();
+                    insta::assert_snapshot!(&out);
+                }
+            )*
+        };
+    }
 }
 
 test_lint! {
@@ -53,6 +102,7 @@ test_lint! {
     manual_inherit,
     manual_inherit_from,
     legacy_let_syntax,
+    legacy_let_syntax_nix3 => session_info!("3.0"),
     collapsible_let_in,
     eta_reduction,
     useless_parens,
@@ -67,5 +117,115 @@ test_lint! {
     bool_simplification,
     useless_has_attr,
     repeated_keys,
-    empty_list_concat
+    empty_list_concat,
+    missing_path => session_info!("2.6")
+        .with_file(std::path::Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/tests/data/missing_path.nix")))
+        .with_generated(vec!["result".into()]),
+    flake_schema => session_info!("2.6").with_mode(Mode::Flake),
+    flake_inputs => session_info!("2.6").with_mode(Mode::Flake),
+    parse_errors,
+    subsumed,
+    unused_let_binding,
+    unused_argument,
+    unused_argument_flake => session_info!("2.6").with_mode(Mode::Flake),
+    unused_pattern_bind,
+    shadowed_binding,
+    duplicate_attribute,
+    mergeable_inherit,
+    useless_rec,
+    rec_instead_of_let,
+    bool_if_else,
+    double_negation,
+    constant_condition,
+    empty_set_update,
+    empty_string_concat,
+    interpolation_only_string,
+    path_to_string,
+    string_path,
+    hardcoded_store_path,
+    lookup_path => session_info!("2.6").with_options(options!("lookup_path" => {
+        "allow" => ["nixos-config"],
+    })),
+    lookup_path_flake => session_info!("2.6").with_mode(Mode::Flake),
+    current_system => session_info!("2.6").with_mode(Mode::Flake),
+    impure_get_env,
+    fetcher_without_hash,
+    branch_pin,
+    sri_hash => session_info!("2.6").with_mode(Mode::Nixpkgs),
+    sri_hash_old_nixpkgs => session_info!("2.6")
+        .with_mode(Mode::Nixpkgs)
+        .with_nixpkgs_version("21.05".parse().unwrap()),
+    deprecated_stdenv_lib,
+    deprecated_builtin => session_info!("2.6").with_options(options!("deprecated_builtin" => {
+        "deprecated" => ["fetchTarball", "hashString=convertHash", "getFlake@2.20"],
+    })),
+    deprecated_builtin_default,
+    deprecated_option_type => session_info!("2.6").with_mode(Mode::NixosModule),
+    obsolete_md_doc,
+    undocumented_option => session_info!("2.6")
+        .with_mode(Mode::NixosModule)
+        .with_options(options!("undocumented_option" => {
+            "require_type" => ["true"],
+            "allow" => ["services.hello.extra"],
+        })),
+    missing_default_text => session_info!("2.6").with_mode(Mode::NixosModule),
+    top_level_with => session_info!("2.6").with_mode(Mode::NixosModule),
+    top_level_with_nested => session_info!("2.6").with_mode(Mode::NixosModule),
+    broad_with => session_info!("2.6").with_options(options!("broad_with" => {
+        "max_lines" => ["5"],
+    })),
+    broad_with_file => session_info!("2.6").with_options(options!("broad_with" => {
+        "max_lines" => ["5"],
+        "severity" => ["hint"],
+    })),
+    with_shadowed,
+    overlay_naming,
+    debug_trace => session_info!("2.6").with_options(options!("debug_trace" => {
+        "allow_prefix" => ["warning:"],
+    })),
+    lib_debug,
+    explicit_phases => session_info!("2.6").with_mode(Mode::Nixpkgs),
+    incomplete_meta => session_info!("2.6").with_mode(Mode::Nixpkgs),
+    incomplete_meta_required => session_info!("2.6")
+        .with_mode(Mode::Nixpkgs)
+        .with_options(options!("incomplete_meta" => {
+            "required" => ["description", "maintainers"],
+        })),
+    if_else_chain,
+    optional_string => session_info!("2.6").with_mode(Mode::Nixpkgs),
+    optional_string_without_lib => session_info!("2.6").with_mode(Mode::Nixpkgs),
+    optional_list,
+    trivial_let,
+    trivial_let_single_use => session_info!("2.6").with_options(options!("trivial_let" => {
+        "inline_single_use" => ["true"],
+    })),
+    manual_map_attrs,
+    single_element_concat,
+    import_from_derivation,
+    indented_interpolation,
+    path_concatenation,
+    manual_version_comparison => session_info!("2.6").with_mode(Mode::Nixpkgs),
+    redundant_module_wrapper => session_info!("2.6").with_mode(Mode::NixosModule),
+    unfiltered_src => session_info!("2.6").with_mode(Mode::Nixpkgs),
+    unfiltered_src_flake => session_info!("2.6").with_mode(Mode::Flake),
+    hardcoded_secret,
+    hardcoded_secret_options => session_info!("2.6").with_options(options!("hardcoded_secret" => {
+        "names" => ["passphrase"],
+        "allow" => ["@*@"],
+    })),
+    string_license => session_info!("2.6").with_mode(Mode::Nixpkgs),
+    combined_name,
+    build_tool_input => session_info!("2.6").with_mode(Mode::Nixpkgs),
+    phase_override => session_info!("2.6").with_mode(Mode::Nixpkgs),
+    non_string_interpolation,
+    length_comparison,
+    attr_names_comparison,
+    has_attr_function
+}
+
+test_project! {
+    unexpected_import_args => ["default.nix", "hello.nix", "world.nix"],
+    dead_code => ["default.nix", "lib.nix", "orphan.nix"],
+    import_cycle => ["default.nix", "a.nix", "b.nix"],
+    module_options => ["default.nix", "configuration.nix", "nixos/modules/hello.nix"]
 }
@@ -48,6 +48,17 @@
 
             buildFeatures = [ "json" ];
 
+            nativeBuildInputs = [ installShellFiles ];
+
+            postInstall = ''
+              $out/bin/statix man --out-dir man
+              installManPage man/*.1
+              installShellCompletion --cmd statix \
+                --bash <($out/bin/statix completions bash) \
+                --fish <($out/bin/statix completions fish) \
+                --zsh <($out/bin/statix completions zsh)
+            '';
+
             meta = with lib; {
               description = "Lints and suggestions for the Nix programming language";
               homepage = "https://git.peppe.rs/languages/statix/about";
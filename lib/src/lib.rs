@@ -1,14 +1,17 @@
 #![recursion_limit = "1024"]
 mod lints;
 mod make;
+pub mod project;
+mod scope;
 pub mod session;
-mod utils;
+pub mod utils;
 
 pub use lints::LINTS;
-use session::SessionInfo;
+use project::{Project, ProjectReport};
+use session::{Mode, SessionInfo, Since, Version};
 
-use rnix::{parser::ParseError, SyntaxElement, SyntaxKind, TextRange};
-use std::{convert::Into, default::Default};
+use rnix::{parser::ParseError, SyntaxElement, SyntaxKind, TextRange, TextSize};
+use std::{cmp::Reverse, collections::HashSet, convert::Into, default::Default, fmt, str::FromStr};
 
 #[cfg(feature = "json-out")]
 use serde::{
@@ -16,7 +19,7 @@ use serde::{
     Serialize,
 };
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 #[cfg_attr(feature = "json-out", derive(Serialize))]
 pub enum Severity {
     Warn,
@@ -30,6 +33,28 @@ impl Default for Severity {
     }
 }
 
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Warn => "warn",
+            Self::Error => "error",
+            Self::Hint => "hint",
+        })
+    }
+}
+
+impl FromStr for Severity {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "warn" => Ok(Self::Warn),
+            "error" => Ok(Self::Error),
+            "hint" => Ok(Self::Hint),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Report generated by a lint
 #[derive(Debug, Default)]
 #[cfg_attr(feature = "json-out", derive(Serialize))]
@@ -40,8 +65,14 @@ pub struct Report {
     pub code: u32,
     /// Report severity level
     pub severity: Severity,
+    /// Diagnostics of reports with a higher priority hide the ones they
+    /// contain, see `subsume`
+    #[cfg_attr(feature = "json-out", serde(skip))]
+    pub priority: u8,
     /// Collection of diagnostics raised by this lint
     pub diagnostics: Vec<Diagnostic>,
+    /// Link to the documentation of this lint
+    pub docs_url: Option<&'static str>,
 }
 
 impl Report {
@@ -58,6 +89,15 @@ impl Report {
         self.diagnostics.push(Diagnostic::new(at, message));
         self
     }
+    /// Point the last diagnostic added to this report at another place
+    /// it refers to, such as the first definition of an attribute
+    /// defined twice
+    pub fn related<S: AsRef<str>>(mut self, at: TextRange, message: S) -> Self {
+        if let Some(diagnostic) = self.diagnostics.last_mut() {
+            diagnostic.related.push(Related::new(at, message));
+        }
+        self
+    }
     /// Add a diagnostic with a fix to this report
     pub fn suggest<S: AsRef<str>>(
         mut self,
@@ -69,11 +109,39 @@ impl Report {
             .push(Diagnostic::suggest(at, message, suggestion));
         self
     }
+    /// Add a diagnostic with the first of several alternative fixes
+    /// (ordered newest first) that the targeted versions support, or
+    /// without a fix if none of them are supported
+    pub fn suggest_newest<S: AsRef<str>>(
+        self,
+        at: TextRange,
+        message: S,
+        sess: &SessionInfo,
+        candidates: Vec<(Since, Suggestion)>,
+    ) -> Self {
+        match candidates
+            .into_iter()
+            .find(|(since, _)| sess.supports(*since))
+        {
+            Some((_, suggestion)) => self.suggest(at, message, suggestion),
+            None => self.diagnostic(at, message),
+        }
+    }
     /// Set severity level
     pub fn severity(mut self, severity: Severity) -> Self {
         self.severity = severity;
         self
     }
+    /// Set priority, see `subsume`
+    pub fn priority(mut self, priority: u8) -> Self {
+        self.priority = priority;
+        self
+    }
+    /// Set the documentation link
+    pub fn docs_url(mut self, docs_url: &'static str) -> Self {
+        self.docs_url = Some(docs_url);
+        self
+    }
     /// A range that encompasses all the suggestions provided in this report
     pub fn total_suggestion_range(&self) -> Option<TextRange> {
         self.diagnostics
@@ -94,32 +162,48 @@ impl Report {
     }
     /// Apply all diagnostics. Assumption: diagnostics do not overlap
     pub fn apply(&self, src: &mut String) {
-        for d in self.diagnostics.iter() {
+        // back to front, so that the ranges of the remaining fixes stay valid
+        let mut diagnostics = self.diagnostics.iter().collect::<Vec<_>>();
+        diagnostics.sort_by_key(|d| Reverse(d.suggestion.as_ref().map(|s| s.at.start())));
+        for d in diagnostics {
             d.apply(src);
         }
     }
-    /// Create a report out of a parse error
-    pub fn from_parse_err(err: ParseError) -> Self {
-        let at = match err {
-            ParseError::Unexpected(at) => at,
-            ParseError::UnexpectedExtra(at) => at,
-            ParseError::UnexpectedWanted(_, at, _) => at,
-            ParseError::UnexpectedDoubleBind(at) => at,
-            ParseError::UnexpectedEOF | ParseError::UnexpectedEOFWanted(_) => {
-                TextRange::empty(0u32.into())
+    /// Create a report out of the parse errors of a file, with a diagnostic
+    /// for each error. `end` is the end of the file, where errors about a
+    /// missing token point
+    pub fn from_parse_errs(errs: Vec<ParseError>, end: TextSize) -> Option<Self> {
+        if errs.is_empty() {
+            return None;
+        }
+        let report = Self::new("Syntax error", 0).severity(Severity::Error);
+        Some(errs.into_iter().fold(report, |report, err| {
+            let at = match err {
+                ParseError::Unexpected(at) => at,
+                ParseError::UnexpectedExtra(at) => at,
+                ParseError::UnexpectedWanted(_, at, _) => at,
+                ParseError::UnexpectedDoubleBind(at) => at,
+                ParseError::UnexpectedEOF | ParseError::UnexpectedEOFWanted(_) => {
+                    TextRange::empty(end)
+                }
+                ParseError::DuplicatedArgs(at, _) => at,
+                // errors added by newer versions of rnix
+                _ => TextRange::empty(0u32.into()),
+            };
+            let mut message = err.to_string();
+            if let Some(first) = message.get_mut(0..1) {
+                first.make_ascii_uppercase();
             }
-            ParseError::DuplicatedArgs(at, _) => at,
-            _ => panic!("report a bug, pepper forgot to handle a parse error"),
-        };
-        let mut message = err.to_string();
-        message
-            .as_mut_str()
-            .get_mut(0..1)
-            .unwrap()
-            .make_ascii_uppercase();
-        Self::new("Syntax error", 0)
-            .diagnostic(at, message)
-            .severity(Severity::Error)
+            report.diagnostic(at, message)
+        }))
+    }
+    /// Create a report for a file that is not valid UTF-8, read with the
+    /// invalid parts replaced. `at` is the first replacement
+    pub fn from_invalid_utf8(at: TextRange) -> Self {
+        Self::new("File is not valid UTF-8", 0).diagnostic(
+            at,
+            "Invalid UTF-8 was replaced, this file will not be fixed",
+        )
     }
 }
 
@@ -130,6 +214,8 @@ pub struct Diagnostic {
     pub at: TextRange,
     pub message: String,
     pub suggestion: Option<Suggestion>,
+    /// Other places this diagnostic refers to, see `Report::related`
+    pub related: Vec<Related>,
 }
 
 impl Diagnostic {
@@ -139,6 +225,7 @@ impl Diagnostic {
             at,
             message: message.as_ref().into(),
             suggestion: None,
+            related: Vec::new(),
         }
     }
     /// Construct a diagnostic with a fix.
@@ -147,6 +234,7 @@ impl Diagnostic {
             at,
             message: message.as_ref().into(),
             suggestion: Some(suggestion),
+            related: Vec::new(),
         }
     }
     /// Apply a diagnostic to a source file
@@ -163,7 +251,7 @@ impl Serialize for Diagnostic {
     where
         S: Serializer,
     {
-        let mut s = serializer.serialize_struct("Diagnostic", 3)?;
+        let mut s = serializer.serialize_struct("Diagnostic", 4)?;
         let at = {
             let start = usize::from(self.at.start());
             let end = usize::from(self.at.end());
@@ -174,6 +262,44 @@ impl Serialize for Diagnostic {
         if let Some(suggestion) = &self.suggestion {
             s.serialize_field("suggestion", suggestion)?;
         }
+        if !self.related.is_empty() {
+            s.serialize_field("related", &self.related)?;
+        }
+        s.end()
+    }
+}
+
+/// Another place a diagnostic refers to, with a message of its own
+#[derive(Debug)]
+pub struct Related {
+    pub at: TextRange,
+    pub message: String,
+}
+
+impl Related {
+    /// Construct a related location.
+    pub fn new<S: AsRef<str>>(at: TextRange, message: S) -> Self {
+        Self {
+            at,
+            message: message.as_ref().into(),
+        }
+    }
+}
+
+#[cfg(feature = "json-out")]
+impl Serialize for Related {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut s = serializer.serialize_struct("Related", 2)?;
+        let at = {
+            let start = usize::from(self.at.start());
+            let end = usize::from(self.at.end());
+            (start, end)
+        };
+        s.serialize_field("at", &at)?;
+        s.serialize_field("message", &self.message)?;
         s.end()
     }
 }
@@ -184,6 +310,8 @@ impl Serialize for Diagnostic {
 pub struct Suggestion {
     pub at: TextRange,
     pub fix: SyntaxElement,
+    /// What the fix does, in a few words, shown next to it
+    pub description: Option<String>,
 }
 
 impl Suggestion {
@@ -192,8 +320,14 @@ impl Suggestion {
         Self {
             at,
             fix: fix.into(),
+            description: None,
         }
     }
+    /// Describe what the fix does, as in "replace with `inherit (pkgs) foo;`"
+    pub fn description<S: AsRef<str>>(mut self, description: S) -> Self {
+        self.description = Some(description.as_ref().into());
+        self
+    }
     /// Apply a suggestion to a source file
     pub fn apply(&self, src: &mut String) {
         let start = usize::from(self.at.start());
@@ -210,7 +344,7 @@ impl Serialize for Suggestion {
     where
         S: Serializer,
     {
-        let mut s = serializer.serialize_struct("Suggestion", 2)?;
+        let mut s = serializer.serialize_struct("Suggestion", 3)?;
         let at = {
             let start = usize::from(self.at.start());
             let end = usize::from(self.at.end());
@@ -219,14 +353,50 @@ impl Serialize for Suggestion {
         let fix = self.fix.to_string();
         s.serialize_field("at", &at)?;
         s.serialize_field("fix", &fix)?;
+        if let Some(description) = &self.description {
+            s.serialize_field("description", description)?;
+        }
         s.end()
     }
 }
 
+/// Drop the diagnostics of a file that are noise: repeated ones, and ones
+/// contained in a diagnostic of another lint with a higher priority, which
+/// rewrites the larger expression as a whole. Reports left without
+/// diagnostics are dropped.
+pub fn subsume(mut reports: Vec<Report>) -> Vec<Report> {
+    let spans = reports
+        .iter()
+        .flat_map(|r| {
+            r.diagnostics
+                .iter()
+                .map(move |d| (r.code, r.priority, d.at))
+        })
+        .collect::<Vec<_>>();
+    let mut seen = HashSet::new();
+    for report in &mut reports {
+        let (code, priority) = (report.code, report.priority);
+        report.diagnostics.retain(|d| {
+            seen.insert((code, d.at, d.message.clone()))
+                && !spans
+                    .iter()
+                    .any(|&(c, p, at)| c != code && p > priority && at.contains_range(d.at))
+        });
+    }
+    reports.retain(|r| !r.diagnostics.is_empty());
+    reports
+}
+
 /// Lint logic is defined via this trait. Do not implement manually,
 /// look at the `lint` attribute macro instead for implementing rules
 pub trait Rule {
     fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report>;
+    /// Check every file of a project at once, for lints that need to see
+    /// more than one file. These lints usually leave out `match_with`, and
+    /// only run with `statix check --project`
+    fn check_project(&self, _project: &Project, _sess: &SessionInfo) -> Vec<ProjectReport> {
+        Vec::new()
+    }
 }
 
 /// Contains information about the lint itself. Do not implement manually,
@@ -235,11 +405,31 @@ pub trait Metadata {
     fn name(&self) -> &'static str;
     fn note(&self) -> &'static str;
     fn code(&self) -> u32;
+    fn severity(&self) -> Severity;
+    fn priority(&self) -> u8;
+    fn docs_url(&self) -> Option<&'static str>;
+    fn min_nix_version(&self) -> Option<Version>;
+    fn max_nix_version(&self) -> Option<Version>;
+    fn modes(&self) -> &'static [Mode];
     fn report(&self) -> Report;
     fn match_with(&self, with: &SyntaxKind) -> bool;
     fn match_kind(&self) -> Vec<SyntaxKind>;
 }
 
+/// Sections of a lint's explanation, scraped from the `## What it does`,
+/// `## Why is this bad?` and `## Example` headings of its doc comment.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Explanation {
+    /// What the lint checks for
+    pub summary: &'static str,
+    /// Why the flagged code is a problem
+    pub rationale: &'static str,
+    /// Code that raises this lint
+    pub bad_example: Option<&'static str>,
+    /// Code that does not raise this lint
+    pub good_example: Option<&'static str>,
+}
+
 /// Contains offline explanation for each lint
 /// The `lint` macro scans nearby doc comments for
 /// explanations and derives this trait.
@@ -250,11 +440,24 @@ pub trait Explain {
     fn explanation(&self) -> &'static str {
         "no explanation found"
     }
+    fn sections(&self) -> Explanation {
+        Explanation::default()
+    }
 }
 
 /// Combines Rule and Metadata, do not implement manually, this is derived by
 /// the `lint` macro.
-pub trait Lint: Metadata + Explain + Rule + Send + Sync {}
+pub trait Lint: Metadata + Explain + Rule + Send + Sync {
+    /// Whether this lint applies to the Nix version and mode of this session,
+    /// see `min_nix_version` and `max_nix_version` (both inclusive), and `modes`
+    /// (an empty list of modes applies everywhere)
+    fn applies_to(&self, sess: &SessionInfo) -> bool {
+        let version = sess.version();
+        self.min_nix_version().is_none_or(|min| *version >= min)
+            && self.max_nix_version().is_none_or(|max| *version <= max)
+            && (self.modes().is_empty() || self.modes().contains(&sess.mode()))
+    }
+}
 
 /// Helper utility to take lints from modules and insert them into a map for efficient
 /// access. Mapping is from a SyntaxKind to a list of lints that apply on that Kind.
@@ -270,11 +473,11 @@ macro_rules! lints {
             mod $s;
         )*
         ::lazy_static::lazy_static! {
-            pub static ref LINTS: Vec<&'static Box<dyn $crate::Lint>> = {
-                let mut v = Vec::new();
+            pub static ref LINTS: Vec<&'static dyn $crate::Lint> = {
+                let mut v: Vec<&'static dyn $crate::Lint> = Vec::new();
                 $(
                     {
-                        let temp_lint = &*$s::LINT;
+                        let temp_lint = &**$s::LINT;
                         v.push(temp_lint);
                     }
                 )*
@@ -21,5 +21,70 @@ lints! {
     bool_simplification,
     useless_has_attr,
     repeated_keys,
-    empty_list_concat
+    empty_list_concat,
+    unexpected_import_args,
+    dead_code,
+    import_cycle,
+    missing_path,
+    flake_schema,
+    flake_inputs,
+    module_options,
+    unused_let_binding,
+    unused_argument,
+    unused_pattern_bind,
+    shadowed_binding,
+    duplicate_attribute,
+    mergeable_inherit,
+    useless_rec,
+    rec_instead_of_let,
+    bool_if_else,
+    double_negation,
+    constant_condition,
+    empty_set_update,
+    empty_string_concat,
+    interpolation_only_string,
+    path_to_string,
+    string_path,
+    hardcoded_store_path,
+    lookup_path,
+    current_system,
+    impure_get_env,
+    fetcher_without_hash,
+    branch_pin,
+    sri_hash,
+    deprecated_stdenv_lib,
+    deprecated_builtin,
+    deprecated_option_type,
+    obsolete_md_doc,
+    undocumented_option,
+    missing_default_text,
+    top_level_with,
+    broad_with,
+    with_shadowed,
+    overlay_naming,
+    debug_trace,
+    lib_debug,
+    explicit_phases,
+    incomplete_meta,
+    if_else_chain,
+    optional_string,
+    optional_list,
+    trivial_let,
+    manual_map_attrs,
+    single_element_concat,
+    import_from_derivation,
+    indented_interpolation,
+    path_concatenation,
+    manual_version_comparison,
+    redundant_module_wrapper,
+    unfiltered_src,
+    hardcoded_secret,
+    string_license,
+    combined_name,
+    build_tool_input,
+    phase_override,
+    non_string_interpolation,
+    length_comparison,
+    attr_names_comparison,
+    has_attr_function
 }
@@ -0,0 +1,89 @@
+use crate::{make, session::SessionInfo, utils::builtin_call, Metadata, Report, Rule, Suggestion};
+
+use macros::lint;
+use rnix::{
+    types::{BinOp, BinOpKind, List, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for comparisons of the names or values of a set with the
+/// empty list, such as `builtins.attrNames x == [ ]`, and compares the
+/// set with `{ }` instead.
+///
+/// ## Why is this bad?
+/// `attrNames` builds the list of every name of the set, only to check
+/// that there are none, while `x == { }` says that the set is checked
+/// for being empty.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   hasOverrides = builtins.attrNames overrides != [ ];
+/// }
+/// ```
+///
+/// Compare with the empty set:
+///
+/// ```nix
+/// {
+///   hasOverrides = overrides != { };
+/// }
+/// ```
+#[lint(
+    name = "attr_names_comparison",
+    note = "Found the names of a set compared with the empty list",
+    code = 87,
+    match_with = SyntaxKind::NODE_BIN_OP
+)]
+struct AttrNamesComparison;
+
+impl Rule for AttrNamesComparison {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let op = BinOp::cast(node.clone())?;
+        let (lhs, rhs) = (op.lhs()?, op.rhs()?);
+        let operator = match op.operator()? {
+            BinOpKind::Equal => "==",
+            BinOpKind::NotEqual => "!=",
+            _ => return None,
+        };
+
+        // `attrNames x == [ ]`, or `[ ] == attrNames x`
+        let (function, set) = match (names_of(&lhs), names_of(&rhs)) {
+            (Some(names), None) if is_empty_list(&rhs) => names,
+            (None, Some(names)) if is_empty_list(&lhs) => names,
+            _ => return None,
+        };
+
+        let at = node.text_range();
+        let empty = rnix::parse("{ }").node().first_child()?;
+        let replacement = make::binary(&set, operator, &empty);
+        let message = format!(
+            "Compare the set with `{{ }}` instead of comparing its `{}` with `[ ]`",
+            function
+        );
+        let fix = Suggestion::new(at, make::replacing(replacement.node(), node))
+            .description(format!("replace with `{}`", replacement.node()));
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// The function and set of `builtins.attrNames x`, `lib.attrValues x` or
+// `attrNames x`
+fn names_of(node: &SyntaxNode) -> Option<(String, SyntaxNode)> {
+    ["attrNames", "attrValues"]
+        .iter()
+        .find_map(|name| match builtin_call(node, name)?.as_slice() {
+            [set] => Some((name.to_string(), set.clone())),
+            _ => None,
+        })
+}
+
+fn is_empty_list(node: &SyntaxNode) -> bool {
+    matches!(List::cast(node.clone()), Some(list) if list.items().next().is_none())
+}
@@ -30,6 +30,7 @@ use rnix::{
     name = "bool_comparison",
     note = "Unnecessary comparison with boolean",
     code = 1,
+    priority = 1,
     match_with = SyntaxKind::NODE_BIN_OP
 )]
 struct BoolComparison;
@@ -61,28 +62,7 @@ impl Rule for BoolComparison {
                         },
                         (NixBoolean::True, false) | (NixBoolean::False, true) => {
                             // `a != true`, `a == false` replace with `!a`
-                            match non_bool_side.kind() {
-                                SyntaxKind::NODE_APPLY
-                                    | SyntaxKind::NODE_PAREN
-                                    | SyntaxKind::NODE_IDENT => {
-                                    // do not parenthsize the replacement
-                                    make::unary_not(&non_bool_side).node().clone()
-                                },
-                                SyntaxKind::NODE_BIN_OP => {
-                                    let inner = BinOp::cast(non_bool_side.clone()).unwrap();
-                                    // `!a ? b`, no paren required
-                                    if inner.operator()? == BinOpKind::IsSet {
-                                        make::unary_not(&non_bool_side).node().clone()
-                                    } else {
-                                        let parens = make::parenthesize(&non_bool_side);
-                                        make::unary_not(parens.node()).node().clone()
-                                    }
-                                },
-                                _ => {
-                                    let parens = make::parenthesize(&non_bool_side);
-                                    make::unary_not(parens.node()).node().clone()
-                                }
-                            }
+                            make::negation(&non_bool_side).node().clone()
                         },
                     }
                 };
@@ -91,7 +71,8 @@ impl Rule for BoolComparison {
                     non_bool_side,
                     bool_side
                 );
-                Some(self.report().suggest(at, message, Suggestion::new(at, replacement)))
+                let description = format!("replace with `{}`", replacement);
+                Some(self.report().suggest(at, message, Suggestion::new(at, replacement).description(description)))
             } else {
                 None
             }
@@ -0,0 +1,60 @@
+use crate::{make, session::SessionInfo, utils::bool_literal, Metadata, Report, Rule, Suggestion};
+
+use macros::lint;
+use rnix::{
+    types::{IfElse, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind,
+};
+
+/// ## What it does
+/// Checks for `if` expressions choosing between `true` and `false`.
+///
+/// ## Why is this bad?
+/// The condition already is the boolean the expression evaluates to.
+///
+/// ## Example
+///
+/// ```nix
+/// enable = if cfg.port != null then true else false;
+/// ```
+///
+/// Use the condition:
+///
+/// ```nix
+/// enable = cfg.port != null;
+/// ```
+#[lint(
+    name = "bool_if_else",
+    note = "Found an `if` choosing between booleans",
+    code = 39,
+    match_with = SyntaxKind::NODE_IF_ELSE
+)]
+struct BoolIfElse;
+
+impl Rule for BoolIfElse {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let if_else = IfElse::cast(node.clone())?;
+        let condition = if_else.condition()?;
+        let replacement = match (
+            bool_literal(&if_else.body()?)?,
+            bool_literal(&if_else.else_body()?)?,
+        ) {
+            (true, false) => condition,
+            (false, true) => make::negation(&condition).node().clone(),
+            // `if c then true else true`, left to the reader
+            _ => return None,
+        };
+        let at = node.text_range();
+        let message = format!("This `if` is the same as `{}`", replacement);
+        let description = format!("replace with `{}`", replacement);
+        Some(self.report().suggest(
+            at,
+            message,
+            Suggestion::new(at, replacement).description(description),
+        ))
+    }
+}
@@ -27,6 +27,7 @@ use rnix::{
     name = "bool_simplification",
     note = "This boolean expression can be simplified",
     code = 18,
+    priority = 1,
     match_with = SyntaxKind::NODE_UNARY_OP
 )]
 struct BoolSimplification;
@@ -49,9 +50,10 @@ impl Rule for BoolSimplification {
                 let lhs = bin_expr.lhs()?;
                 let rhs = bin_expr.rhs()?;
                 let replacement = make::binary(&lhs, "!=", &rhs).node().clone();
+                let description = format!("replace with `{}`", replacement);
                 Some(
                     self.report()
-                        .suggest(at, message, Suggestion::new(at, replacement)),
+                        .suggest(at, message, Suggestion::new(at, replacement).description(description)),
                 )
             } else {
                 None
@@ -0,0 +1,125 @@
+use crate::{
+    session::SessionInfo,
+    utils::{attr_name, function_name, string_literal},
+    Metadata, Report, Rule,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, AttrSet, EntryHolder, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// Fetchers of git repositories, in builtins and nixpkgs
+const GIT_FETCHERS: &[&str] = &[
+    "fetchGit",
+    "fetchgit",
+    "fetchFromGitHub",
+    "fetchFromGitLab",
+    "fetchFromGitea",
+    "fetchFromBitbucket",
+    "fetchFromSourcehut",
+    "fetchFromCodeberg",
+];
+
+/// Names that are branches in most repositories
+const BRANCHES: &[&str] = &[
+    "HEAD",
+    "master",
+    "main",
+    "trunk",
+    "develop",
+    "development",
+    "dev",
+    "nightly",
+    "unstable",
+    "staging",
+    "nixos-unstable",
+    "nixpkgs-unstable",
+];
+
+/// ## What it does
+/// Checks for git fetchers pinned to a branch, such as
+/// `rev = "master";`, or `ref = "main";` without a `rev`.
+///
+/// ## Why is this bad?
+/// A branch moves: the fetched source changes with every push, so
+/// the expression is not reproducible, and a hash of the source stops
+/// matching.
+///
+/// ## Example
+///
+/// ```nix
+/// fetchFromGitHub {
+///   owner = "NixOS";
+///   repo = "nix";
+///   rev = "master";
+///   hash = "...";
+/// }
+/// ```
+///
+/// Pin a commit or a tag:
+///
+/// ```nix
+/// fetchFromGitHub {
+///   owner = "NixOS";
+///   repo = "nix";
+///   rev = "2.17.0";
+///   hash = "...";
+/// }
+/// ```
+#[lint(
+    name = "branch_pin",
+    note = "Found a fetcher pinned to a branch",
+    code = 52,
+    match_with = SyntaxKind::NODE_APPLY
+)]
+struct BranchPin;
+
+impl Rule for BranchPin {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        if !is_git_fetcher(&apply.lambda()?) {
+            return None;
+        }
+        let set = AttrSet::cast(apply.value()?)?;
+        let entries = set
+            .entries()
+            .filter_map(|kv| Some((attr_name(&kv.key()?.path().next()?)?, kv.value()?)))
+            .collect::<Vec<_>>();
+        let has_rev = entries.iter().any(|(name, _)| name == "rev");
+        let mut report = self.report();
+        for (name, value) in &entries {
+            // `ref` and `branch` are only used to find `rev`, if given
+            let pins =
+                name == "rev" || name == "tag" || (!has_rev && (name == "ref" || name == "branch"));
+            let branch = match string_literal(value) {
+                Some(branch) if pins && is_branch(&branch) => branch,
+                _ => continue,
+            };
+            let message = format!(
+                "`{}` is a branch, pin the `rev` of a commit or a tag instead",
+                branch
+            );
+            report = report.diagnostic(value.text_range(), message);
+        }
+        if report.diagnostics.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+
+fn is_git_fetcher(function: &SyntaxNode) -> bool {
+    matches!(function_name(function), Some(name) if GIT_FETCHERS.contains(&name.as_str()))
+}
+
+fn is_branch(name: &str) -> bool {
+    let name = name.strip_prefix("refs/heads/").unwrap_or(name);
+    BRANCHES.contains(&name)
+}
@@ -0,0 +1,103 @@
+use crate::{
+    session::{Mode, SessionInfo},
+    utils::is_top_level,
+    Metadata, Report, Rule, Severity,
+};
+
+use macros::lint;
+use rnix::{
+    types::{TypedNode, With},
+    NodeOrToken, SyntaxElement, SyntaxKind, TextRange,
+};
+
+/// ## What it does
+/// Checks for `with` expressions inside of another `with`, and for
+/// `with` over more than 20 lines. The number of lines, and the
+/// severity, can be set in statix.toml:
+///
+/// ```toml
+/// [options.broad_with]
+/// max_lines = 50
+/// severity = "hint"
+/// ```
+///
+/// ## Why is this bad?
+/// A variable that is not bound anywhere around it may come from any
+/// `with` it is in, which is only known when evaluating. The more the
+/// `with` covers, and the more of them there are, the harder it gets
+/// to tell where a name comes from.
+///
+/// ## Example
+///
+/// ```nix
+/// with pkgs;
+/// with lib;
+/// {
+///   packages = [ hello ];
+/// }
+/// ```
+///
+/// Select from the set, or use a `with` over the list only:
+///
+/// ```nix
+/// {
+///   packages = with pkgs; [ hello ];
+/// }
+/// ```
+#[lint(
+    name = "broad_with",
+    note = "Found a `with` over a large or nested scope",
+    code = 61,
+    match_with = SyntaxKind::NODE_WITH
+)]
+struct BroadWith;
+
+impl Rule for BroadWith {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let with = With::cast(node.clone())?;
+        let namespace = with.namespace()?;
+        let body = with.body()?;
+        // `with lib;` over a module is `top_level_with`
+        if sess.mode() == Mode::NixosModule
+            && namespace.kind() == SyntaxKind::NODE_IDENT
+            && namespace.text() == "lib"
+            && is_top_level(node)
+        {
+            return None;
+        }
+        let outer = node.ancestors().skip(1).filter_map(With::cast).find(
+            |w| matches!(w.body(), Some(b) if b.text_range().contains_range(node.text_range())),
+        );
+        let max_lines = sess
+            .option(self.name(), "max_lines")
+            .and_then(|values| values.first()?.parse::<usize>().ok())
+            .unwrap_or(20);
+        let lines = body.to_string().lines().count();
+        let message = if let Some(outer) = outer {
+            let outer = outer.namespace()?;
+            format!(
+                "Variables here may come from `{}` or from `{}`",
+                namespace, outer
+            )
+        } else if lines > max_lines {
+            let scope = if is_top_level(node) {
+                "the whole file".to_owned()
+            } else {
+                format!("{} lines", lines)
+            };
+            format!("`with {};` covers {}", namespace, scope)
+        } else {
+            return None;
+        };
+        let severity = sess
+            .option(self.name(), "severity")
+            .and_then(|values| values.first()?.parse::<Severity>().ok())
+            .unwrap_or_else(|| self.severity());
+        let at = TextRange::new(node.text_range().start(), body.text_range().start());
+        Some(self.report().severity(severity).diagnostic(at, message))
+    }
+}
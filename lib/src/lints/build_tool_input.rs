@@ -0,0 +1,232 @@
+use crate::{
+    make,
+    session::{Mode, SessionInfo},
+    utils::{
+        attr_name, derivation_attrs, function_name, indentation, with_preceeding_whitespace,
+        BUILDERS,
+    },
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, EntryHolder, List, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
+};
+
+/// Packages run during the build, rather than linked against
+const TOOLS: &[&str] = &[
+    "autoconf",
+    "automake",
+    "autoPatchelfHook",
+    "autoreconfHook",
+    "bison",
+    "cmake",
+    "copyDesktopItems",
+    "desktop-file-utils",
+    "doxygen",
+    "extra-cmake-modules",
+    "flex",
+    "gnum4",
+    "help2man",
+    "installShellFiles",
+    "intltool",
+    "libtool",
+    "makeBinaryWrapper",
+    "makeWrapper",
+    "meson",
+    "ninja",
+    "pkg-config",
+    "pkgconfig",
+    "qmake",
+    "removeReferencesTo",
+    "scdoc",
+    "texinfo",
+    "wrapGAppsHook",
+    "wrapGAppsHook3",
+    "wrapGAppsHook4",
+    "wrapQtAppsHook",
+];
+
+/// ## What it does
+/// Checks derivations for build tools, such as `cmake` or
+/// `pkg-config`, in `buildInputs`, and moves them to
+/// `nativeBuildInputs` when both are written out as lists.
+///
+/// ## Why is this bad?
+/// `buildInputs` are built for the platform the package runs on, and
+/// `nativeBuildInputs` for the platform building it. Both are the same
+/// when building natively, but when cross-compiling, a tool in
+/// `buildInputs` is built for the wrong platform and cannot be run.
+/// Setup hooks such as `makeWrapper` are only run from
+/// `nativeBuildInputs` then.
+///
+/// ## Example
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   buildInputs = [ cmake zlib ];
+/// }
+/// ```
+///
+/// Move the tools to `nativeBuildInputs`:
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   nativeBuildInputs = [ cmake ];
+///   buildInputs = [ zlib ];
+/// }
+/// ```
+#[lint(
+    name = "build_tool_input",
+    note = "Found a build tool in `buildInputs`",
+    code = 83,
+    match_with = SyntaxKind::NODE_APPLY,
+    modes = [Mode::Nixpkgs]
+)]
+struct BuildToolInput;
+
+impl Rule for BuildToolInput {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        if !BUILDERS.contains(&function_name(&apply.lambda()?)?.as_str()) {
+            return None;
+        }
+        let set = derivation_attrs(&apply.value()?)?;
+        let mut build_inputs = None;
+        let mut native_inputs = None;
+        for kv in set.entries() {
+            let key = kv.key()?;
+            let first = attr_name(&key.path().next()?);
+            match first.as_deref() {
+                Some("buildInputs") if key.path().count() == 1 => build_inputs = Some(kv),
+                Some("nativeBuildInputs") => native_inputs = Some(kv),
+                _ => {}
+            }
+        }
+        let build_inputs = build_inputs?;
+        let inputs = List::cast(build_inputs.value()?)?;
+        let items = inputs.items().collect::<Vec<_>>();
+        let tools = items
+            .iter()
+            .filter(
+                |item| matches!(function_name(item), Some(name) if TOOLS.contains(&name.as_str())),
+            )
+            .collect::<Vec<_>>();
+        if tools.is_empty() {
+            return None;
+        }
+        let tool_list = tools
+            .iter()
+            .map(|tool| tool.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let edits = match &native_inputs {
+            // `buildInputs = [ cmake ];`
+            None if tools.len() == items.len() => {
+                vec![(
+                    build_inputs.key()?.node().text_range(),
+                    "nativeBuildInputs".to_owned(),
+                )]
+            }
+            // `nativeBuildInputs = [ cmake ];` before `buildInputs`
+            None => {
+                let separator = if set.node().to_string().contains('\n') {
+                    format!("{}{}", sess.style().eol(), indentation(build_inputs.node()))
+                } else {
+                    " ".to_owned()
+                };
+                let start = build_inputs.node().text_range().start();
+                let mut edits = vec![(
+                    TextRange::empty(start),
+                    format!("nativeBuildInputs = [ {} ];{}", tool_list, separator),
+                )];
+                edits.extend(removals(&tools));
+                edits
+            }
+            // the tools appended to `nativeBuildInputs`
+            Some(native) => {
+                let native = match native.value().and_then(List::cast) {
+                    Some(list) => list,
+                    None => return Some(self.diagnostics(&tools)),
+                };
+                let mut edits = vec![appended(&native, &tools, sess)];
+                if tools.len() == items.len() {
+                    edits.push((
+                        with_preceeding_whitespace(build_inputs.node()),
+                        String::new(),
+                    ));
+                } else {
+                    edits.extend(removals(&tools));
+                }
+                edits
+            }
+        };
+        let replacement = make::edited(&set, &edits);
+        let fix = Suggestion::new(set.node().text_range(), replacement.node().clone())
+            .description("move to `nativeBuildInputs`");
+        let first = tools.first()?;
+        let report = self
+            .report()
+            .suggest(first.text_range(), message(first), fix);
+        let report = tools[1..].iter().fold(report, |report, tool| {
+            report.diagnostic(tool.text_range(), message(tool))
+        });
+        Some(report)
+    }
+}
+
+impl BuildToolInput {
+    fn diagnostics(&self, tools: &[&SyntaxNode]) -> Report {
+        tools.iter().fold(self.report(), |report, tool| {
+            report.diagnostic(tool.text_range(), message(tool))
+        })
+    }
+}
+
+fn message(tool: &SyntaxNode) -> String {
+    format!(
+        "`{}` runs during the build, move it to `nativeBuildInputs`",
+        tool
+    )
+}
+
+fn removals(tools: &[&SyntaxNode]) -> Vec<(TextRange, String)> {
+    tools
+        .iter()
+        .map(|tool| (with_preceeding_whitespace(tool), String::new()))
+        .collect()
+}
+
+// `tools` inserted after the last item of `list`, on a line of their
+// own if the items are
+fn appended(list: &List, tools: &[&SyntaxNode], sess: &SessionInfo) -> (TextRange, String) {
+    match list.items().last() {
+        Some(last) => {
+            let separator = if list.node().to_string().contains('\n') {
+                format!("{}{}", sess.style().eol(), indentation(&last))
+            } else {
+                " ".to_owned()
+            };
+            let text = tools
+                .iter()
+                .map(|tool| format!("{}{}", separator, tool))
+                .collect::<String>();
+            (TextRange::empty(last.text_range().end()), text)
+        }
+        None => {
+            let text = tools
+                .iter()
+                .map(|tool| tool.to_string())
+                .collect::<Vec<_>>()
+                .join(" ");
+            (list.node().text_range(), format!("[ {} ]", text))
+        }
+    }
+}
@@ -40,6 +40,7 @@ use rowan::Direction;
     name = "collapsible_let_in",
     note = "These let-in expressions are collapsible",
     code = 6,
+    priority = 1,
     match_with = SyntaxKind::NODE_LET_IN
 )]
 struct CollapsibleLetIn;
@@ -77,7 +78,7 @@ impl Rule for CollapsibleLetIn {
                 Some(
                     self.report()
                         .diagnostic(first_annotation, first_message)
-                        .suggest(second_annotation, second_message, Suggestion::new(replacement_at, replacement))
+                        .suggest(second_annotation, second_message, Suggestion::new(replacement_at, replacement).description("merge into the outer `let`"))
                 )
             } else {
                 None
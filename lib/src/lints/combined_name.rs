@@ -0,0 +1,115 @@
+use crate::{
+    make,
+    session::SessionInfo,
+    utils::{attr_name, derivation_attrs, function_name, indentation, BUILDERS},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, EntryHolder, Str, TypedNode},
+    NodeOrToken, StrPart, SyntaxElement, SyntaxKind,
+};
+
+/// ## What it does
+/// Checks for derivations with a `name` holding the version, such as
+/// `name = "hello-2.12";`, and splits it into `pname` and `version`.
+///
+/// ## Why is this bad?
+/// With `pname` and `version`, overrides can change the version without
+/// repeating the name, tools such as `nix-update` and repology find the
+/// version, and `nix-env --upgrade` compares versions rather than
+/// guessing them from the name.
+///
+/// ## Example
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   name = "hello-2.12";
+/// }
+/// ```
+///
+/// Use `pname` and `version`:
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   version = "2.12";
+/// }
+/// ```
+#[lint(
+    name = "combined_name",
+    note = "Found the version in the `name` of a derivation",
+    code = 82,
+    match_with = SyntaxKind::NODE_APPLY
+)]
+struct CombinedName;
+
+impl Rule for CombinedName {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        if !BUILDERS.contains(&function_name(&apply.lambda()?)?.as_str()) {
+            return None;
+        }
+        let set = derivation_attrs(&apply.value()?)?;
+        let mut name = None;
+        let mut has_version = false;
+        for kv in set.entries() {
+            let key = kv.key()?;
+            let first = attr_name(&key.path().next()?);
+            match first.as_deref() {
+                Some("pname") => return None,
+                Some("name") if key.path().count() == 1 => name = Some(kv),
+                Some("version") => has_version = true,
+                _ => {}
+            }
+        }
+        let name = name?;
+        let value = Str::cast(name.value()?)?;
+        let at = name.node().text_range();
+        let message = "Split the `name` into `pname` and `version`";
+        let entries = match value.parts().as_slice() {
+            // `name = "hello-2.12";`
+            [StrPart::Literal(text)] if !has_version => {
+                let (pname, version) = split_name(text)?;
+                vec![
+                    format!("pname = \"{}\";", pname),
+                    format!("version = \"{}\";", version),
+                ]
+            }
+            // `name = "hello-${version}";`, or `${finalAttrs.version}`
+            [StrPart::Literal(text), StrPart::Ast(version)] if has_version => {
+                let pname = text.strip_suffix('-')?;
+                if function_name(&version.first_child()?).as_deref() != Some("version") {
+                    return Some(self.report().diagnostic(at, message));
+                }
+                vec![format!("pname = \"{}\";", pname)]
+            }
+            _ => return None,
+        };
+        let separator = if set.node().to_string().contains('\n') {
+            format!("{}{}", sess.style().eol(), indentation(name.node()))
+        } else {
+            " ".to_owned()
+        };
+        let replacement = make::edited(&set, &[(at, entries.join(&separator))]);
+        let fix = Suggestion::new(set.node().text_range(), replacement.node().clone())
+            .description("use `pname` and `version`");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// `hello` and `2.12` for `hello-2.12`: the version starts at the first
+// dash followed by something else than a letter, as for
+// `builtins.parseDrvName`
+fn split_name(name: &str) -> Option<(&str, &str)> {
+    let dash = name
+        .match_indices('-')
+        .map(|(i, _)| i)
+        .find(|&i| matches!(name[i + 1..].chars().next(), Some(c) if !c.is_ascii_alphabetic()))?;
+    Some((&name[..dash], &name[dash + 1..]))
+}
@@ -0,0 +1,111 @@
+use crate::{
+    make,
+    session::SessionInfo,
+    utils::{bool_literal, unparenthesized},
+    Metadata, Report, Rule, Severity, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Assert, BinOp, BinOpKind, IfElse, Str, TypedNode},
+    NodeOrToken, StrPart, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for `if` and `assert` expressions with a literal `true` or
+/// `false` as condition, or a comparison of a literal with itself, such
+/// as `"a" == "a"`.
+///
+/// ## Why is this bad?
+/// Only one branch can ever be taken, and the assertion either never
+/// fails, or always does. These are usually leftovers from debugging,
+/// or configuration that is dead.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   extraFlags = if false then [ "--verbose" ] else [ ];
+/// }
+/// ```
+///
+/// Keep the branch that is taken:
+///
+/// ```nix
+/// {
+///   extraFlags = [ ];
+/// }
+/// ```
+#[lint(
+    name = "constant_condition",
+    note = "Found a constant condition",
+    code = 41,
+    match_with = [SyntaxKind::NODE_IF_ELSE, SyntaxKind::NODE_ASSERT]
+)]
+struct ConstantCondition;
+
+impl Rule for ConstantCondition {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        if let Some(if_else) = IfElse::cast(node.clone()) {
+            let condition = if_else.condition()?;
+            let value = constant(&condition)?;
+            let (taken, branch) = if value {
+                (if_else.body()?, "then")
+            } else {
+                (if_else.else_body()?, "else")
+            };
+            let message = format!("This condition is always `{}`", value);
+            let fix = Suggestion::new(node.text_range(), make::replacing(&taken, node))
+                .description(format!("replace with the `{}` branch", branch));
+            Some(self.report().suggest(condition.text_range(), message, fix))
+        } else {
+            let assert = Assert::cast(node.clone())?;
+            let condition = assert.condition()?;
+            if !constant(&condition)? {
+                return Some(self.report().severity(Severity::Error).diagnostic(
+                    condition.text_range(),
+                    "This assertion always fails, use `throw` to fail with a message",
+                ));
+            }
+            let message = "This assertion always holds";
+            let fix = Suggestion::new(node.text_range(), make::replacing(&assert.body()?, node))
+                .description("remove the assertion");
+            Some(self.report().suggest(condition.text_range(), message, fix))
+        }
+    }
+}
+
+// the value of `true`, `false`, and of comparisons of a literal with
+// itself
+fn constant(condition: &SyntaxNode) -> Option<bool> {
+    let condition = unparenthesized(condition);
+    if let Some(value) = bool_literal(&condition) {
+        return Some(value);
+    }
+    let op = BinOp::cast(condition)?;
+    let (lhs, rhs) = (op.lhs()?, op.rhs()?);
+    if !is_literal(&lhs) || lhs.to_string() != rhs.to_string() {
+        return None;
+    }
+    match op.operator()? {
+        BinOpKind::Equal => Some(true),
+        BinOpKind::NotEqual => Some(false),
+        _ => None,
+    }
+}
+
+fn is_literal(node: &SyntaxNode) -> bool {
+    match node.kind() {
+        SyntaxKind::NODE_LITERAL => true,
+        SyntaxKind::NODE_STRING => matches!(
+            Str::cast(node.clone()),
+            Some(s) if s.parts().iter().all(|p| matches!(p, StrPart::Literal(_)))
+        ),
+        SyntaxKind::NODE_IDENT => bool_literal(node).is_some() || node.text() == "null",
+        _ => false,
+    }
+}
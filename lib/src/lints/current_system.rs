@@ -0,0 +1,86 @@
+use crate::{
+    make,
+    scope::{self, Binding, BindingKind},
+    session::{Mode, SessionInfo},
+    Metadata, Report, Rule, Severity, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Select, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind,
+};
+
+/// ## What it does
+/// Checks for `builtins.currentSystem` in flakes.
+///
+/// ## Why is this bad?
+/// Flakes are evaluated purely, where `builtins.currentSystem` is not
+/// available. The outputs of a flake are defined for each system
+/// instead, such as `packages.x86_64-linux`, usually by a function
+/// taking `system`, as with `flake-utils.lib.eachDefaultSystem`.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   outputs = { nixpkgs, flake-utils, ... }:
+///     flake-utils.lib.eachDefaultSystem (system: {
+///       packages.default = nixpkgs.legacyPackages.${builtins.currentSystem}.hello;
+///     });
+/// }
+/// ```
+///
+/// Use the system the outputs are defined for:
+///
+/// ```nix
+/// {
+///   outputs = { nixpkgs, flake-utils, ... }:
+///     flake-utils.lib.eachDefaultSystem (system: {
+///       packages.default = nixpkgs.legacyPackages.${system}.hello;
+///     });
+/// }
+/// ```
+#[lint(
+    name = "current_system",
+    note = "Found `builtins.currentSystem` in a flake",
+    code = 49,
+    severity = Severity::Error,
+    match_with = SyntaxKind::NODE_SELECT,
+    modes = [Mode::Flake]
+)]
+struct CurrentSystem;
+
+impl Rule for CurrentSystem {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let select = Select::cast(node.clone())?;
+        if select.set()?.text() != "builtins" || select.index()?.text() != "currentSystem" {
+            return None;
+        }
+        let at = node.text_range();
+        // a `system` argument or binding around the select
+        let system = scope::lookup(node, "system");
+        // `let system = builtins.currentSystem;` and `{ system ? builtins.currentSystem }:`
+        // define `system` with the select itself
+        let defines_system = |b: &Binding| {
+            matches!(b.kind, BindingKind::Let | BindingKind::PatEntry)
+                && b.entry.text_range().contains_range(at)
+        };
+        match system {
+            Some(binding) if binding.kind != BindingKind::Rec && !defines_system(&binding) => {
+                let message = "`builtins.currentSystem` is not available in pure evaluation, use `system` instead";
+                let fix = Suggestion::new(at, make::ident("system").node().clone())
+                    .description("replace with `system`");
+                Some(self.report().suggest(at, message, fix))
+            }
+            _ => {
+                let message = "`builtins.currentSystem` is not available in pure evaluation, define the outputs for each system, with a function taking `system`";
+                Some(self.report().diagnostic(at, message))
+            }
+        }
+    }
+}
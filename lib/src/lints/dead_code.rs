@@ -0,0 +1,162 @@
+use std::collections::HashSet;
+
+use crate::{
+    project::{File, Import, ImportKind, Project, ProjectReport},
+    session::SessionInfo,
+    utils::references,
+    Metadata, Report, Rule,
+};
+
+use macros::lint;
+use rnix::{
+    types::{
+        AttrSet, EntryHolder, Ident, Inherit, KeyValue, LetIn, Select, TokenWrapper, TypedNode,
+    },
+    SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
+};
+
+/// ## What it does
+/// Checks for code that is never used: files that are not imported
+/// from any entry point of the project, and attributes of an imported
+/// file that none of its importers select. Runs with `statix check
+/// --project`; the entry points are `flake.nix`, `default.nix` and
+/// `shell.nix`, or the `roots` set in `statix.toml`. Unused `let`
+/// bindings are reported by `unused_let_binding`.
+///
+/// ## Why is this bad?
+/// Dead code still has to be read and maintained, and is usually left
+/// over from a refactor.
+///
+/// ## Example
+///
+/// ```nix
+/// # ./lib.nix, imported as `(import ./lib.nix).double`
+/// {
+///   double = x: x * 2;
+///   triple = x: x * 3;
+/// }
+/// ```
+///
+/// Remove what is not used:
+///
+/// ```nix
+/// {
+///   double = x: x * 2;
+/// }
+/// ```
+#[lint(name = "dead_code", note = "Found unused code", code = 25)]
+struct DeadCode;
+
+impl Rule for DeadCode {
+    fn validate(&self, _node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        None
+    }
+
+    fn check_project(&self, project: &Project, _sess: &SessionInfo) -> Vec<ProjectReport> {
+        let mut reports = Vec::new();
+        for (idx, reachable) in project.reachable().into_iter().enumerate() {
+            let mut report = self.report();
+            if !project.roots().is_empty() && !reachable {
+                let at = TextRange::empty(0.into());
+                let message = "This file is not imported from any entry point of the project";
+                report = report.diagnostic(at, message);
+            } else if !project.roots().contains(&idx) {
+                for key in unused_members(project, idx) {
+                    let at = key.node().text_range();
+                    let message = format!(
+                        "`{}` is not used by any importer of this file",
+                        key.as_str()
+                    );
+                    report = report.diagnostic(at, message);
+                }
+            }
+            if !report.diagnostics.is_empty() {
+                reports.push(ProjectReport { file: idx, report });
+            }
+        }
+        reports
+    }
+}
+
+fn single_key(kv: &KeyValue) -> Option<Ident> {
+    let key = kv.key()?;
+    let mut path = key.path();
+    let ident = Ident::cast(path.next()?)?;
+    if path.next().is_some() {
+        None
+    } else {
+        Some(ident)
+    }
+}
+
+// top-level attributes of a file that no importer selects
+fn unused_members(project: &Project, idx: usize) -> Vec<Ident> {
+    let expr = project.file(idx).expr();
+    let expr = match expr.clone().and_then(LetIn::cast) {
+        Some(let_in) => let_in.body(),
+        None => expr,
+    };
+    let set = match expr.and_then(AttrSet::cast) {
+        Some(s) if !s.recursive() => s,
+        _ => return Vec::new(),
+    };
+    let mut used = HashSet::new();
+    let mut importers = project.importers(idx).peekable();
+    if importers.peek().is_none() {
+        return Vec::new();
+    }
+    for (from, import) in importers {
+        match selected(project.file(from), import) {
+            Some(names) => used.extend(names),
+            None => return Vec::new(),
+        }
+    }
+    set.entries()
+        .filter_map(|kv| single_key(&kv))
+        .filter(|ident| !used.contains(ident.as_str()))
+        .collect()
+}
+
+// attributes selected from an imported file, or `None` if the import
+// escapes in a way that any attribute could be used
+fn selected(file: &File, import: &Import) -> Option<HashSet<String>> {
+    if import.kind != ImportKind::Import || import.arg().is_some() {
+        return None;
+    }
+    let mut value = import.expr.clone();
+    while value.parent()?.kind() == SyntaxKind::NODE_PAREN {
+        value = value.parent()?;
+    }
+    let parent = value.parent()?;
+    if let Some(name) = select_index(&parent, &value) {
+        return Some(std::iter::once(name).collect());
+    }
+    // `let lib = import ./lib.nix; in lib.double`
+    let key_value = KeyValue::cast(parent.clone())?;
+    if key_value.value()? != value || parent.parent()?.kind() != SyntaxKind::NODE_LET_IN {
+        return None;
+    }
+    let binding = single_key(&key_value)?;
+    let mut names = HashSet::new();
+    for reference in references(&file.root, binding.as_str()) {
+        let parent = reference.parent()?;
+        if let Some(name) = select_index(&parent, &reference) {
+            names.insert(name);
+        } else if parent.kind() == SyntaxKind::NODE_INHERIT_FROM {
+            let inherit = Inherit::cast(parent.parent()?)?;
+            names.extend(inherit.idents().map(|i| i.as_str().to_owned()));
+        } else {
+            return None;
+        }
+    }
+    Some(names)
+}
+
+// `name` if `parent` is `set.name`
+fn select_index(parent: &SyntaxNode, set: &SyntaxNode) -> Option<String> {
+    let select = Select::cast(parent.clone())?;
+    if select.set()? != *set {
+        return None;
+    }
+    Ident::cast(select.index()?).map(|i| i.as_str().to_owned())
+}
@@ -0,0 +1,92 @@
+use crate::{
+    make,
+    session::SessionInfo,
+    utils::{call, function_name},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Str, TypedNode},
+    NodeOrToken, StrPart, SyntaxElement, SyntaxKind,
+};
+
+/// Trace functions, with the number of arguments before the value they
+/// return
+const TRACES: &[(&str, usize)] = &[
+    ("trace", 1),
+    ("traceIf", 2),
+    ("traceSeq", 1),
+    ("traceSeqN", 2),
+    ("traceVal", 0),
+    ("traceValFn", 1),
+    ("traceValSeq", 0),
+    ("traceValSeqFn", 1),
+    ("traceValSeqN", 1),
+    ("traceValSeqNFn", 2),
+];
+
+/// ## What it does
+/// Checks for calls to `builtins.trace` and to the trace functions of
+/// `lib.debug`, such as `lib.traceVal`, and replaces them with the
+/// value they return. Traces with a message starting with some prefix
+/// can be allowed in statix.toml:
+///
+/// ```toml
+/// [options.debug_trace]
+/// allow_prefix = [ "warning:" ]
+/// ```
+///
+/// ## Why is this bad?
+/// Traces are usually left over from debugging, and print on every
+/// evaluation. Warnings meant for users are better written with
+/// `lib.warn`.
+///
+/// ## Example
+///
+/// ```nix
+/// builtins.trace "version: ${version}" version
+/// ```
+///
+/// Remove the trace:
+///
+/// ```nix
+/// version
+/// ```
+#[lint(
+    name = "debug_trace",
+    note = "Found a trace left over from debugging",
+    code = 64,
+    match_with = SyntaxKind::NODE_APPLY
+)]
+struct DebugTrace;
+
+impl Rule for DebugTrace {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let (function, args) = call(node);
+        let name = function_name(&function)?;
+        let &(_, before) = TRACES.iter().find(|(trace, _)| *trace == name)?;
+        if args.len() != before + 1 {
+            return None;
+        }
+        if name == "trace" {
+            let allowed = sess.option(self.name(), "allow_prefix").unwrap_or_default();
+            let message = Str::cast(args[0].clone()).map(|s| s.parts());
+            if let Some([StrPart::Literal(message), ..]) = message.as_deref() {
+                if allowed.iter().any(|p| message.starts_with(p.as_str())) {
+                    return None;
+                }
+            }
+        }
+        let value = &args[before];
+        let at = node.text_range();
+        let message = format!("Remove the call to `{}`", name);
+        let replacement = make::replacing(value, node);
+        let fix = Suggestion::new(at, replacement).description("remove the trace");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
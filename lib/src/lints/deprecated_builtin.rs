@@ -0,0 +1,174 @@
+use crate::{
+    make,
+    session::{SessionInfo, Since, Version},
+    utils::GLOBALS,
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, Ident, Select, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// Builtins deprecated by Nix, as entries of the `deprecated` option
+const DEPRECATED: &[&str] = &[
+    // superseded by `fetchTree { type = "hg"; }`
+    "fetchMercurial@2.4",
+];
+
+/// ## What it does
+/// Checks for calls to builtins deprecated by the targeted version of
+/// Nix, and for `builtins.unsafeDiscardStringContext` dropping the
+/// dependency on a derivation. `toPath` and `isNull` are checked by
+/// `deprecated_to_path` and `deprecated_is_null`. The list of deprecated
+/// builtins can be replaced in statix.toml, as `name`, `name@version` to
+/// flag them from that version of Nix on, and `name=replacement` to
+/// suggest another builtin:
+///
+/// ```toml
+/// [options.deprecated_builtin]
+/// deprecated = [ "fetchMercurial@2.4", "hashString@2.21=convertHash" ]
+/// ```
+///
+/// ## Why is this bad?
+/// Deprecated builtins may be removed by newer versions of Nix.
+/// Discarding the context of a derivation's path gives a store path
+/// that is not built along with the expression using it.
+///
+/// ## Example
+///
+/// ```nix
+/// builtins.unsafeDiscardStringContext "${pkgs.hello}/bin/hello"
+/// ```
+///
+/// Keep the context:
+///
+/// ```nix
+/// "${pkgs.hello}/bin/hello"
+/// ```
+#[lint(
+    name = "deprecated_builtin",
+    note = "Found a deprecated builtin",
+    code = 55,
+    match_with = SyntaxKind::NODE_APPLY
+)]
+struct DeprecatedBuiltin;
+
+impl Rule for DeprecatedBuiltin {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        let function = apply.lambda()?;
+        let name = builtin_name(&function)?;
+
+        if name == "unsafeDiscardStringContext" {
+            let argument = apply.value()?;
+            let package = interpolated_package(&argument)?;
+            let at = node.text_range();
+            let message = format!(
+                "If `{}` is a derivation, this drops the dependency on it, which may then not be built",
+                package
+            );
+            return Some(self.report().diagnostic(at, message));
+        }
+
+        let entries = match sess.option(self.name(), "deprecated") {
+            Some(entries) => entries.iter().map(String::as_str).collect(),
+            None => DEPRECATED.to_vec(),
+        };
+        let deprecated = entries
+            .into_iter()
+            .filter_map(parse_deprecated)
+            .find(|d| d.name == name)?;
+        if let Some(since) = deprecated.since {
+            if !sess.supports(Since::Nix(since)) {
+                return None;
+            }
+        }
+        let at = function.text_range();
+        match deprecated.replacement {
+            Some(replacement) => {
+                let builtins = make::ident("builtins");
+                let replacement = make::select(builtins.node(), make::ident(replacement).node());
+                let message = format!(
+                    "`{}` is deprecated, use `{}` instead",
+                    function,
+                    replacement.node()
+                );
+                let description = format!("replace with `{}`", replacement.node());
+                Some(self.report().suggest(
+                    at,
+                    message,
+                    Suggestion::new(at, replacement.node().clone()).description(description),
+                ))
+            }
+            None => {
+                let message = format!("`{}` is deprecated", function);
+                Some(self.report().diagnostic(at, message))
+            }
+        }
+    }
+}
+
+/// An entry of the `deprecated` option: `name`, `name@version`,
+/// `name=replacement` or `name@version=replacement`
+struct Deprecated<'a> {
+    name: &'a str,
+    since: Option<Version>,
+    replacement: Option<&'a str>,
+}
+
+fn parse_deprecated(entry: &str) -> Option<Deprecated<'_>> {
+    let (entry, replacement) = match entry.split_once('=') {
+        Some((entry, replacement)) => (entry, Some(replacement.trim())),
+        None => (entry, None),
+    };
+    let (name, since) = match entry.split_once('@') {
+        Some((name, since)) => (name, Some(since.trim().parse().ok()?)),
+        None => (entry, None),
+    };
+    Some(Deprecated {
+        name: name.trim(),
+        since,
+        replacement,
+    })
+}
+
+// `builtins.name`, or `name` for the builtins in scope everywhere
+fn builtin_name(function: &SyntaxNode) -> Option<String> {
+    match Select::cast(function.clone()) {
+        Some(select) if select.set()?.text() == "builtins" => Some(select.index()?.to_string()),
+        Some(_) => None,
+        None => Ident::cast(function.clone())
+            .map(|i| i.as_str().to_owned())
+            .filter(|name| GLOBALS.contains(&name.as_str())),
+    }
+}
+
+// what looks like a package, `pkgs.hello` or `hello.out`, interpolated
+// into a string or passed to `toString`
+fn interpolated_package(node: &SyntaxNode) -> Option<SyntaxNode> {
+    let interpolated = match node.kind() {
+        SyntaxKind::NODE_STRING => node
+            .children()
+            .filter(|c| c.kind() == SyntaxKind::NODE_STRING_INTERPOL)
+            .filter_map(|c| c.first_child())
+            .collect::<Vec<_>>(),
+        SyntaxKind::NODE_PAREN => return interpolated_package(&node.first_child()?),
+        SyntaxKind::NODE_APPLY => {
+            let apply = Apply::cast(node.clone())?;
+            if builtin_name(&apply.lambda()?)? != "toString" {
+                return None;
+            }
+            vec![apply.value()?]
+        }
+        _ => return None,
+    };
+    interpolated
+        .into_iter()
+        .find(|expr| expr.kind() == SyntaxKind::NODE_SELECT)
+}
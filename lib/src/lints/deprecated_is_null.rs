@@ -51,7 +51,8 @@ impl Rule for DeprecatedIsNull {
                 let at = node.text_range();
                 let replacement = parenthesized.node().clone();
                 let message = "`isNull` is deprecated, check equality with `null` instead";
-                Some(self.report().suggest(at, message, Suggestion::new(at, replacement)))
+                let description = format!("replace with `{}`", replacement);
+                Some(self.report().suggest(at, message, Suggestion::new(at, replacement).description(description)))
             } else {
                 None
             }
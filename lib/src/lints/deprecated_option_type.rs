@@ -0,0 +1,80 @@
+use crate::{
+    make,
+    session::{Mode, SessionInfo},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Select, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind,
+};
+
+/// ## What it does
+/// Checks for the deprecated option types `types.string`, replaced by
+/// `types.str`, and `types.loaOf`, replaced by `types.attrsOf`.
+///
+/// ## Why is this bad?
+/// Both were removed from nixpkgs. `types.string` concatenated multiple
+/// definitions without a separator: `types.str` refuses them, and
+/// `types.lines` joins them with newlines.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   options.services.hello.greeting = lib.mkOption {
+///     type = lib.types.string;
+///   };
+/// }
+/// ```
+///
+/// Use `types.str`:
+///
+/// ```nix
+/// {
+///   options.services.hello.greeting = lib.mkOption {
+///     type = lib.types.str;
+///   };
+/// }
+/// ```
+#[lint(
+    name = "deprecated_option_type",
+    note = "Found a deprecated option type",
+    code = 56,
+    match_with = SyntaxKind::NODE_SELECT,
+    modes = [Mode::NixosModule]
+)]
+struct DeprecatedOptionType;
+
+impl Rule for DeprecatedOptionType {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let select = Select::cast(node.clone())?;
+        let set = select.set()?;
+        // `types.x` and `lib.types.x`
+        let is_types = match Select::cast(set.clone()) {
+            Some(inner) => inner.index()?.text() == "types",
+            None => set.kind() == SyntaxKind::NODE_IDENT && set.text() == "types",
+        };
+        if !is_types {
+            return None;
+        }
+        let index = select.index()?;
+        let (replacement, message) = match index.text().to_string().as_str() {
+            "string" => (
+                "str",
+                "`types.string` was removed, use `types.str`, or `types.lines` to merge definitions",
+            ),
+            "loaOf" => ("attrsOf", "`types.loaOf` was removed, use `types.attrsOf`"),
+            _ => return None,
+        };
+        let at = index.text_range();
+        let fix = Suggestion::new(at, make::ident(replacement).node().clone())
+            .description(format!("replace with `{}`", replacement));
+        Some(self.report().suggest(node.text_range(), message, fix))
+    }
+}
@@ -0,0 +1,141 @@
+use crate::{
+    make,
+    scope::{self, BindingKind},
+    session::SessionInfo,
+    utils::indentation,
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Lambda, Select, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
+};
+use rowan::Direction;
+
+/// ## What it does
+/// Checks for `stdenv.lib`, which was removed from nixpkgs in favour of
+/// `lib`. Where `lib` is not in scope, the fix adds it to the arguments
+/// taking `stdenv`.
+///
+/// ## Why is this bad?
+/// `stdenv.lib` was deprecated in nixpkgs 21.05, and no longer exists.
+///
+/// ## Example
+///
+/// ```nix
+/// { stdenv, fetchurl }:
+/// stdenv.mkDerivation {
+///   meta.license = stdenv.lib.licenses.mit;
+/// }
+/// ```
+///
+/// Use `lib`:
+///
+/// ```nix
+/// { lib, stdenv, fetchurl }:
+/// stdenv.mkDerivation {
+///   meta.license = lib.licenses.mit;
+/// }
+/// ```
+#[lint(
+    name = "deprecated_stdenv_lib",
+    note = "Found `stdenv.lib`",
+    code = 54,
+    match_with = SyntaxKind::NODE_SELECT
+)]
+struct DeprecatedStdenvLib;
+
+impl Rule for DeprecatedStdenvLib {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let select = Select::cast(node.clone())?;
+        if select.index()?.text() != "lib" {
+            return None;
+        }
+        let set = select.set()?;
+        // `stdenv.lib`, or `pkgs.stdenv.lib`
+        let from = match set.kind() {
+            SyntaxKind::NODE_IDENT if set.text() == "stdenv" => None,
+            SyntaxKind::NODE_SELECT => {
+                let inner = Select::cast(set.clone())?;
+                if inner.index()?.text() != "stdenv" {
+                    return None;
+                }
+                Some(inner.set()?)
+            }
+            _ => return None,
+        };
+        let at = node.text_range();
+        let message = "`stdenv.lib` was removed, use `lib` instead";
+        if let Some(from) = from {
+            let replacement = make::select(&from, make::ident("lib").node());
+            let description = format!("replace with `{}`", replacement.node());
+            let fix = Suggestion::new(at, replacement.node().clone()).description(description);
+            return Some(self.report().suggest(at, message, fix));
+        }
+        if scope::lookup(node, "lib").is_some() {
+            let fix = Suggestion::new(at, make::ident("lib").node().clone())
+                .description("replace with `lib`");
+            return Some(self.report().suggest(at, message, fix));
+        }
+        // add `lib` to the pattern taking `stdenv`
+        let stdenv = scope::lookup(node, "stdenv").filter(|b| b.kind == BindingKind::PatEntry);
+        let stdenv = match stdenv {
+            Some(stdenv) => stdenv,
+            None => return Some(self.report().diagnostic(at, message)),
+        };
+        let lambda = Lambda::cast(stdenv.scope.clone())?;
+        let insertion = lib_entry(&stdenv.entry, sess.style().eol());
+        let edits = [insertion, (at, "lib".to_owned())];
+        let replacement = make::edited(&lambda, &edits);
+        let fix = Suggestion::new(lambda.node().text_range(), replacement.node().clone())
+            .description("replace with `lib`, and add `lib` to the arguments");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// where and how to add `lib` next to the pattern entry `stdenv`, following
+// the style of the pattern:
+//
+//   { stdenv, ... }    {\n  stdenv,\n  ...\n}    { stdenv\n, ...\n}
+fn lib_entry(stdenv: &SyntaxNode, eol: &str) -> (TextRange, String) {
+    let start = TextRange::empty(stdenv.text_range().start());
+    let pattern = match stdenv.parent() {
+        Some(pattern) if pattern.to_string().contains('\n') => pattern,
+        _ => return (start, "lib, ".to_owned()),
+    };
+    let next = stdenv
+        .siblings_with_tokens(Direction::Next)
+        .skip(1)
+        .find(|e| e.kind() != SyntaxKind::TOKEN_WHITESPACE);
+    let comma_on_line = match &next {
+        Some(e) if e.kind() == SyntaxKind::TOKEN_COMMA => !pattern
+            .to_string()
+            .get(
+                usize::from(stdenv.text_range().end() - pattern.text_range().start())
+                    ..usize::from(e.text_range().start() - pattern.text_range().start()),
+            )
+            .unwrap_or_default()
+            .contains('\n'),
+        _ => false,
+    };
+    if comma_on_line {
+        // trailing commas
+        return (start, format!("lib,{}{}", eol, indentation(stdenv)));
+    }
+    // leading commas, indented as the lines of the other entries
+    let other = pattern
+        .children()
+        .find(|n| n.kind() == SyntaxKind::NODE_PAT_ENTRY && n != stdenv);
+    match other {
+        Some(other) => (
+            TextRange::empty(stdenv.text_range().end()),
+            format!("{}{}, lib", eol, indentation(&other)),
+        ),
+        None => (start, "lib, ".to_owned()),
+    }
+}
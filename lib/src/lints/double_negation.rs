@@ -0,0 +1,76 @@
+use crate::{
+    make, session::SessionInfo, utils::unparenthesized, Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{BinOp, BinOpKind, TypedNode, UnaryOp, UnaryOpKind},
+    NodeOrToken, SyntaxElement, SyntaxKind,
+};
+
+/// ## What it does
+/// Checks for negations of negations: `!!x`, `!(!x)` and `!(x != y)`.
+///
+/// ## Why is this bad?
+/// Every negation is one more thing to keep in mind while reading the
+/// expression.
+///
+/// ## Example
+///
+/// ```nix
+/// if !(cfg.package != null) then [ ] else [ cfg.package ]
+/// ```
+///
+/// Drop both negations:
+///
+/// ```nix
+/// if cfg.package == null then [ ] else [ cfg.package ]
+/// ```
+#[lint(
+    name = "double_negation",
+    note = "Found a double negation",
+    code = 40,
+    priority = 1,
+    match_with = SyntaxKind::NODE_UNARY_OP
+)]
+struct DoubleNegation;
+
+impl Rule for DoubleNegation {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let unary = UnaryOp::cast(node.clone())?;
+        if unary.operator() != UnaryOpKind::Invert {
+            return None;
+        }
+        // the parentheses are put back by `make::replacing` where needed
+        let inner = unparenthesized(&unary.value()?);
+        let (message, replacement) = if let Some(negated) = UnaryOp::cast(inner.clone()) {
+            if negated.operator() != UnaryOpKind::Invert {
+                return None;
+            }
+            (
+                "This negates an expression twice",
+                unparenthesized(&negated.value()?),
+            )
+        } else {
+            let bin_expr = BinOp::cast(inner)?;
+            if bin_expr.operator()? != BinOpKind::NotEqual {
+                return None;
+            }
+            let (lhs, rhs) = (bin_expr.lhs()?, bin_expr.rhs()?);
+            let equal = make::binary(&lhs, "==", &rhs).node().clone();
+            ("Try `==` instead of `!(... != ...)`", equal)
+        };
+        let replacement = make::replacing(&replacement, node);
+        let at = node.text_range();
+        let description = format!("replace with `{}`", replacement);
+        Some(self.report().suggest(
+            at,
+            message,
+            Suggestion::new(at, replacement).description(description),
+        ))
+    }
+}
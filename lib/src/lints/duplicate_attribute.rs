@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    session::SessionInfo,
+    utils::{attr_name, unparenthesized},
+    Metadata, Report, Rule, Severity,
+};
+
+use macros::lint;
+use rnix::{
+    types::{AttrSet, Inherit, KeyValue, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
+};
+
+/// ## What it does
+/// Checks for attributes defined twice in an attribute set or `let`,
+/// including through different attribute paths, such as `a.b = 1;`
+/// and `a = { b = 2; };`.
+///
+/// ## Why is this bad?
+/// Nix refuses to evaluate the file. Attribute paths and nested sets
+/// are merged with each other, which makes the second definition easy
+/// to miss.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   services.nginx.enable = true;
+///   services = {
+///     nginx.enable = false;
+///   };
+/// }
+/// ```
+///
+/// Keep one of the definitions:
+///
+/// ```nix
+/// {
+///   services = {
+///     nginx.enable = false;
+///   };
+/// }
+/// ```
+#[lint(
+    name = "duplicate_attribute",
+    note = "Found an attribute defined twice",
+    code = 35,
+    severity = Severity::Error,
+    match_with = [
+        SyntaxKind::NODE_ATTR_SET,
+        SyntaxKind::NODE_LET_IN,
+        SyntaxKind::NODE_LEGACY_LET
+    ]
+)]
+struct DuplicateAttribute;
+
+impl Rule for DuplicateAttribute {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let mut duplicates = Vec::new();
+        let mut attrs = Attrs::default();
+        attrs.define_all(node, &[], &mut duplicates);
+        let mut report = self.report();
+        for duplicate in duplicates {
+            report = report
+                .diagnostic(
+                    duplicate.second,
+                    format!("`{}` is defined again here", duplicate.path),
+                )
+                .related(
+                    duplicate.first,
+                    format!("`{}` is first defined here", duplicate.path),
+                );
+        }
+        if report.diagnostics.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+
+struct Duplicate {
+    path: String,
+    first: TextRange,
+    second: TextRange,
+}
+
+/// The attributes of a set as Nix builds them: `a.b = 1;` and
+/// `a = { c = 2; };` both define attributes of the same set `a`
+#[derive(Default)]
+struct Attrs(BTreeMap<String, Attr>);
+
+struct Attr {
+    /// Where the attribute is first named
+    at: TextRange,
+    /// The attributes of a set defined by an attribute path or a set
+    /// literal, which later definitions are merged into
+    attrs: Option<Attrs>,
+}
+
+impl Attrs {
+    // the key-values and `inherit`s of an attribute set or `let`
+    fn define_all(&mut self, node: &SyntaxNode, prefix: &[String], dups: &mut Vec<Duplicate>) {
+        for child in node.children() {
+            if let Some(kv) = KeyValue::cast(child.clone()) {
+                let path = match kv.key() {
+                    Some(key) => key.path().collect::<Vec<_>>(),
+                    None => continue,
+                };
+                self.define(&path, kv.value(), prefix, dups);
+            } else if let Some(inherit) = Inherit::cast(child) {
+                for ident in inherit.idents() {
+                    let path = [ident.node().clone()];
+                    self.define(&path, None, prefix, dups);
+                }
+            }
+        }
+    }
+
+    fn define(
+        &mut self,
+        path: &[SyntaxNode],
+        value: Option<SyntaxNode>,
+        prefix: &[String],
+        dups: &mut Vec<Duplicate>,
+    ) {
+        let (first, rest) = match path.split_first() {
+            Some(split) => split,
+            None => return,
+        };
+        // `${name} = ...;` is only known when evaluating
+        let name = match attr_name(first) {
+            Some(name) => name,
+            None => return,
+        };
+        let at = first.text_range();
+        let full = prefix
+            .iter()
+            .cloned()
+            .chain(Some(name.clone()))
+            .collect::<Vec<_>>();
+        let set = value.as_ref().and_then(set_literal);
+        match self.0.get_mut(&name) {
+            // `a.b = 1;` after `a = { ... };` or `a.c = 2;`
+            Some(Attr {
+                attrs: Some(attrs), ..
+            }) if !rest.is_empty() => attrs.define(rest, value, &full, dups),
+            // `a = { b = 1; };` after `a.c = 2;`, merged as long as the
+            // names of the set are new
+            Some(Attr {
+                attrs: Some(attrs), ..
+            }) if set.is_some() => {
+                let mut literal = Attrs::default();
+                literal.define_all(set.as_ref().unwrap(), &full, &mut Vec::new());
+                for (name, attr) in literal.0 {
+                    match attrs.0.get(&name) {
+                        Some(existing) => dups.push(Duplicate {
+                            path: join(&full, &name),
+                            first: existing.at,
+                            second: attr.at,
+                        }),
+                        None => {
+                            attrs.0.insert(name, attr);
+                        }
+                    }
+                }
+            }
+            Some(existing) => dups.push(Duplicate {
+                path: full.join("."),
+                first: existing.at,
+                second: at,
+            }),
+            None => {
+                let attrs = if !rest.is_empty() {
+                    let mut attrs = Attrs::default();
+                    attrs.define(rest, value, &full, dups);
+                    Some(attrs)
+                } else {
+                    set.map(|set| {
+                        let mut attrs = Attrs::default();
+                        attrs.define_all(&set, &full, &mut Vec::new());
+                        attrs
+                    })
+                };
+                self.0.insert(name, Attr { at, attrs });
+            }
+        }
+    }
+}
+
+// the set `value` evaluates to, when written as a literal
+fn set_literal(value: &SyntaxNode) -> Option<SyntaxNode> {
+    AttrSet::cast(unparenthesized(value)).map(|set| set.node().clone())
+}
+
+fn join(prefix: &[String], name: &str) -> String {
+    prefix
+        .iter()
+        .map(String::as_str)
+        .chain(Some(name))
+        .collect::<Vec<_>>()
+        .join(".")
+}
@@ -43,7 +43,7 @@ impl Rule for EmptyInherit {
                 Some(
                     self
                     .report()
-                    .suggest(at, message, Suggestion::new(replacement_at, replacement))
+                    .suggest(at, message, Suggestion::new(replacement_at, replacement).description("remove the `inherit`"))
                 )
             } else {
                 None
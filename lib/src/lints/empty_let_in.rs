@@ -29,6 +29,7 @@ use rnix::{
     name = "empty_let_in",
     note = "Useless let-in expression",
     code = 2,
+    priority = 1,
     match_with = SyntaxKind::NODE_LET_IN
 )]
 struct EmptyLetIn;
@@ -57,7 +58,7 @@ impl Rule for EmptyLetIn {
                 Some(if has_comments {
                     self.report().diagnostic(at, message)
                 } else {
-                    self.report().suggest(at, message, Suggestion::new(at, replacement))
+                    self.report().suggest(at, message, Suggestion::new(at, replacement).description("remove the empty `let in`"))
                 })
             } else {
                 None
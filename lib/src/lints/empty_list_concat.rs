@@ -1,4 +1,9 @@
-use crate::{session::SessionInfo, Metadata, Report, Rule, Suggestion};
+use crate::{
+    make,
+    session::SessionInfo,
+    utils::{call, function_name, unparenthesized},
+    Metadata, Report, Rule, Suggestion,
+};
 
 use if_chain::if_chain;
 use macros::lint;
@@ -8,7 +13,8 @@ use rnix::{
 };
 
 /// ## What it does
-/// Checks for concatenations to empty lists
+/// Checks for concatenations to empty lists, and for `concatLists`
+/// applied to a list of a single list.
 ///
 /// ## Why is this bad?
 /// Concatenation with the empty list is a no-op.
@@ -23,18 +29,35 @@ use rnix::{
 /// ```nix
 /// something
 /// ```
+///
+/// Likewise, `lib.concatLists [ something ]` is `something`.
 #[lint(
     name = "empty_list_concat",
-    note = "Unnecessary concatenation with empty list",
+    note = "Unnecessary list concatenation",
     code = 23,
-    match_with = SyntaxKind::NODE_BIN_OP
+    priority = 1,
+    match_with = [SyntaxKind::NODE_BIN_OP, SyntaxKind::NODE_APPLY]
 )]
 struct EmptyListConcat;
 
 impl Rule for EmptyListConcat {
     fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        if let Some(list) = single_concat_lists(node) {
+            let at = node.text_range();
+            let message = "`concatLists` of a single list is that list";
+            let replacement = make::replacing(&list, node);
+            let description = format!("replace with `{}`", replacement);
+            return Some(self.report().suggest(
+                at,
+                message,
+                Suggestion::new(at, replacement).description(description),
+            ));
+        }
         if_chain! {
-            if let NodeOrToken::Node(node) = node;
             if let Some(bin_expr) = BinOp::cast(node.clone());
             if let Some(lhs) = bin_expr.lhs();
             if let Some(rhs) = bin_expr.rhs();
@@ -43,13 +66,20 @@ impl Rule for EmptyListConcat {
             then {
                 let at = node.text_range();
                 let message = "Concatenation with the empty list, `[]`, is a no-op";
-                if is_empty_array(&lhs) {
-                    Some(self.report().suggest(at, message, Suggestion::new(at, rhs)))
+                let kept = if is_empty_array(&lhs) {
+                    rhs
                 } else if is_empty_array(&rhs) {
-                    Some(self.report().suggest(at, message, Suggestion::new(at, lhs)))
+                    lhs
                 } else {
-                    None
-                }
+                    return None;
+                };
+                let replacement = make::replacing(&kept, node);
+                let description = format!("replace with `{}`", replacement);
+                Some(self.report().suggest(
+                    at,
+                    message,
+                    Suggestion::new(at, replacement).description(description),
+                ))
             } else {
                 None
             }
@@ -58,7 +88,25 @@ impl Rule for EmptyListConcat {
 }
 
 fn is_empty_array(node: &SyntaxNode) -> bool {
-    List::cast(node.clone())
+    List::cast(unparenthesized(node))
         .map(|list| list.items().count() == 0)
         .unwrap_or_default()
 }
+
+// the only element of `xs` in `concatLists xs`, for `builtins.concatLists`,
+// `lib.concatLists` and `concatLists` brought in scope
+fn single_concat_lists(node: &SyntaxNode) -> Option<SyntaxNode> {
+    let (function, args) = call(node);
+    if function_name(&function).as_deref() != Some("concatLists") {
+        return None;
+    }
+    let list = match args.as_slice() {
+        [list] => List::cast(unparenthesized(list))?,
+        _ => return None,
+    };
+    let mut items = list.items();
+    match (items.next(), items.next()) {
+        (Some(item), None) => Some(item),
+        _ => None,
+    }
+}
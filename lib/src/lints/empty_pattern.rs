@@ -62,7 +62,7 @@ impl Rule for EmptyPattern {
                 let at = pattern.node().text_range();
                 let message = "This pattern is empty, use `_` instead";
                 let replacement = make::ident("_").node().clone();
-                Some(self.report().suggest(at, message, Suggestion::new(at, replacement)))
+                Some(self.report().suggest(at, message, Suggestion::new(at, replacement).description("replace with `_`")))
             } else {
                 None
             }
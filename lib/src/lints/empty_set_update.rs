@@ -0,0 +1,98 @@
+use crate::{
+    make,
+    session::SessionInfo,
+    utils::{bool_literal, unparenthesized},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, AttrSet, BinOp, BinOpKind, Ident, Select, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for updates with an empty attribute set: `x // { }` and
+/// `{ } // x`, including sets from `lib.optionalAttrs` that are always
+/// empty, such as `lib.optionalAttrs false { ... }`.
+///
+/// ## Why is this bad?
+/// Updating with the empty set is a no-op.
+///
+/// ## Example
+///
+/// ```nix
+/// attrs // { }
+/// ```
+///
+/// Remove the update:
+///
+/// ```nix
+/// attrs
+/// ```
+#[lint(
+    name = "empty_set_update",
+    note = "Unnecessary update with empty set",
+    code = 42,
+    priority = 1,
+    match_with = SyntaxKind::NODE_BIN_OP
+)]
+struct EmptySetUpdate;
+
+impl Rule for EmptySetUpdate {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let bin_expr = BinOp::cast(node.clone())?;
+        if bin_expr.operator()? != BinOpKind::Update {
+            return None;
+        }
+        let (lhs, rhs) = (bin_expr.lhs()?, bin_expr.rhs()?);
+        let (empty, kept) = if is_empty_set(&lhs) {
+            (lhs, rhs)
+        } else if is_empty_set(&rhs) {
+            (rhs, lhs)
+        } else {
+            return None;
+        };
+        let at = node.text_range();
+        let message = if AttrSet::cast(unparenthesized(&empty)).is_some() {
+            "Updating with the empty set, `{ }`, is a no-op".to_owned()
+        } else {
+            format!("`{}` is always empty, updating with it is a no-op", empty)
+        };
+        let replacement = make::replacing(&kept, node);
+        let description = format!("replace with `{}`", replacement);
+        Some(self.report().suggest(
+            at,
+            message,
+            Suggestion::new(at, replacement).description(description),
+        ))
+    }
+}
+
+fn is_empty_set(node: &SyntaxNode) -> bool {
+    let node = unparenthesized(node);
+    if let Some(set) = AttrSet::cast(node.clone()) {
+        return set.node().children().next().is_none();
+    }
+    // `optionalAttrs cond set`, as `lib.optionalAttrs` or brought in scope
+    let (function, cond, set) = match optional_attrs_call(&node) {
+        Some(call) => call,
+        None => return false,
+    };
+    let named = match Select::cast(function.clone()) {
+        Some(select) => matches!(select.index(), Some(index) if index.text() == "optionalAttrs"),
+        None => matches!(Ident::cast(function), Some(ident) if ident.as_str() == "optionalAttrs"),
+    };
+    named && (bool_literal(&unparenthesized(&cond)) == Some(false) || is_empty_set(&set))
+}
+
+// `f cond set`, as the function and its two arguments
+fn optional_attrs_call(node: &SyntaxNode) -> Option<(SyntaxNode, SyntaxNode, SyntaxNode)> {
+    let outer = Apply::cast(node.clone())?;
+    let inner = Apply::cast(outer.lambda()?)?;
+    Some((inner.lambda()?, inner.value()?, outer.value()?))
+}
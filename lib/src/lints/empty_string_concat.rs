@@ -0,0 +1,88 @@
+use crate::{
+    make,
+    session::SessionInfo,
+    utils::{is_string, unparenthesized},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{BinOp, BinOpKind, Str, TypedNode},
+    NodeOrToken, StrPart, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for concatenations with the empty string: `"" + x` and
+/// `x + ""`.
+///
+/// ## Why is this bad?
+/// Appending the empty string to a string is a no-op. Where it is used
+/// to turn a path into a string, `toString` says so, and `"" + x` is
+/// only fixed when `x` is known to be a string.
+///
+/// ## Example
+///
+/// ```nix
+/// "" + "${name}-${version}"
+/// ```
+///
+/// Remove the concatenation:
+///
+/// ```nix
+/// "${name}-${version}"
+/// ```
+#[lint(
+    name = "empty_string_concat",
+    note = "Unnecessary concatenation with empty string",
+    code = 43,
+    priority = 1,
+    match_with = SyntaxKind::NODE_BIN_OP
+)]
+struct EmptyStringConcat;
+
+impl Rule for EmptyStringConcat {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let bin_expr = BinOp::cast(node.clone())?;
+        if bin_expr.operator()? != BinOpKind::Add {
+            return None;
+        }
+        let (lhs, rhs) = (bin_expr.lhs()?, bin_expr.rhs()?);
+        let at = node.text_range();
+        let message = "Concatenation with the empty string, `\"\"`, is a no-op";
+        let kept = if is_empty_string(&lhs) {
+            // `"" + ./file` turns the path into a string, and is kept for
+            // anything that is not known to be a string already
+            if !is_string(&rhs) {
+                let message = "Concatenation with the empty string, use `toString` to turn a path into a string";
+                return Some(self.report().diagnostic(at, message));
+            }
+            rhs
+        } else if is_empty_string(&rhs) {
+            lhs
+        } else {
+            return None;
+        };
+        let replacement = make::replacing(&kept, node);
+        let description = format!("replace with `{}`", replacement);
+        Some(self.report().suggest(
+            at,
+            message,
+            Suggestion::new(at, replacement).description(description),
+        ))
+    }
+}
+
+// `""` and `''''`
+fn is_empty_string(node: &SyntaxNode) -> bool {
+    match Str::cast(unparenthesized(node)) {
+        Some(s) => s
+            .parts()
+            .iter()
+            .all(|part| matches!(part, StrPart::Literal(l) if l.is_empty())),
+        None => false,
+    }
+}
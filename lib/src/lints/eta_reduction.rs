@@ -37,6 +37,7 @@ use rnix::{
     name = "eta_reduction",
     note = "This function expression is eta reducible",
     code = 7,
+    priority = 1,
     match_with = SyntaxKind::NODE_LAMBDA
 )]
 struct EtaReduction;
@@ -72,7 +73,8 @@ impl Rule for EtaReduction {
                         "Found eta-reduction: `{}`",
                         replacement.text()
                     );
-                Some(self.report().suggest(at, message, Suggestion::new(at, replacement)))
+                let description = format!("replace with `{}`", replacement);
+                Some(self.report().suggest(at, message, Suggestion::new(at, replacement).description(description)))
             } else {
                 None
             }
@@ -0,0 +1,144 @@
+use crate::{
+    make,
+    session::{Mode, SessionInfo},
+    utils::{
+        attr_name, derivation_attrs, function_name, indentation, string_literal,
+        with_preceeding_whitespace,
+    },
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, EntryHolder, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// The phases run by default, with the attribute skipping them
+const DEFAULT_PHASES: &[(&str, &str)] = &[
+    ("unpackPhase", "dontUnpack"),
+    ("patchPhase", "dontPatch"),
+    ("configurePhase", "dontConfigure"),
+    ("buildPhase", "dontBuild"),
+    ("installPhase", "dontInstall"),
+    ("fixupPhase", "dontFixup"),
+];
+
+/// The phases skipped by default, with the attribute running them
+const OPTIONAL_PHASES: &[(&str, &str)] = &[
+    ("checkPhase", "doCheck"),
+    ("installCheckPhase", "doInstallCheck"),
+    ("distPhase", "doDist"),
+];
+
+/// ## What it does
+/// Checks for `phases` in the arguments of `mkDerivation`. When it only
+/// lists standard phases, it is replaced with the attributes skipping,
+/// or running, each of them.
+///
+/// ## Why is this bad?
+/// Setting `phases` also skips the phases added by setup hooks, such as
+/// those of `autoPatchelfHook` or `wrapGAppsHook`, without saying so.
+///
+/// ## Example
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   phases = [ "installPhase" "fixupPhase" ];
+/// }
+/// ```
+///
+/// Skip the phases that are not needed:
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   dontUnpack = true;
+///   dontPatch = true;
+///   dontConfigure = true;
+///   dontBuild = true;
+/// }
+/// ```
+#[lint(
+    name = "explicit_phases",
+    note = "Found `phases` in a derivation",
+    code = 66,
+    match_with = SyntaxKind::NODE_APPLY,
+    modes = [Mode::Nixpkgs]
+)]
+struct ExplicitPhases;
+
+impl Rule for ExplicitPhases {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        if function_name(&apply.lambda()?)? != "mkDerivation" {
+            return None;
+        }
+        let set = derivation_attrs(&apply.value()?)?;
+        let phases = set.entries().find(|kv| {
+            let key = kv.key().and_then(|k| k.path().next());
+            matches!(key, Some(k) if attr_name(&k).as_deref() == Some("phases"))
+        })?;
+        let at = phases.node().text_range();
+        let message = "Skip phases with `dontBuild` and the like, or override them";
+        let names = match listed_phases(&phases.value()?) {
+            Some(names) => names,
+            None => return Some(self.report().diagnostic(at, message)),
+        };
+        let is_standard = |name: &String| {
+            DEFAULT_PHASES
+                .iter()
+                .chain(OPTIONAL_PHASES)
+                .any(|(phase, _)| phase == name)
+        };
+        if !names.iter().all(is_standard) {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let skipped = DEFAULT_PHASES
+            .iter()
+            .filter(|(phase, _)| !names.iter().any(|n| n == phase));
+        let run = OPTIONAL_PHASES
+            .iter()
+            .filter(|(phase, _)| names.iter().any(|n| n == phase));
+        let entries = skipped
+            .chain(run)
+            .map(|(_, attr)| format!("{} = true;", attr))
+            .collect::<Vec<_>>();
+        let edit = if entries.is_empty() {
+            (with_preceeding_whitespace(phases.node()), String::new())
+        } else {
+            let separator = if set.node().to_string().contains('\n') {
+                format!("{}{}", sess.style().eol(), indentation(phases.node()))
+            } else {
+                " ".to_owned()
+            };
+            (at, entries.join(&separator))
+        };
+        let replacement = make::edited(&set, &[edit]);
+        let fix = Suggestion::new(set.node().text_range(), replacement.node().clone())
+            .description("replace `phases`");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// the names of the phases of a list of strings, or of a string
+fn listed_phases(value: &SyntaxNode) -> Option<Vec<String>> {
+    match value.kind() {
+        SyntaxKind::NODE_LIST => value
+            .children()
+            .map(|element| string_literal(&element))
+            .collect(),
+        SyntaxKind::NODE_STRING => Some(
+            string_literal(value)?
+                .split_whitespace()
+                .map(str::to_owned)
+                .collect(),
+        ),
+        _ => None,
+    }
+}
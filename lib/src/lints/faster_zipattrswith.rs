@@ -1,6 +1,6 @@
 use crate::{
     make,
-    session::{SessionInfo, Version},
+    session::SessionInfo,
     Metadata, Report, Rule, Suggestion,
 };
 
@@ -34,15 +34,14 @@ use rnix::{
     name = "faster_zipattrswith",
     note = "Found lib.zipAttrsWith",
     code = 16,
+    min_nix_version = "2.6",
     match_with = SyntaxKind::NODE_SELECT
 )]
 struct FasterZipAttrsWith;
 
 impl Rule for FasterZipAttrsWith {
-    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
-        let lint_version = "2.6".parse::<Version>().unwrap();
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
         if_chain! {
-            if sess.version() >= &lint_version;
             if let NodeOrToken::Node(node) = node;
             if let Some(select_expr) = Select::cast(node.clone());
             if let Some(select_from) = select_expr.set();
@@ -60,9 +59,10 @@ impl Rule for FasterZipAttrsWith {
                     make::select(builtins.node(), &zip_attrs_with).node().clone()
                 };
                 let message = format!("Prefer `builtins.zipAttrsWith` over `{}.zipAttrsWith`", select_from);
+                let description = format!("replace with `{}`", replacement);
                 Some(
                     self.report()
-                        .suggest(at, message, Suggestion::new(at, replacement)),
+                        .suggest(at, message, Suggestion::new(at, replacement).description(description)),
                 )
             } else {
                 None
@@ -0,0 +1,144 @@
+use crate::{
+    make, scope,
+    session::SessionInfo,
+    utils::{attr_name, indentation},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, AttrSet, EntryHolder, Ident, Select, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
+};
+
+/// The value of `lib.fakeHash`, for files without `lib` in scope
+const FAKE_HASH: &str = "\"sha256-AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA=\"";
+
+/// ## What it does
+/// Checks for calls to `builtins.fetchTarball` and `builtins.fetchurl`
+/// without a hash, and to `builtins.fetchGit` without a `rev`.
+///
+/// ## Why is this bad?
+/// What is fetched changes whenever the URL or branch is updated, so
+/// the expression is not reproducible, and it is not allowed in pure
+/// evaluation. The fix adds a placeholder hash, `lib.fakeHash`: the
+/// next build fails and prints the actual hash to replace it with.
+///
+/// ## Example
+///
+/// ```nix
+/// builtins.fetchTarball "https://github.com/NixOS/nixpkgs/archive/nixos-23.05.tar.gz"
+/// ```
+///
+/// Pin the hash:
+///
+/// ```nix
+/// builtins.fetchTarball {
+///   url = "https://github.com/NixOS/nixpkgs/archive/nixos-23.05.tar.gz";
+///   sha256 = "sha256-...";
+/// }
+/// ```
+#[lint(
+    name = "fetcher_without_hash",
+    note = "Found a fetcher without a hash",
+    code = 51,
+    match_with = SyntaxKind::NODE_APPLY
+)]
+struct FetcherWithoutHash;
+
+impl Rule for FetcherWithoutHash {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        let fetcher = fetcher(&apply.lambda()?)?;
+        let argument = apply.value()?;
+        let pin = if fetcher == "fetchGit" {
+            "rev"
+        } else {
+            "sha256"
+        };
+        let set = AttrSet::cast(argument.clone());
+        if let Some(set) = &set {
+            let keys = set
+                .entries()
+                .filter_map(|kv| attr_name(&kv.key()?.path().next()?))
+                .collect::<Vec<_>>();
+            // `ref` without `rev` is left to `branch_pin`
+            let pinned = ["sha256", "hash", "narHash", "rev", "ref"];
+            if !keys.iter().any(|k| k == "url") || keys.iter().any(|k| pinned.contains(&k.as_str()))
+            {
+                return None;
+            }
+        } else if argument.kind() != SyntaxKind::NODE_STRING {
+            return None;
+        }
+
+        let at = node.text_range();
+        if fetcher == "fetchGit" {
+            let message = "Nothing pins what is fetched, add the `rev` of the commit to fetch";
+            return Some(self.report().diagnostic(at, message));
+        }
+        let message = format!("Nothing pins what is fetched, add `{}`", pin);
+        // `lib.fakeHash` if `lib` is around
+        let lib = scope::lookup(node, "lib").is_some();
+        let hash = if lib { "lib.fakeHash" } else { FAKE_HASH };
+        let style = sess.style();
+        let replacement = match set {
+            Some(set) => {
+                let last = set.node().children().last()?;
+                let separator = if set.node().to_string().contains('\n') {
+                    format!("{}{}", style.eol(), indentation(&last))
+                } else {
+                    " ".to_owned()
+                };
+                let end = TextRange::empty(last.text_range().end());
+                let entry = format!("{}{} = {};", separator, pin, hash);
+                let set = make::edited(&set, &[(end, entry)]);
+                make::apply(&apply.lambda()?, set.node()).node().clone()
+            }
+            None => {
+                let base = indentation(node);
+                let unit = style.indent_unit();
+                let eol = style.eol();
+                let text = format!(
+                    "{} {{{eol}{base}{unit}url = {};{eol}{base}{unit}{} = {};{eol}{base}}}",
+                    apply.lambda()?,
+                    argument,
+                    pin,
+                    hash,
+                    eol = eol,
+                    base = base,
+                    unit = unit
+                );
+                rnix::parse(&text).node().first_child()?
+            }
+        };
+        let fix =
+            Suggestion::new(at, replacement).description(format!("add `{} = {};`", pin, hash));
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// `builtins.fetchTarball`, `builtins.fetchurl`, `builtins.fetchGit`, and
+// the first and last without `builtins.`
+fn fetcher(function: &SyntaxNode) -> Option<String> {
+    let name = match Select::cast(function.clone()) {
+        Some(select) if select.set()?.text() == "builtins" => select.index()?.to_string(),
+        Some(_) => return None,
+        None => {
+            let name = Ident::cast(function.clone())?.as_str().to_owned();
+            if name == "fetchurl" {
+                // usually the one of nixpkgs, which requires a hash
+                return None;
+            }
+            name
+        }
+    };
+    match name.as_str() {
+        "fetchTarball" | "fetchurl" | "fetchGit" => Some(name),
+        _ => None,
+    }
+}
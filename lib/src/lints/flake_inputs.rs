@@ -0,0 +1,253 @@
+use std::collections::HashSet;
+
+use crate::{
+    make,
+    session::{Mode, SessionInfo},
+    utils::{self, attr_name, references},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{
+        AttrSet, EntryHolder, Ident, KeyValue, Lambda, Pattern, Select, Str, TokenWrapper,
+        TypedNode,
+    },
+    NodeOrToken, StrPart, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks the inputs of `flake.nix`: inputs pointing at the same URL,
+/// `follows` that refer to an input that is not declared, and inputs
+/// that `outputs` never uses.
+///
+/// ## Why is this bad?
+/// Every input is fetched and locked, unused and duplicated inputs slow
+/// down evaluation and clutter `flake.lock`. A `follows` naming an
+/// undeclared input is an error when the flake is locked.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   inputs.nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+///   inputs.unused.url = "github:numtide/flake-utils";
+///   inputs.home-manager.inputs.nixpkgs.follows = "nixpks";
+///
+///   outputs = { self, nixpkgs, ... }: { };
+/// }
+/// ```
+///
+/// Remove unused inputs and follow declared ones:
+///
+/// ```nix
+/// {
+///   inputs.nixpkgs.url = "github:NixOS/nixpkgs/nixos-unstable";
+///   inputs.home-manager.inputs.nixpkgs.follows = "nixpkgs";
+///
+///   outputs = { self, nixpkgs, ... }: { };
+/// }
+/// ```
+#[lint(
+    name = "flake_inputs",
+    note = "Found an issue with the inputs of this flake",
+    code = 29,
+    match_with = SyntaxKind::NODE_ROOT,
+    modes = [Mode::Flake]
+)]
+struct FlakeInputs;
+
+// an attribute bound to anything but an attribute set, with the full path
+// of attribute names leading to it from the top of the flake
+struct Leaf {
+    path: Vec<String>,
+    value: SyntaxNode,
+    // the binding declaring this leaf, at the top of the flake or directly in `inputs`
+    binding: SyntaxNode,
+}
+
+impl Rule for FlakeInputs {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let set = match node {
+            NodeOrToken::Node(node) => AttrSet::cast(node.first_child()?)?,
+            _ => return None,
+        };
+        let mut leaves = Vec::new();
+        flatten(&set, &[], None, &mut leaves);
+        let inputs = leaves
+            .iter()
+            .filter(|l| l.path.len() > 2 && l.path[0] == "inputs")
+            .collect::<Vec<_>>();
+
+        let mut declared = Vec::<&str>::new();
+        for leaf in &inputs {
+            if !declared.contains(&leaf.path[1].as_str()) {
+                declared.push(&leaf.path[1]);
+            }
+        }
+
+        let mut report = self.report();
+
+        // inputs pointing at the same url
+        let mut urls = Vec::<(String, &str)>::new();
+        for leaf in inputs
+            .iter()
+            .filter(|l| l.path.len() == 3 && l.path[2] == "url")
+        {
+            let url = match string(&leaf.value) {
+                Some(u) => u.trim_end_matches('/').to_owned(),
+                None => continue,
+            };
+            match urls.iter().find(|(u, _)| *u == url) {
+                Some((_, first)) => {
+                    let at = leaf.value.text_range();
+                    let message =
+                        format!("`{}` points at the same URL as `{}`", leaf.path[1], first);
+                    report = report.diagnostic(at, message);
+                }
+                None => urls.push((url, &leaf.path[1])),
+            }
+        }
+
+        // follows of undeclared inputs
+        let mut followed = HashSet::new();
+        for leaf in inputs
+            .iter()
+            .filter(|l| l.path.last().unwrap() == "follows")
+        {
+            let target = match string(&leaf.value) {
+                Some(t) => t,
+                None => continue,
+            };
+            let input = target.split('/').next().unwrap_or_default().to_owned();
+            if input.is_empty() {
+                continue;
+            }
+            if !declared.contains(&input.as_str()) {
+                let at = leaf.value.text_range();
+                let message = format!(
+                    "`{}` is not an input of this flake, it cannot be followed",
+                    input
+                );
+                report = report.diagnostic(at, message);
+            }
+            followed.insert(input);
+        }
+
+        // inputs never used by outputs
+        let outputs = leaves
+            .iter()
+            .find(|l| l.path == ["outputs"])
+            .and_then(|l| Lambda::cast(l.value.clone()));
+        if let Some(used) = outputs.as_ref().and_then(used_inputs) {
+            let pattern = outputs
+                .and_then(|o| o.arg())
+                .and_then(Pattern::cast)
+                .map(|p| {
+                    p.entries()
+                        .filter_map(|e| Some(e.name()?.as_str().to_owned()))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default();
+            for name in declared
+                .iter()
+                .filter(|&&n| n != "self" && !used.contains(n) && !followed.contains(n))
+            {
+                let bindings = inputs
+                    .iter()
+                    .filter(|l| l.path[1] == *name)
+                    .map(|l| l.binding.clone())
+                    .collect::<HashSet<_>>();
+                let first = inputs.iter().find(|l| l.path[1] == *name).unwrap();
+                let at = first.binding.text_range();
+                let message = format!("Input `{}` is not used by `outputs`", name);
+                // remove inputs declared by a single binding that are not bound by name
+                report = match bindings.into_iter().collect::<Vec<_>>().as_slice() {
+                    [binding] if !pattern.iter().any(|p| p == name) => {
+                        let replacement_at = utils::with_preceeding_whitespace(binding);
+                        let replacement = make::empty().node().clone();
+                        report.suggest(at, message, Suggestion::new(replacement_at, replacement).description(format!("remove the input `{}`", name)))
+                    }
+                    _ => report.diagnostic(at, message),
+                };
+            }
+        }
+
+        if report.diagnostics.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+
+fn flatten(set: &AttrSet, prefix: &[String], binding: Option<&SyntaxNode>, out: &mut Vec<Leaf>) {
+    for kv in set.entries() {
+        let path = match key_path(&kv) {
+            Some(p) => p,
+            None => continue,
+        };
+        let value = match kv.value() {
+            Some(v) => v,
+            None => continue,
+        };
+        let path = prefix.iter().cloned().chain(path).collect::<Vec<_>>();
+        // bindings inside `inputs = { ... };` can be removed one by one
+        let binding = match binding {
+            Some(b) if prefix != ["inputs"] => b.clone(),
+            _ => kv.node().clone(),
+        };
+        match AttrSet::cast(value.clone()) {
+            Some(nested) if !nested.recursive() => flatten(&nested, &path, Some(&binding), out),
+            _ => out.push(Leaf {
+                path,
+                value,
+                binding,
+            }),
+        }
+    }
+}
+
+fn key_path(kv: &KeyValue) -> Option<Vec<String>> {
+    kv.key()?.path().map(|p| attr_name(&p)).collect()
+}
+
+fn string(node: &SyntaxNode) -> Option<String> {
+    match Str::cast(node.clone())?.parts().as_slice() {
+        [StrPart::Literal(s)] => Some(s.clone()),
+        _ => None,
+    }
+}
+
+// inputs used by the `outputs` function, `None` if all of them could be
+fn used_inputs(outputs: &Lambda) -> Option<HashSet<String>> {
+    let arg = outputs.arg()?;
+    let body = outputs.body()?;
+    let mut used = HashSet::new();
+    let binding = match Pattern::cast(arg.clone()) {
+        Some(pattern) => {
+            for name in pattern.entries().filter_map(|e| e.name()) {
+                if references(&body, name.as_str()).next().is_some() {
+                    used.insert(name.as_str().to_owned());
+                }
+            }
+            pattern.at()
+        }
+        None => Ident::cast(arg),
+    };
+    if let Some(binding) = binding {
+        for reference in references(&body, binding.as_str()) {
+            let index = reference
+                .parent()
+                .and_then(Select::cast)
+                .filter(|s| s.set().as_ref() == Some(&reference))
+                .and_then(|s| attr_name(&s.index()?));
+            match index {
+                Some(name) => used.insert(name),
+                // the whole set of inputs is passed on
+                None => return None,
+            };
+        }
+    }
+    Some(used)
+}
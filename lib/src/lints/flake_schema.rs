@@ -0,0 +1,225 @@
+use crate::{
+    session::{Mode, SessionInfo},
+    utils::{attr_name, edit_distance, first_key},
+    Metadata, Report, Rule,
+};
+
+use macros::lint;
+use rnix::{
+    types::{AttrSet, EntryHolder, KeyValue, Lambda, LetIn, Pattern, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks the structure of `flake.nix`: a missing `description`, an
+/// `outputs` function that does not take `self`, output names that look
+/// like typos of standard outputs, and misspelled systems in per-system
+/// outputs.
+///
+/// ## Why is this bad?
+/// Nix accepts unknown outputs and systems silently, so a typo means the
+/// output is never found by `nix build` or `nix flake check`, with no
+/// error pointing at the cause.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   outputs = { nixpkgs, ... }: {
+///     packgaes.x86_64-linx.default = nixpkgs.legacyPackages.x86_64-linux.hello;
+///   };
+/// }
+/// ```
+///
+/// Fix the names and describe the flake:
+///
+/// ```nix
+/// {
+///   description = "hello";
+///   outputs = { self, nixpkgs }: {
+///     packages.x86_64-linux.default = nixpkgs.legacyPackages.x86_64-linux.hello;
+///   };
+/// }
+/// ```
+#[lint(
+    name = "flake_schema",
+    note = "Found an issue in the structure of this flake",
+    code = 28,
+    match_with = SyntaxKind::NODE_ROOT,
+    modes = [Mode::Flake]
+)]
+struct FlakeSchema;
+
+static OUTPUTS: &[&str] = &[
+    "apps",
+    "bundlers",
+    "checks",
+    "darwinConfigurations",
+    "darwinModules",
+    "defaultApp",
+    "defaultBundler",
+    "defaultPackage",
+    "defaultTemplate",
+    "devShell",
+    "devShells",
+    "formatter",
+    "homeConfigurations",
+    "homeManagerModules",
+    "homeModules",
+    "hydraJobs",
+    "legacyPackages",
+    "lib",
+    "nixosConfigurations",
+    "nixosModule",
+    "nixosModules",
+    "overlay",
+    "overlays",
+    "packages",
+    "templates",
+];
+
+// outputs keyed by system first, as in `packages.<system>.<name>`
+static PER_SYSTEM: &[&str] = &[
+    "apps",
+    "bundlers",
+    "checks",
+    "defaultApp",
+    "defaultBundler",
+    "defaultPackage",
+    "devShell",
+    "devShells",
+    "formatter",
+    "legacyPackages",
+    "packages",
+];
+
+static SYSTEMS: &[&str] = &[
+    "aarch64-darwin",
+    "aarch64-linux",
+    "armv6l-linux",
+    "armv7l-linux",
+    "i686-linux",
+    "powerpc64le-linux",
+    "riscv64-linux",
+    "x86_64-darwin",
+    "x86_64-freebsd",
+    "x86_64-linux",
+];
+
+impl Rule for FlakeSchema {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let set = match node {
+            NodeOrToken::Node(node) => AttrSet::cast(node.first_child()?)?,
+            _ => return None,
+        };
+        let mut report = self.report();
+
+        if !set
+            .entries()
+            .any(|kv| first_key(&kv).as_deref() == Some("description"))
+        {
+            let at = set.node().first_token()?.text_range();
+            report = report.diagnostic(at, "This flake has no `description`");
+        }
+
+        let outputs = set
+            .entries()
+            .find(|kv| first_key(kv).as_deref() == Some("outputs"));
+        if let Some(lambda) = outputs.and_then(|kv| Lambda::cast(kv.value()?)) {
+            if let Some(pattern) = lambda.arg().and_then(Pattern::cast) {
+                if !pattern
+                    .entries()
+                    .any(|e| e.name().is_some_and(|n| n.as_str() == "self"))
+                {
+                    let at = pattern.node().text_range();
+                    report = report.diagnostic(at, "`outputs` does not take `self`");
+                }
+            }
+            for kv in body(lambda).iter().flat_map(|s| s.entries()) {
+                report = check_output(report, &kv);
+            }
+        }
+
+        if report.diagnostics.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+
+// the attribute set of outputs returned by the `outputs` function
+fn body(lambda: Lambda) -> Option<AttrSet> {
+    let mut body = lambda.body()?;
+    while let Some(let_in) = LetIn::cast(body.clone()) {
+        body = let_in.body()?;
+    }
+    AttrSet::cast(body)
+}
+
+fn closest(name: &str, known: &[&'static str]) -> Option<&'static str> {
+    known
+        .iter()
+        .copied()
+        .map(|k| (edit_distance(name, k), k))
+        .filter(|(d, _)| *d > 0 && *d <= 2)
+        .min_by_key(|(d, _)| *d)
+        .map(|(_, k)| k)
+}
+
+fn check_output(mut report: Report, kv: &KeyValue) -> Report {
+    let key = match kv.key() {
+        Some(k) => k,
+        None => return report,
+    };
+    let path = key.path().collect::<Vec<_>>();
+    let output = match path.first().and_then(attr_name) {
+        Some(o) => o,
+        None => return report,
+    };
+    // check the systems of a misspelled output as if it were spelled right
+    let output = if OUTPUTS.contains(&output.as_str()) {
+        output
+    } else if let Some(suggestion) = closest(&output, OUTPUTS) {
+        let at = path[0].text_range();
+        let message = format!(
+            "Unknown output `{}`, did you mean `{}`?",
+            output, suggestion
+        );
+        report = report.diagnostic(at, message);
+        suggestion.to_owned()
+    } else {
+        return report;
+    };
+    if !PER_SYSTEM.contains(&output.as_str()) {
+        return report;
+    }
+    // `packages.x86_64-linux.hello = ...;` or `packages = { x86_64-linux = ...; };`
+    let systems: Vec<SyntaxNode> = match path.get(1) {
+        Some(system) => vec![system.clone()],
+        None => kv
+            .value()
+            .and_then(AttrSet::cast)
+            .map(|set| {
+                set.entries()
+                    .filter_map(|kv| kv.key()?.path().next())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    };
+    for system in systems {
+        let name = match attr_name(&system) {
+            Some(n) => n,
+            None => continue,
+        };
+        if SYSTEMS.contains(&name.as_str()) {
+            continue;
+        }
+        if let Some(suggestion) = closest(&name, SYSTEMS) {
+            let at = system.text_range();
+            let message = format!("Unknown system `{}`, did you mean `{}`?", name, suggestion);
+            report = report.diagnostic(at, message);
+        }
+    }
+    report
+}
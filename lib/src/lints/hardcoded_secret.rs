@@ -0,0 +1,168 @@
+use crate::{
+    session::SessionInfo,
+    utils::{attr_name, string_literal},
+    Metadata, Report, Rule,
+};
+
+use macros::lint;
+use rnix::{
+    types::{KeyValue, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// Parts of attribute names that hold secrets, compared without case,
+/// `_` and `-`
+const NAMES: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "privatekey",
+    "apikey",
+    "accesskey",
+];
+
+/// Attribute names holding something other than the secret itself,
+/// such as `passwordFile` or `hashedPassword`
+const NOT_SECRETS: &[&str] = &["file", "path", "command", "hashed", "length"];
+
+/// Prefixes of tokens, with the shortest length of the token
+const CREDENTIALS: &[(&str, usize)] = &[
+    ("ghp_", 40),
+    ("gho_", 40),
+    ("ghu_", 40),
+    ("ghs_", 40),
+    ("ghr_", 40),
+    ("github_pat_", 40),
+    ("glpat-", 26),
+    ("xoxb-", 20),
+    ("xoxp-", 20),
+    ("AKIA", 20),
+];
+
+/// ## What it does
+/// Checks for string literals given to attributes named like secrets,
+/// such as `password` or `token`, and for literals that look like
+/// credentials, such as GitHub tokens or private keys. The names to
+/// check, and literals to allow, where `*` matches any text, can be set
+/// in statix.toml:
+///
+/// ```toml
+/// [options.hardcoded_secret]
+/// names = [ "password", "token", "secret" ]
+/// allow = [ "@*@", "changeme" ]
+/// ```
+///
+/// ## Why is this bad?
+/// Everything in the Nix store is readable by every user of the
+/// machine, and expressions are usually shared through version control.
+/// Secrets should be read from a file at runtime instead, such as with
+/// the `passwordFile` options of NixOS, agenix or sops-nix.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   services.grafana.settings.security.admin_password = "hunter2";
+/// }
+/// ```
+///
+/// Read the secret from a file:
+///
+/// ```nix
+/// {
+///   services.grafana.settings.security.admin_password = "$__file{/run/secrets/grafana}";
+/// }
+/// ```
+#[lint(
+    name = "hardcoded_secret",
+    note = "Found a secret in the expression",
+    code = 80,
+    match_with = SyntaxKind::NODE_STRING
+)]
+struct HardcodedSecret;
+
+impl Rule for HardcodedSecret {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let value = string_literal(node)?;
+        if value.trim().is_empty() {
+            return None;
+        }
+        let allowed = sess.option(self.name(), "allow").unwrap_or_default();
+        if allowed
+            .iter()
+            .any(|pattern| matches_pattern(pattern, &value))
+        {
+            return None;
+        }
+        let names = sess.option(self.name(), "names");
+        let message = match secret_name(node) {
+            Some(name) if is_secret_name(&name, names) => format!(
+                "`{}` is given a literal, which is readable by anyone from the Nix store",
+                name
+            ),
+            _ if is_credential(&value) => {
+                "This looks like a credential, which is readable by anyone from the Nix store"
+                    .to_owned()
+            }
+            _ => return None,
+        };
+        Some(self.report().diagnostic(node.text_range(), message))
+    }
+}
+
+// The last attribute of the key the string is the value of
+fn secret_name(node: &SyntaxNode) -> Option<String> {
+    let kv = node.parent().and_then(KeyValue::cast)?;
+    if kv.value()? != *node {
+        return None;
+    }
+    attr_name(&kv.key()?.path().last()?)
+}
+
+fn is_secret_name(name: &str, names: Option<&[String]>) -> bool {
+    let normalized = normalize(name);
+    if NOT_SECRETS.iter().any(|n| normalized.contains(n)) {
+        return false;
+    }
+    match names {
+        Some(names) => names.iter().any(|n| normalized.contains(&normalize(n))),
+        None => NAMES.iter().any(|n| normalized.contains(n)),
+    }
+}
+
+// `adminPassword` and `admin_password` as `adminpassword`
+fn normalize(name: &str) -> String {
+    name.to_lowercase().replace(['_', '-'], "")
+}
+
+fn is_credential(value: &str) -> bool {
+    // `"Authorization: Bearer ghp_..."`
+    let is_token = |word: &str| {
+        CREDENTIALS
+            .iter()
+            .any(|&(prefix, len)| word.starts_with(prefix) && word.len() >= len)
+    };
+    let is_private_key = value.contains("-----BEGIN") && value.contains("PRIVATE KEY-----");
+    is_private_key
+        || value
+            .split(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '=' | ':'))
+            .any(is_token)
+}
+
+// Whether `value` matches `pattern`, where `*` matches any text
+fn matches_pattern(pattern: &str, value: &str) -> bool {
+    match pattern.split_once('*') {
+        None => pattern == value,
+        Some((prefix, rest)) => {
+            value.starts_with(prefix)
+                && (prefix.len()..=value.len())
+                    .filter(|&i| value.is_char_boundary(i))
+                    .any(|i| matches_pattern(rest, &value[i..]))
+        }
+    }
+}
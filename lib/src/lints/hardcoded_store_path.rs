@@ -0,0 +1,108 @@
+use crate::{session::SessionInfo, Metadata, Report, Rule};
+
+use macros::lint;
+use rnix::{NodeOrToken, SyntaxElement, SyntaxKind, TextRange, TextSize};
+
+const STORE_DIR: &str = "/nix/store/";
+
+/// Characters of the hashes in store paths
+const HASH_CHARS: &str = "0123456789abcdfghijklmnpqrsvwxyz";
+const HASH_LEN: usize = 32;
+
+/// ## What it does
+/// Checks for string and path literals containing a path in
+/// `/nix/store`.
+///
+/// ## Why is this bad?
+/// The store path only exists on machines that happen to have built or
+/// downloaded it, and changes with every update of the package. It is
+/// not a dependency of the expression either, so it can be garbage
+/// collected. Refer to the package instead, which gives its store path
+/// when interpolated.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   shell = "/nix/store/9krlzvny65gdc8s7kpb6lkx8cd02c25b-bash-5.2-p15/bin/bash";
+/// }
+/// ```
+///
+/// Refer to the package:
+///
+/// ```nix
+/// {
+///   shell = "${pkgs.bash}/bin/bash";
+/// }
+/// ```
+#[lint(
+    name = "hardcoded_store_path",
+    note = "Found a hard-coded store path",
+    code = 47,
+    match_with = [SyntaxKind::TOKEN_STRING_CONTENT, SyntaxKind::TOKEN_PATH]
+)]
+struct HardcodedStorePath;
+
+impl Rule for HardcodedStorePath {
+    fn validate(&self, token: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let token = match token {
+            NodeOrToken::Token(token) => token,
+            _ => return None,
+        };
+        let text = token.text();
+        let mut report = self.report();
+        for (offset, _) in text.match_indices(STORE_DIR) {
+            let rest = &text[offset + STORE_DIR.len()..];
+            let hash = match rest.get(..HASH_LEN) {
+                Some(hash) if hash.chars().all(|c| HASH_CHARS.contains(c)) => hash,
+                _ => continue,
+            };
+            let path_len = STORE_DIR.len()
+                + rest
+                    .find(|c: char| c.is_whitespace() || "\"'`;:,)".contains(c))
+                    .unwrap_or(rest.len());
+            let start = token.text_range().start() + TextSize::from(offset as u32);
+            let at = TextRange::at(start, TextSize::from(path_len as u32));
+            let path = &text[offset..offset + path_len];
+            let message = match package_name(&rest[hash.len()..]) {
+                Some(name) => {
+                    let subpath = path[STORE_DIR.len() + HASH_LEN..]
+                        .find('/')
+                        .map_or("", |i| &path[STORE_DIR.len() + HASH_LEN + i..]);
+                    format!(
+                        "This path is only in some stores, try `${{pkgs.{}}}{}`",
+                        name, subpath
+                    )
+                }
+                None => "This path is only in some stores, refer to the package instead".to_owned(),
+            };
+            report = report.diagnostic(at, message);
+        }
+        if report.diagnostics.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+
+// `hello` from `-hello-2.12.1/bin/hello`, the name of a store path without
+// its version
+fn package_name(rest: &str) -> Option<String> {
+    let name = rest.strip_prefix('-')?.split('/').next()?;
+    let parts = name
+        .split('-')
+        .take_while(|part| !part.starts_with(|c: char| c.is_ascii_digit()))
+        .collect::<Vec<_>>();
+    let name = parts.join("-");
+    let valid = name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "_-'".contains(c));
+    // `source` is the name of any unpacked source
+    if valid && name != "source" {
+        Some(name)
+    } else {
+        None
+    }
+}
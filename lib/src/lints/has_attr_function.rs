@@ -0,0 +1,96 @@
+use crate::{
+    make,
+    session::SessionInfo,
+    utils::{call, function_name, key_text, string_literal},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Select, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for `builtins.hasAttr` and `lib.hasAttr` with a literal name,
+/// such as `builtins.hasAttr "enable" cfg`, and replaces them with the
+/// `?` operator. Names that are computed are left to the function.
+///
+/// ## Why is this bad?
+/// `cfg ? enable` is shorter, reads in the same order as `cfg.enable`,
+/// and is what the other checks for attributes use, such as
+/// `cfg ? services.nginx`.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   enabled = builtins.hasAttr "enable" cfg;
+/// }
+/// ```
+///
+/// Use the `?` operator:
+///
+/// ```nix
+/// {
+///   enabled = cfg ? enable;
+/// }
+/// ```
+#[lint(
+    name = "has_attr_function",
+    note = "Found `hasAttr` with a literal name",
+    code = 88,
+    match_with = SyntaxKind::NODE_APPLY
+)]
+struct HasAttrFunction;
+
+impl Rule for HasAttrFunction {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let (function, args) = call(node);
+        if !is_has_attr(&function) {
+            return None;
+        }
+        let (name, set) = match args.as_slice() {
+            [name, set] => (string_literal(name)?, set),
+            _ => return None,
+        };
+        // `a ? b` binds tighter than anything but selections, calls and `-`
+        let set = match set.kind() {
+            SyntaxKind::NODE_IDENT
+            | SyntaxKind::NODE_SELECT
+            | SyntaxKind::NODE_OR_DEFAULT
+            | SyntaxKind::NODE_APPLY
+            | SyntaxKind::NODE_PAREN
+            | SyntaxKind::NODE_ATTR_SET
+            | SyntaxKind::NODE_LIST
+            | SyntaxKind::NODE_STRING
+            | SyntaxKind::NODE_LITERAL => set.clone(),
+            _ => make::parenthesize(set).node().clone(),
+        };
+        let key = rnix::parse(&key_text(&name)).node().first_child()?;
+        let replacement = make::binary(&set, "?", &key);
+        let at = node.text_range();
+        let message = "Use the `?` operator instead of `hasAttr`";
+        let fix = Suggestion::new(at, make::replacing(replacement.node(), node))
+            .description(format!("replace with `{}`", replacement.node()));
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// `builtins.hasAttr`, `lib.hasAttr` or `hasAttr`
+fn is_has_attr(function: &SyntaxNode) -> bool {
+    if function_name(function).as_deref() != Some("hasAttr") {
+        return false;
+    }
+    match Select::cast(function.clone()) {
+        Some(select) => matches!(
+            select.set().map(|set| set.to_string()).as_deref(),
+            Some("builtins" | "lib" | "lib.attrsets")
+        ),
+        None => true,
+    }
+}
@@ -0,0 +1,149 @@
+use crate::{
+    make,
+    session::SessionInfo,
+    utils::{indentation, key_text, string_literal, unparenthesized},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{BinOp, BinOpKind, IfElse, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for chains of `if` comparing the same expression to
+/// strings, three or more by default, and replaces them with a lookup
+/// in a set. The number of comparisons can be set in statix.toml:
+///
+/// ```toml
+/// [options.if_else_chain]
+/// min_branches = 4
+/// ```
+///
+/// ## Why is this bad?
+/// A set lists each case once, and finds the case without comparing
+/// against each of the others. The lookup fails when the expression is
+/// not a string, where the comparisons would be false.
+///
+/// ## Example
+///
+/// ```nix
+/// if system == "x86_64-linux" then "amd64"
+/// else if system == "aarch64-linux" then "arm64"
+/// else if system == "i686-linux" then "386"
+/// else throw "unsupported system"
+/// ```
+///
+/// Look the value up in a set:
+///
+/// ```nix
+/// {
+///   x86_64-linux = "amd64";
+///   aarch64-linux = "arm64";
+///   i686-linux = "386";
+/// }.${system} or (throw "unsupported system")
+/// ```
+#[lint(
+    name = "if_else_chain",
+    note = "Found a chain of `if` comparing to strings",
+    code = 68,
+    match_with = SyntaxKind::NODE_IF_ELSE
+)]
+struct IfElseChain;
+
+impl Rule for IfElseChain {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        // the head of the chain, not an `if` in an `else`
+        if let Some(parent) = node.parent().and_then(IfElse::cast) {
+            if parent.else_body().as_ref() == Some(node) {
+                return None;
+            }
+        }
+        let min_branches = sess
+            .option(self.name(), "min_branches")
+            .and_then(|values| values.first()?.parse::<usize>().ok())
+            .unwrap_or(3);
+        let mut subject: Option<String> = None;
+        let mut branches = Vec::new();
+        let mut current = node.clone();
+        let default = loop {
+            let if_else = match IfElse::cast(current.clone()) {
+                Some(if_else) => if_else,
+                None => break current,
+            };
+            let (lhs, rhs) = match comparison(&if_else.condition()?) {
+                Some(sides) => sides,
+                None => break current,
+            };
+            let (expr, key) = match (string_literal(&lhs), string_literal(&rhs)) {
+                (None, Some(key)) => (lhs, key),
+                (Some(key), None) => (rhs, key),
+                _ => break current,
+            };
+            match &subject {
+                Some(subject) if *subject != expr.to_string() => break current,
+                Some(_) => {}
+                None => subject = Some(expr.to_string()),
+            }
+            branches.push((key, if_else.body()?));
+            current = if_else.else_body()?;
+        };
+        if branches.len() < min_branches {
+            return None;
+        }
+        let subject = subject?;
+        let at = node.text_range();
+        let message = format!(
+            "Look `{}` up in a set of the {} cases",
+            subject,
+            branches.len()
+        );
+        let mut keys = branches.iter().map(|(key, _)| key).collect::<Vec<_>>();
+        keys.sort();
+        keys.dedup();
+        if keys.len() != branches.len() {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let style = sess.style();
+        let (base, unit, eol) = (indentation(node), style.indent_unit(), style.eol());
+        let mut text = format!("{{{}", eol);
+        for (key, body) in &branches {
+            text.push_str(&format!(
+                "{}{}{} = {};{}",
+                base,
+                unit,
+                key_text(key),
+                body,
+                eol
+            ));
+        }
+        // `or` takes a single operand, `x.a or f y` applies `x.a or f`
+        let default = match default.kind() {
+            SyntaxKind::NODE_IDENT
+            | SyntaxKind::NODE_LITERAL
+            | SyntaxKind::NODE_STRING
+            | SyntaxKind::NODE_LIST
+            | SyntaxKind::NODE_ATTR_SET
+            | SyntaxKind::NODE_PAREN => default,
+            _ => make::parenthesize(&default).node().clone(),
+        };
+        text.push_str(&format!("{}}}.${{{}}} or {}", base, subject, default));
+        let replacement = make::replacing(&rnix::parse(&text).node().first_child()?, node);
+        let fix = Suggestion::new(at, replacement).description("replace with a lookup in a set");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// the sides of `a == b`
+fn comparison(node: &SyntaxNode) -> Option<(SyntaxNode, SyntaxNode)> {
+    let op = BinOp::cast(unparenthesized(node))?;
+    if op.operator() != Some(BinOpKind::Equal) {
+        return None;
+    }
+    Some((op.lhs()?, op.rhs()?))
+}
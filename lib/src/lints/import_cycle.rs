@@ -0,0 +1,118 @@
+use std::collections::HashSet;
+
+use crate::{
+    project::{ImportKind, Project, ProjectReport},
+    session::SessionInfo,
+    Metadata, Report, Rule,
+};
+
+use macros::lint;
+use rnix::SyntaxElement;
+
+/// ## What it does
+/// Checks for files that import each other in a cycle, through `import`
+/// or `callPackage`. Runs with `statix check --project`.
+///
+/// ## Why is this bad?
+/// Evaluating any file of the cycle imports the others, which sooner or
+/// later import it again. Unless every step of the cycle is lazy, this
+/// fails with an infinite recursion error that does not point at the
+/// imports involved.
+///
+/// ## Example
+///
+/// ```nix
+/// # ./a.nix
+/// { b = import ./b.nix; x = 1; }
+///
+/// # ./b.nix
+/// { y = (import ./a.nix).x + 1; }
+/// ```
+///
+/// Move what both files need into a third file that imports neither.
+#[lint(
+    name = "import_cycle",
+    note = "Files import each other in a cycle",
+    code = 26
+)]
+struct ImportCycle;
+
+impl Rule for ImportCycle {
+    fn validate(&self, _node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        None
+    }
+
+    fn check_project(&self, project: &Project, _sess: &SessionInfo) -> Vec<ProjectReport> {
+        let mut reports = Vec::new();
+        for cycle in cycles(project) {
+            let names = cycle
+                .iter()
+                .chain(cycle.first())
+                .map(|&(file, _)| project.file(file).path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(" -> ");
+            for &(file, import) in &cycle {
+                let at = project.file(file).imports[import].path.text_range();
+                let message = format!("Import cycle: {}", names);
+                let report = self.report().diagnostic(at, message);
+                reports.push(ProjectReport { file, report });
+            }
+        }
+        reports
+    }
+}
+
+// imports of a file that are evaluated along with it, as
+// (index of the import, imported file)
+fn edges(project: &Project, file: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    project
+        .file(file)
+        .imports
+        .iter()
+        .enumerate()
+        .filter(|(_, i)| i.kind != ImportKind::Module)
+        .filter_map(|(idx, i)| Some((idx, i.target?)))
+}
+
+// every distinct cycle found by a depth first search, as a list of
+// (file, index of the import leading to the next file)
+fn cycles(project: &Project) -> Vec<Vec<(usize, usize)>> {
+    let mut visited = vec![false; project.files().len()];
+    let mut seen = HashSet::new();
+    let mut cycles = Vec::new();
+    for start in 0..project.files().len() {
+        if visited[start] {
+            continue;
+        }
+        // (file, edges left to explore, import taken to reach the next file)
+        let mut stack = vec![(start, edges(project, start).collect::<Vec<_>>(), None)];
+        visited[start] = true;
+        while let Some((_, pending, taken)) = stack.last_mut() {
+            let (import, next) = match pending.pop() {
+                Some(edge) => edge,
+                None => {
+                    stack.pop();
+                    continue;
+                }
+            };
+            *taken = Some(import);
+            if let Some(pos) = stack.iter().position(|(f, _, _)| *f == next) {
+                let mut cycle = stack[pos..]
+                    .iter()
+                    .map(|(f, _, i)| (*f, i.unwrap()))
+                    .collect::<Vec<_>>();
+                let first = (0..cycle.len()).min_by_key(|&i| cycle[i].0).unwrap();
+                cycle.rotate_left(first);
+                let mut key = cycle.iter().map(|(f, _)| *f).collect::<Vec<_>>();
+                key.sort_unstable();
+                if seen.insert(key) {
+                    cycles.push(cycle);
+                }
+            } else if !visited[next] {
+                visited[next] = true;
+                stack.push((next, edges(project, next).collect(), None));
+            }
+        }
+    }
+    cycles
+}
@@ -0,0 +1,150 @@
+use crate::{
+    scope,
+    session::SessionInfo,
+    utils::{call, function_name},
+    Metadata, Report, Rule, Severity,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, BinOp, BinOpKind, Ident, KeyValue, Select, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// Functions reading the path they are given during evaluation
+const READERS: &[&str] = &[
+    "import",
+    "scopedImport",
+    "readFile",
+    "readDir",
+    "fileContents",
+    "importJSON",
+    "importTOML",
+    "callPackage",
+];
+
+/// Functions returning a derivation
+const BUILDERS: &[&str] = &[
+    "derivation",
+    "mkDerivation",
+    "runCommand",
+    "runCommandLocal",
+    "runCommandCC",
+    "runCommandNoCC",
+    "writeText",
+    "writeTextFile",
+    "writeTextDir",
+    "writeScript",
+    "writeShellScript",
+    "symlinkJoin",
+    "linkFarm",
+    "applyPatches",
+    "fetchFromGitHub",
+    "fetchFromGitLab",
+    "fetchFromSourcehut",
+    "fetchgit",
+    "fetchzip",
+    "fetchpatch",
+];
+
+/// ## What it does
+/// Checks for reading the output of a derivation during evaluation,
+/// such as `import (runCommand ...)` or `readFile "${drv}/file"`,
+/// known as import from derivation (IFD). Projects using it on purpose
+/// can lower the severity in statix.toml:
+///
+/// ```toml
+/// [options.import_from_derivation]
+/// severity = "hint"
+/// ```
+///
+/// ## Why is this bad?
+/// Evaluation stops until the derivation is built, one at a time, and
+/// fails where building is not possible, such as `nix flake check`
+/// evaluating outputs for other systems, or Hydra.
+///
+/// ## Example
+///
+/// ```nix
+/// import (pkgs.runCommand "generated.nix" { } "${pkgs.gen}/bin/gen > $out")
+/// ```
+///
+/// Commit the generated file instead, or build the result as a
+/// derivation rather than evaluating it.
+#[lint(
+    name = "import_from_derivation",
+    note = "Found import from derivation",
+    code = 74,
+    match_with = SyntaxKind::NODE_APPLY
+)]
+struct ImportFromDerivation;
+
+impl Rule for ImportFromDerivation {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        let reader = function_name(&apply.lambda()?)?;
+        if !READERS.contains(&reader.as_str()) {
+            return None;
+        }
+        let path = apply.value()?;
+        let builder = derivation(&path, true)?;
+        let severity = sess
+            .option(self.name(), "severity")
+            .and_then(|values| values.first()?.parse::<Severity>().ok())
+            .unwrap_or_else(|| self.severity());
+        let message = format!(
+            "`{}` reads the output of `{}` during evaluation, which has to be built first",
+            reader, builder
+        );
+        Some(
+            self.report()
+                .severity(severity)
+                .diagnostic(node.text_range(), message),
+        )
+    }
+}
+
+// The builder of the derivation `node` refers to, following variables
+// to their binding in a `let` or `rec` set once when `follow` is set
+fn derivation(node: &SyntaxNode, follow: bool) -> Option<String> {
+    match node.kind() {
+        SyntaxKind::NODE_PAREN => derivation(&node.first_child()?, follow),
+        SyntaxKind::NODE_APPLY => {
+            let (function, _) = call(node);
+            let name = function_name(&function)?;
+            // `builtins.fetchGit` and the like are fetched, not built
+            let from_builtins = Select::cast(function)
+                .and_then(|s| s.set())
+                .and_then(|set| function_name(&set));
+            if BUILDERS.contains(&name.as_str()) && from_builtins.as_deref() != Some("builtins") {
+                Some(name)
+            } else {
+                None
+            }
+        }
+        // `"${drv}/default.nix"`
+        SyntaxKind::NODE_STRING => node
+            .children()
+            .filter(|c| c.kind() == SyntaxKind::NODE_STRING_INTERPOL)
+            .find_map(|c| derivation(&c.first_child()?, follow)),
+        // `drv + "/default.nix"`
+        SyntaxKind::NODE_BIN_OP => {
+            let op = BinOp::cast(node.clone())?;
+            if op.operator() != Some(BinOpKind::Add) {
+                return None;
+            }
+            derivation(&op.lhs()?, follow)
+        }
+        SyntaxKind::NODE_IDENT if follow => {
+            let name = Ident::cast(node.clone())?;
+            let binding = scope::lookup(node, name.as_str())?;
+            let value = KeyValue::cast(binding.entry)?.value()?;
+            derivation(&value, false)
+        }
+        _ => None,
+    }
+}
@@ -0,0 +1,72 @@
+use crate::{session::SessionInfo, Metadata, Report, Rule};
+
+use macros::lint;
+use rnix::{
+    types::{Select, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextSize,
+};
+
+/// ## What it does
+/// Checks for `builtins.getEnv`. Calls that are meant to be evaluated
+/// with `--impure` can be acknowledged with a comment mentioning
+/// `--impure`, on the same line or the line before.
+///
+/// ## Why is this bad?
+/// The result depends on the environment of whoever evaluates the
+/// expression, and is always `""` in pure evaluation, as with flakes,
+/// without any error.
+///
+/// ## Example
+///
+/// ```nix
+/// { pkgs }:
+/// pkgs.writeText "user" (builtins.getEnv "USER")
+/// ```
+///
+/// Take the value as an argument:
+///
+/// ```nix
+/// { pkgs, user }:
+/// pkgs.writeText "user" user
+/// ```
+#[lint(
+    name = "impure_get_env",
+    note = "Found `builtins.getEnv`",
+    code = 50,
+    match_with = SyntaxKind::NODE_SELECT
+)]
+struct ImpureGetEnv;
+
+impl Rule for ImpureGetEnv {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let select = Select::cast(node.clone())?;
+        if select.set()?.text() != "builtins" || select.index()?.text() != "getEnv" {
+            return None;
+        }
+        if is_acknowledged(node) {
+            return None;
+        }
+        let at = node.text_range();
+        let message = "This is `\"\"` in pure evaluation, take the value as an argument or a flake input, or mention `--impure` in a comment";
+        Some(self.report().diagnostic(at, message))
+    }
+}
+
+// a comment mentioning `--impure` on the line of `node` or the line before
+fn is_acknowledged(node: &SyntaxNode) -> bool {
+    let root = node.ancestors().last().unwrap_or_else(|| node.clone());
+    let text = root.to_string();
+    let line_of = |offset: TextSize| text[..usize::from(offset)].matches('\n').count();
+    let line = line_of(node.text_range().start());
+    root.descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| t.kind() == SyntaxKind::TOKEN_COMMENT && t.text().contains("--impure"))
+        .any(|t| {
+            let comment = line_of(t.text_range().start());
+            comment == line || comment + 1 == line
+        })
+}
@@ -0,0 +1,127 @@
+use crate::{
+    session::{Mode, SessionInfo},
+    utils::{attr_name, derivation_attrs, function_name, BUILDERS},
+    Metadata, Report, Rule,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, AttrSet, EntryHolder, Paren, TokenWrapper, TypedNode, With, Wrapper},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for derivations whose `meta` lacks some attributes, by
+/// default `description` and `license`. The attributes to require can
+/// be set in statix.toml:
+///
+/// ```toml
+/// [options.incomplete_meta]
+/// required = [ "description", "license", "maintainers", "platforms" ]
+/// ```
+///
+/// A `meta` that is not written out, such as `meta = old.meta;`, is
+/// left alone.
+///
+/// ## Why is this bad?
+/// `meta` is what search.nixos.org shows, what decides whether a
+/// package may be built for a platform, and whether its license is
+/// accepted.
+///
+/// ## Example
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   meta.description = "A program that greets";
+/// }
+/// ```
+///
+/// Add the missing attributes:
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   meta = {
+///     description = "A program that greets";
+///     license = lib.licenses.gpl3Plus;
+///   };
+/// }
+/// ```
+#[lint(
+    name = "incomplete_meta",
+    note = "Found a derivation with an incomplete `meta`",
+    code = 67,
+    match_with = SyntaxKind::NODE_APPLY,
+    modes = [Mode::Nixpkgs]
+)]
+struct IncompleteMeta;
+
+impl Rule for IncompleteMeta {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        let function = apply.lambda()?;
+        if !BUILDERS.contains(&function_name(&function)?.as_str()) {
+            return None;
+        }
+        let set = derivation_attrs(&apply.value()?)?;
+        let mut meta = None;
+        let mut keys = Vec::new();
+        for kv in set.entries() {
+            let key = kv.key()?;
+            let mut path = key.path();
+            if attr_name(&path.next()?)?.as_str() != "meta" {
+                continue;
+            }
+            meta.get_or_insert_with(|| key.node().text_range());
+            match path.next() {
+                // `meta.description = "";`
+                Some(attr) => keys.push(attr_name(&attr)?),
+                // `meta = { description = ""; };`
+                None => keys.extend(meta_keys(&kv.value()?)?),
+            }
+        }
+        let default = ["description".to_owned(), "license".to_owned()];
+        let required = sess.option(self.name(), "required").unwrap_or(&default);
+        let missing = required
+            .iter()
+            .filter(|r| !keys.contains(r))
+            .map(|r| format!("`{}`", r))
+            .collect::<Vec<_>>();
+        let (at, message) = match meta {
+            _ if missing.is_empty() => return None,
+            Some(at) => (at, format!("`meta` has no {}", missing.join(", "))),
+            None => (
+                function.text_range(),
+                format!("This derivation has no `meta`, add one with {}", missing.join(", ")),
+            ),
+        };
+        Some(self.report().diagnostic(at, message))
+    }
+}
+
+// the attributes of a `meta` set written out, also under a `with`
+fn meta_keys(value: &SyntaxNode) -> Option<Vec<String>> {
+    let mut value = value.clone();
+    let set = loop {
+        value = if let Some(with) = With::cast(value.clone()) {
+            with.body()?
+        } else if let Some(paren) = Paren::cast(value.clone()) {
+            paren.inner()?
+        } else {
+            break AttrSet::cast(value)?;
+        };
+    };
+    let mut keys = Vec::new();
+    for kv in set.entries() {
+        keys.push(attr_name(&kv.key()?.path().next()?)?);
+    }
+    for inherit in set.inherits() {
+        keys.extend(inherit.idents().map(|i| i.as_str().to_owned()));
+    }
+    Some(keys)
+}
@@ -0,0 +1,232 @@
+use crate::{
+    make, scope,
+    session::SessionInfo,
+    utils::{attr_name, call, function_name, GLOBALS},
+    Metadata, Report, Rule, Severity, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, Ident, KeyValue, ParsedType, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange, TextSize,
+};
+use std::convert::TryFrom;
+
+/// Variables set for the shell by `stdenv` and the builder, which no
+/// `with` brings into scope
+const SHELL_VARIABLES: &[&str] = &[
+    "HOME",
+    "NIX_BUILD_CORES",
+    "NIX_BUILD_TOP",
+    "NIX_CFLAGS_COMPILE",
+    "NIX_LDFLAGS",
+    "PATH",
+    "PWD",
+    "TMPDIR",
+    "out",
+    "sourceRoot",
+];
+
+/// Functions whose last argument is a shell script
+const SCRIPT_WRITERS: &[&str] = &[
+    "runCommand",
+    "runCommandCC",
+    "runCommandLocal",
+    "writeScript",
+    "writeScriptBin",
+    "writeShellScript",
+    "writeShellScriptBin",
+];
+
+/// ## What it does
+/// Checks indented strings, `''...''`, for interpolations of variables
+/// that are not bound, which are likely shell variables such as
+/// `${HOME}`, and escapes them. Escaped interpolations of variables
+/// bound in the file, such as `''${version}`, are pointed out as well.
+///
+/// Under a `with`, any variable may be bound, so only the scripts of
+/// phases, hooks and functions such as `runCommand` are checked, for
+/// variables the shell sets, such as `${out}` or `${TMPDIR}`.
+///
+/// ## Why is this bad?
+/// In an indented string, `${x}` is interpolated by Nix, and `''${x}`
+/// is left as `${x}` for the shell. An unescaped shell variable fails
+/// evaluation, or worse, picks up a Nix variable of the same name
+/// under a `with`, while an escaped Nix variable ends up empty in the
+/// script.
+///
+/// ## Example
+///
+/// ```nix
+/// ''
+///   mkdir -p ${HOME}/.config
+/// ''
+/// ```
+///
+/// Escape the interpolation for the shell:
+///
+/// ```nix
+/// ''
+///   mkdir -p ''${HOME}/.config
+/// ''
+/// ```
+#[lint(
+    name = "indented_interpolation",
+    note = "Found a suspicious interpolation in an indented string",
+    code = 75,
+    match_with = SyntaxKind::NODE_STRING
+)]
+struct IndentedInterpolation;
+
+impl Rule for IndentedInterpolation {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let start = node.first_token()?;
+        // variables of a `with` cannot be told apart from shell variables,
+        // but for those of `stdenv` in scripts
+        let under_with = node.ancestors().any(|a| a.kind() == SyntaxKind::NODE_WITH);
+        if start.text() != "''" || (under_with && !is_script(node)) {
+            return None;
+        }
+        // `${HOME}`
+        let unbound = node
+            .children()
+            .filter(|c| c.kind() == SyntaxKind::NODE_STRING_INTERPOL)
+            .filter_map(|interpol| {
+                let ident = interpol.first_child().and_then(Ident::cast)?;
+                let name = ident.as_str();
+                let is_bound = scope::lookup(node, name).is_some()
+                    || GLOBALS.contains(&name)
+                    || name.starts_with("__")
+                    || (under_with && !SHELL_VARIABLES.contains(&name));
+                if is_bound {
+                    None
+                } else {
+                    Some((interpol, name.to_owned()))
+                }
+            })
+            .collect::<Vec<_>>();
+
+        // `''${version}`
+        let escaped = node
+            .children_with_tokens()
+            .filter_map(|c| c.into_token())
+            .filter(|t| t.kind() == SyntaxKind::TOKEN_STRING_CONTENT)
+            .flat_map(|t| {
+                let offset = t.text_range().start();
+                escaped_interpolations(t.text())
+                    .into_iter()
+                    .map(move |(range, name)| (range + offset, name))
+            })
+            .filter(|(_, name)| scope::lookup(node, name).is_some())
+            .collect::<Vec<_>>();
+
+        if unbound.is_empty() && escaped.is_empty() {
+            return None;
+        }
+        let mut report = self.report();
+        if let Some((first, name)) = unbound.first() {
+            let edits = unbound
+                .iter()
+                .map(|(interpol, _)| {
+                    (
+                        TextRange::empty(interpol.text_range().start()),
+                        "''".to_owned(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let replacement = make::edited(&ParsedType::try_from(node.clone()).ok()?, &edits);
+            let fix = Suggestion::new(node.text_range(), replacement.node().clone())
+                .description("escape the interpolation with `''${`");
+            report = report.suggest(first.text_range(), unbound_message(name), fix);
+            for (interpol, name) in unbound.iter().skip(1) {
+                report = report.diagnostic(interpol.text_range(), unbound_message(name));
+            }
+        } else {
+            report = report.severity(Severity::Hint);
+        }
+        for (at, name) in escaped {
+            let message = format!(
+                "`''${{{0}}}` is left to the shell, use `${{{0}}}` to interpolate the Nix variable `{0}`",
+                name
+            );
+            report = report.diagnostic(at, message);
+        }
+        Some(report)
+    }
+}
+
+// The script of a phase or hook, `installPhase = '' ... '';`, or of a
+// function such as `runCommand`
+fn is_script(node: &SyntaxNode) -> bool {
+    let parent = match node.parent() {
+        Some(parent) => parent,
+        None => return false,
+    };
+    if let Some(kv) = KeyValue::cast(parent.clone()) {
+        let name = kv
+            .key()
+            .and_then(|key| key.path().last())
+            .and_then(|attr| attr_name(&attr));
+        return kv.value().as_ref() == Some(node) && name.is_some_and(|n| is_script_attr(&n));
+    }
+    match Apply::cast(parent) {
+        Some(apply) if apply.value().as_ref() == Some(node) => {
+            let (function, _) = call(apply.node());
+            matches!(function_name(&function), Some(name) if SCRIPT_WRITERS.contains(&name.as_str()))
+        }
+        _ => false,
+    }
+}
+
+// `installPhase`, `postPatch`, `shellHook`, `buildCommand` or `script`
+fn is_script_attr(name: &str) -> bool {
+    let is_hook = ["pre", "post"].iter().any(|prefix| {
+        matches!(
+            name.strip_prefix(prefix).and_then(|rest| rest.chars().next()),
+            Some(c) if c.is_ascii_uppercase()
+        )
+    });
+    is_hook || name.ends_with("Phase") || matches!(name, "shellHook" | "buildCommand" | "script")
+}
+
+fn unbound_message(name: &str) -> String {
+    format!(
+        "`{0}` is not bound, write `''${{{0}}}` to leave `${{{0}}}` to the shell",
+        name
+    )
+}
+
+// The ranges and names of `''${name}` in the text of an indented string
+fn escaped_interpolations(text: &str) -> Vec<(TextRange, String)> {
+    let mut found = Vec::new();
+    let mut i = 0;
+    while let Some(pos) = text[i..].find("''") {
+        let at = i + pos;
+        let rest = &text[at + 2..];
+        if let Some(interpol) = rest.strip_prefix("${") {
+            let name = interpol
+                .split('}')
+                .next()
+                .filter(|_| interpol.contains('}'))
+                .filter(|name| is_identifier(name));
+            if let Some(name) = name {
+                let end = at + 2 + 2 + name.len() + 1;
+                let range = TextRange::new(TextSize::from(at as u32), TextSize::from(end as u32));
+                found.push((range, name.to_owned()));
+            }
+        }
+        // `''` escapes the character after it, `'''` is a literal `''`
+        i = at + 2 + rest.chars().next().map_or(0, char::len_utf8);
+    }
+    found
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '\'' | '-'))
+}
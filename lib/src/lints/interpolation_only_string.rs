@@ -0,0 +1,78 @@
+use crate::{
+    make,
+    session::SessionInfo,
+    utils::{is_string, lone_interpolation},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{types::TypedNode, NodeOrToken, SyntaxElement, SyntaxKind};
+
+/// ## What it does
+/// Checks for strings made of a single interpolation, such as
+/// `"${name}"`.
+///
+/// ## Why is this bad?
+/// Interpolating a string into an otherwise empty string gives the very
+/// same string. Where the interpolation converts a value to a string,
+/// `toString` says so. Strings that are known to be strings are fixed,
+/// for others, `toString` is only suggested, as it converts paths
+/// differently: `"${./foo}"` is a store path, `toString ./foo` is not.
+///
+/// ## Example
+///
+/// ```nix
+/// "${lib.concatStringsSep ", " names}"
+/// ```
+///
+/// Use the string:
+///
+/// ```nix
+/// lib.concatStringsSep ", " names
+/// ```
+#[lint(
+    name = "interpolation_only_string",
+    note = "Found a string made of a single interpolation",
+    code = 44,
+    match_with = SyntaxKind::NODE_STRING
+)]
+struct InterpolationOnlyString;
+
+impl Rule for InterpolationOnlyString {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        // `{ "${name}" = ...; }` names an attribute
+        if node.parent()?.kind() == SyntaxKind::NODE_KEY {
+            return None;
+        }
+        let expr = lone_interpolation(node)?;
+        // paths are left to `path_to_string`
+        if expr.kind() == SyntaxKind::NODE_PATH_WITH_INTERPOL
+            || matches!(expr.first_token(), Some(t) if t.kind() == SyntaxKind::TOKEN_PATH)
+        {
+            return None;
+        }
+        let at = node.text_range();
+        if is_string(&expr) {
+            let replacement = make::replacing(&expr, node);
+            let message = format!("`{}` is already a string", expr);
+            let description = format!("replace with `{}`", replacement);
+            Some(self.report().suggest(
+                at,
+                message,
+                Suggestion::new(at, replacement).description(description),
+            ))
+        } else {
+            let to_string = make::apply(make::ident("toString").node(), &expr);
+            let message = format!(
+                "Use `{}` directly if it is a string, or `{}` otherwise",
+                expr,
+                make::replacing(to_string.node(), node)
+            );
+            Some(self.report().diagnostic(at, message))
+        }
+    }
+}
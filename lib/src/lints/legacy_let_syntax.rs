@@ -12,6 +12,8 @@ use rnix::{
 ///
 /// ## Why is this bad?
 /// This syntax construct is undocumented, refrain from using it.
+/// The lint is only run when targeting Nix 2, newer interpreters are
+/// not assumed to parse it at all.
 ///
 /// ## Example
 ///
@@ -39,12 +41,14 @@ use rnix::{
     name = "legacy_let_syntax",
     note = "Using undocumented `let` syntax",
     code = 5,
+    priority = 1,
+    max_nix_version = "2.99",
     match_with = SyntaxKind::NODE_LEGACY_LET
 )]
 struct ManualInherit;
 
 impl Rule for ManualInherit {
-    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
         if_chain! {
             if let NodeOrToken::Node(node) = node;
             if let Some(legacy_let) = LegacyLet::cast(node.clone());
@@ -56,7 +60,7 @@ impl Rule for ManualInherit {
             then {
                 let inherits = legacy_let.inherits();
                 let entries = legacy_let.entries();
-                let attrset = make::attrset(inherits, entries, true);
+                let attrset = make::attrset(inherits, entries, true, sess.style());
                 let parenthesized = make::parenthesize(attrset.node());
                 let selected = make::select(parenthesized.node(), make::ident("body").node());
 
@@ -64,7 +68,7 @@ impl Rule for ManualInherit {
                 let message = "Prefer `rec` over undocumented `let` syntax";
                 let replacement = selected.node().clone();
 
-                Some(self.report().suggest(at, message, Suggestion::new(at, replacement)))
+                Some(self.report().suggest(at, message, Suggestion::new(at, replacement).description("rewrite as a `rec` attribute set")))
             } else {
                 None
             }
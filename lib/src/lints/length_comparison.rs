@@ -0,0 +1,97 @@
+use crate::{make, session::SessionInfo, utils::builtin_call, Metadata, Report, Rule, Suggestion};
+
+use macros::lint;
+use rnix::{
+    types::{BinOp, BinOpKind, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for comparisons of the length of a list with zero, such as
+/// `builtins.length xs == 0` or `lib.length xs > 0`, and compares the
+/// list with `[ ]` instead.
+///
+/// ## Why is this bad?
+/// The length is not needed to tell whether a list is empty, and
+/// `xs == [ ]` says what is checked without working out what the
+/// comparison with a number means.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   hasPatches = builtins.length patches != 0;
+/// }
+/// ```
+///
+/// Compare with the empty list:
+///
+/// ```nix
+/// {
+///   hasPatches = patches != [ ];
+/// }
+/// ```
+#[lint(
+    name = "length_comparison",
+    note = "Found the length of a list compared with zero",
+    code = 86,
+    match_with = SyntaxKind::NODE_BIN_OP
+)]
+struct LengthComparison;
+
+impl Rule for LengthComparison {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let op = BinOp::cast(node.clone())?;
+        let (lhs, rhs) = (op.lhs()?, op.rhs()?);
+        let kind = op.operator()?;
+
+        // `length xs == 0`, or `0 == length xs`
+        let (list, n, kind) = match (length_of(&lhs), length_of(&rhs)) {
+            (Some(list), None) => (list, rhs, kind),
+            (None, Some(list)) => (list, lhs, flipped(kind)?),
+            _ => return None,
+        };
+        let n = match n.first_token() {
+            Some(token) if n.kind() == SyntaxKind::NODE_LITERAL => token.text().parse().ok()?,
+            _ => return None,
+        };
+        let operator = match (kind, n) {
+            (BinOpKind::Equal, 0) | (BinOpKind::Less, 1) | (BinOpKind::LessOrEq, 0) => "==",
+            (BinOpKind::NotEqual, 0) | (BinOpKind::More, 0) | (BinOpKind::MoreOrEq, 1) => "!=",
+            _ => return None,
+        };
+
+        let at = node.text_range();
+        let empty = rnix::parse("[ ]").node().first_child()?;
+        let replacement = make::binary(&list, operator, &empty);
+        let message = "Compare the list with `[ ]` instead of its length";
+        let fix = Suggestion::new(at, make::replacing(replacement.node(), node))
+            .description(format!("replace with `{}`", replacement.node()));
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// The list of `builtins.length xs`, `lib.length xs` or `length xs`
+fn length_of(node: &SyntaxNode) -> Option<SyntaxNode> {
+    match builtin_call(node, "length")?.as_slice() {
+        [list] => Some(list.clone()),
+        _ => None,
+    }
+}
+
+// The comparison with its operands swapped, `a < b` for `b > a`
+fn flipped(kind: BinOpKind) -> Option<BinOpKind> {
+    use BinOpKind::*;
+    match kind {
+        Less => Some(More),
+        LessOrEq => Some(MoreOrEq),
+        More => Some(Less),
+        MoreOrEq => Some(LessOrEq),
+        Equal | NotEqual => Some(kind),
+        _ => None,
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{session::SessionInfo, utils::function_name, Metadata, Report, Rule, Severity};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, Select, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// Functions of `lib.debug` running tests, also available from `lib`
+const TEST_FUNCTIONS: &[&str] = &["runTests", "testAllTrue"];
+
+/// ## What it does
+/// Checks for uses of `lib.debug`, and for calls to `lib.runTests` and
+/// `lib.testAllTrue` outside of files named like tests, such as
+/// `tests.nix`. The trace functions are left to `debug_trace`. The
+/// severity can be set in statix.toml:
+///
+/// ```toml
+/// [options.lib_debug]
+/// severity = "hint"
+/// ```
+///
+/// ## Why is this bad?
+/// `lib.debug` holds helpers for debugging and testing Nix code, which
+/// have no use in the code being tested.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   checks = lib.debug.runTests { testId = { expr = lib.id 1; expected = 1; }; };
+/// }
+/// ```
+///
+/// Move the tests to a file of their own, such as `tests.nix`.
+#[lint(
+    name = "lib_debug",
+    note = "Found a debugging helper of `lib.debug`",
+    code = 65,
+    match_with = [SyntaxKind::NODE_SELECT, SyntaxKind::NODE_APPLY]
+)]
+struct LibDebug;
+
+impl Rule for LibDebug {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let stem = sess.file().and_then(|f| f.file_stem()?.to_str());
+        let is_test_file = matches!(
+            stem,
+            Some(s) if s.starts_with("test") || s.ends_with("test") || s.ends_with("tests")
+        );
+        if is_test_file {
+            return None;
+        }
+        let name = if let Some(select) = Select::cast(node.clone()) {
+            // `lib.debug.f`
+            let set = select.set()?;
+            if !is_lib_debug(&set) {
+                return None;
+            }
+            select.index()?.to_string()
+        } else {
+            // `lib.runTests { }`, through `lib.debug` it is reported above
+            let function = Apply::cast(node.clone())?.lambda()?;
+            let name = function_name(&function)?;
+            let through_debug = Select::cast(function).and_then(|s| s.set());
+            if !TEST_FUNCTIONS.contains(&name.as_str())
+                || matches!(through_debug, Some(set) if is_lib_debug(&set))
+            {
+                return None;
+            }
+            name
+        };
+        if name.starts_with("trace") {
+            return None;
+        }
+        let severity = sess
+            .option(self.name(), "severity")
+            .and_then(|values| values.first()?.parse::<Severity>().ok())
+            .unwrap_or_else(|| self.severity());
+        let message = if TEST_FUNCTIONS.contains(&name.as_str()) {
+            format!("`{}` belongs in a file of tests", name)
+        } else {
+            format!("`{}` is a debugging helper", name)
+        };
+        Some(
+            self.report()
+                .severity(severity)
+                .diagnostic(node.text_range(), message),
+        )
+    }
+}
+
+// `lib.debug`, or `pkgs.lib.debug`
+fn is_lib_debug(node: &SyntaxNode) -> bool {
+    let select = match Select::cast(node.clone()) {
+        Some(select) => select,
+        None => return false,
+    };
+    if !matches!(select.index(), Some(index) if index.text() == "debug") {
+        return false;
+    }
+    matches!(select.set(), Some(set) if function_name(&set).as_deref() == Some("lib"))
+}
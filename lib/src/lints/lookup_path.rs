@@ -0,0 +1,170 @@
+use crate::{
+    make, scope,
+    session::{Mode, SessionInfo},
+    utils::{attr_name, function_name, key_text, string_literal},
+    Metadata, Report, Rule, Severity, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, AttrSet, EntryHolder, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for lookup paths, such as `<nixpkgs>`, which are found
+/// through `NIX_PATH`. Lookups that are intended can be allowed in
+/// statix.toml:
+///
+/// ```toml
+/// [options.lookup_path]
+/// allow = [ "nixos-config" ]
+/// ```
+///
+/// In flakes, lookup paths are reported as errors, and
+/// `import <nixpkgs> { }` is replaced with the packages of the
+/// `nixpkgs` input, when the input and `system` are in scope.
+///
+/// ## Why is this bad?
+/// What `<nixpkgs>` refers to depends on the machine evaluating the
+/// expression, and lookups fail in pure and restricted evaluation,
+/// which flakes use. In a flake, `import <nixpkgs> { }` silently
+/// uses the channels of the user rather than the locked input.
+///
+/// ## Example
+///
+/// ```nix
+/// { pkgs ? import <nixpkgs> { } }:
+/// pkgs.hello
+/// ```
+///
+/// Pin nixpkgs, as a flake input, or with a fetcher:
+///
+/// ```nix
+/// { pkgs ? import (fetchTarball "https://github.com/NixOS/nixpkgs/archive/nixos-23.05.tar.gz") { } }:
+/// pkgs.hello
+/// ```
+///
+/// In a flake, use the packages of the input:
+///
+/// ```nix
+/// {
+///   outputs = { self, nixpkgs }: let system = "x86_64-linux"; in {
+///     packages.${system}.default = nixpkgs.legacyPackages.${system}.hello;
+///   };
+/// }
+/// ```
+#[lint(
+    name = "lookup_path",
+    note = "Found a lookup path",
+    code = 48,
+    match_with = SyntaxKind::TOKEN_PATH
+)]
+struct LookupPath;
+
+impl Rule for LookupPath {
+    fn validate(&self, token: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let token = match token {
+            NodeOrToken::Token(token) => token,
+            _ => return None,
+        };
+        let lookup = token.text().strip_prefix('<')?.strip_suffix('>')?;
+        // `<nixpkgs/lib>` looks up `nixpkgs`
+        let name = lookup.split('/').next()?;
+        let allowed = sess.option(self.name(), "allow").unwrap_or_default();
+        if allowed.iter().any(|a| a == name || a == lookup) {
+            return None;
+        }
+        let at = token.text_range();
+        let report = if sess.mode() == Mode::Flake && lookup == "nixpkgs" {
+            let call = match imported_nixpkgs(&token.parent()) {
+                Some(call) => call,
+                None => return Some(self.flake_error(token, name)),
+            };
+            let message = "`import <nixpkgs> { }` is not available in pure evaluation, use `inputs.nixpkgs.legacyPackages.${system}` instead";
+            let report = self.report().severity(Severity::Error);
+            match legacy_packages(&call) {
+                Some(replacement) => {
+                    let fix =
+                        Suggestion::new(call.text_range(), make::replacing(&replacement, &call))
+                            .description("use the `nixpkgs` input");
+                    report.suggest(at, message, fix)
+                }
+                None => report.diagnostic(at, message),
+            }
+        } else if sess.mode() == Mode::Flake {
+            self.flake_error(token, name)
+        } else {
+            let message = format!("`{}` depends on `NIX_PATH`, pin `{}` instead", token, name);
+            self.report().diagnostic(at, message)
+        };
+        Some(report)
+    }
+}
+
+impl LookupPath {
+    fn flake_error(&self, token: &rnix::SyntaxToken, name: &str) -> Report {
+        let message = format!(
+            "`{}` is not available in pure evaluation, add `{}` to the inputs of the flake instead",
+            token, name
+        );
+        self.report()
+            .severity(Severity::Error)
+            .diagnostic(token.text_range(), message)
+    }
+}
+
+// `import <nixpkgs> { }` around the literal `<nixpkgs>`
+fn imported_nixpkgs(literal: &SyntaxNode) -> Option<SyntaxNode> {
+    let import = literal.parent().and_then(Apply::cast)?;
+    if function_name(&import.lambda()?)? != "import" || import.value()? != *literal {
+        return None;
+    }
+    let call = import.node().parent().and_then(Apply::cast)?;
+    if call.lambda()? != *import.node() {
+        return None;
+    }
+    Some(call.node().clone())
+}
+
+// `nixpkgs.legacyPackages.${system}` for `import <nixpkgs> { inherit
+// system; }`, when the arguments set nothing else
+fn legacy_packages(call: &SyntaxNode) -> Option<SyntaxNode> {
+    let is_bound = |name: &str| scope::lookup(call, name).is_some();
+    let input = if is_bound("nixpkgs") {
+        "nixpkgs"
+    } else if is_bound("inputs") {
+        "inputs.nixpkgs"
+    } else {
+        return None;
+    };
+    let args = AttrSet::cast(Apply::cast(call.clone())?.value()?)?;
+    let mut system = None;
+    for kv in args.entries() {
+        let key = kv.key()?;
+        let mut path = key.path();
+        match (path.next().and_then(|k| attr_name(&k)), path.next()) {
+            (Some(name), None) if name == "system" => system = Some(kv.value()?),
+            _ => return None,
+        }
+    }
+    for inherit in args.inherits() {
+        for ident in inherit.idents() {
+            match (ident.as_str(), inherit.from()) {
+                ("system", None) => system = Some(make::ident("system").node().clone()),
+                _ => return None,
+            }
+        }
+    }
+    let system = match system {
+        // `legacyPackages.x86_64-linux` for a literal system
+        Some(system) => match string_literal(&system) {
+            Some(literal) => key_text(&literal),
+            None => format!("${{{}}}", system),
+        },
+        None if is_bound("system") => "${system}".to_owned(),
+        None => return None,
+    };
+    let text = format!("{}.legacyPackages.{}", input, system);
+    rnix::parse(&text).node().first_child()
+}
@@ -59,7 +59,8 @@ impl Rule for ManualInherit {
                 let at = node.text_range();
                 let replacement = make::inherit_stmt(&[key]).node().clone();
                 let message = "This assignment is better written with `inherit`";
-                Some(self.report().suggest(at, message, Suggestion::new(at, replacement)))
+                let description = format!("replace with `{}`", replacement);
+                Some(self.report().suggest(at, message, Suggestion::new(at, replacement).description(description)))
             } else {
                 None
             }
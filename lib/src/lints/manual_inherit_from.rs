@@ -64,7 +64,8 @@ impl Rule for ManualInherit {
                     make::inherit_from_stmt(set, &[key]).node().clone()
                 };
                 let message = "This assignment is better written with `inherit`";
-                Some(self.report().suggest(at, message, Suggestion::new(at, replacement)))
+                let description = format!("replace with `{}`", replacement);
+                Some(self.report().suggest(at, message, Suggestion::new(at, replacement).description(description)))
             } else {
                 None
             }
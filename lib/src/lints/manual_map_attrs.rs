@@ -0,0 +1,188 @@
+use crate::{
+    make, scope,
+    session::SessionInfo,
+    utils::{attr_name, call, function_name, is_reference, unparenthesized},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{AttrSet, EntryHolder, Lambda, ParsedType, Select, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+use std::convert::TryFrom;
+
+/// ## What it does
+/// Checks for sets rebuilt from the names of another set, with
+/// `listToAttrs` over `attrNames`, or with `genAttrs`, and replaces
+/// them with `mapAttrs`, or `lib.mapAttrs'` when the names change.
+///
+/// ## Why is this bad?
+/// `mapAttrs` says what the expression does, and is faster: it does not
+/// go through a list, nor look each attribute up by name.
+///
+/// ## Example
+///
+/// ```nix
+/// builtins.listToAttrs (map (name: {
+///   inherit name;
+///   value = toString set.${name};
+/// }) (builtins.attrNames set))
+/// ```
+///
+/// Use `mapAttrs`:
+///
+/// ```nix
+/// builtins.mapAttrs (name: value: toString value) set
+/// ```
+#[lint(
+    name = "manual_map_attrs",
+    note = "Found a set rebuilt from the names of another",
+    code = 72,
+    match_with = SyntaxKind::NODE_APPLY
+)]
+struct ManualMapAttrs;
+
+impl Rule for ManualMapAttrs {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let (function, args) = call(node);
+        let (names, lambda) = match (function_name(&function)?.as_str(), args.as_slice()) {
+            // `listToAttrs (map f (attrNames set))`
+            ("listToAttrs", [list]) => {
+                let (map, map_args) = call(&unparenthesized(list));
+                match (function_name(&map)?.as_str(), map_args.as_slice()) {
+                    ("map", [lambda, names]) => (names.clone(), lambda.clone()),
+                    _ => return None,
+                }
+            }
+            // `genAttrs (attrNames set) f`
+            ("genAttrs", [names, lambda]) => (names.clone(), lambda.clone()),
+            _ => return None,
+        };
+        let (attr_names, attr_names_args) = call(&unparenthesized(&names));
+        let set = match (
+            function_name(&attr_names)?.as_str(),
+            attr_names_args.as_slice(),
+        ) {
+            ("attrNames", [set]) => set.clone(),
+            _ => return None,
+        };
+        let lambda = Lambda::cast(unparenthesized(&lambda))?;
+        let arg = lambda.arg()?;
+        if arg.kind() != SyntaxKind::NODE_IDENT {
+            return None;
+        }
+        let arg = arg.text().to_string();
+        let body = lambda.body()?;
+        let (name, value) = if function_name(&function)? == "genAttrs" {
+            (None, body)
+        } else {
+            name_value(&body, &arg)?
+        };
+        let at = node.text_range();
+        let renames = matches!(&name, Some(name) if name.to_string() != arg);
+        let message = if renames {
+            "Use `lib.mapAttrs'` instead"
+        } else {
+            "Use `mapAttrs` instead"
+        };
+        let value = unparenthesized(&value);
+        // `set.${name}` is the value `mapAttrs` gives
+        let mut edits = Vec::new();
+        for select in value.descendants().filter_map(Select::cast) {
+            let index = select.index()?.to_string();
+            if select.set()?.to_string() == set.to_string()
+                && (index == format!("${{{}}}", arg) || index == format!("\"${{{}}}\"", arg))
+            {
+                edits.push((select.node().text_range(), "value".to_owned()));
+            }
+        }
+        let references = |name: &str, node: &SyntaxNode| {
+            node.descendants()
+                .any(|n| n.kind() == SyntaxKind::NODE_IDENT && is_reference(&n) && n.text() == name)
+        };
+        if references("value", &value) || arg == "value" {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let value = make::edited(&ParsedType::try_from(value).ok()?, &edits);
+        let value = value.node();
+        let used = |node: &SyntaxNode| references(&arg, node);
+        let name_arg = if used(value) || matches!(&name, Some(n) if used(n)) {
+            arg.as_str()
+        } else {
+            "_"
+        };
+        let (function, body) = if renames {
+            let lib = scope::lookup(node, "lib").is_some();
+            if !lib {
+                return Some(self.report().diagnostic(at, message));
+            }
+            let pair = make::apply(
+                make::apply(
+                    make::select(
+                        make::ident("lib").node(),
+                        make::ident("nameValuePair").node(),
+                    )
+                    .node(),
+                    name.as_ref()?,
+                )
+                .node(),
+                value,
+            );
+            ("lib.mapAttrs'".to_owned(), pair.node().clone())
+        } else {
+            let prefix = match Select::cast(function) {
+                Some(select) => select.set()?.to_string(),
+                None => "builtins".to_owned(),
+            };
+            (format!("{}.mapAttrs", prefix), value.clone())
+        };
+        let text = format!("{} ({}: value: {})", function, name_arg, body);
+        let replacement = make::apply(&rnix::parse(&text).node().first_child()?, &set);
+        let fix = Suggestion::new(at, make::replacing(replacement.node(), node))
+            .description(format!("replace with `{}`", function));
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// the name and value of `{ name = n; value = v; }`, `{ inherit name; value
+// = v; }` or `nameValuePair n v`, with no name when it is `arg`
+fn name_value(body: &SyntaxNode, arg: &str) -> Option<(Option<SyntaxNode>, SyntaxNode)> {
+    let body = unparenthesized(body);
+    let (function, args) = call(&body);
+    if let (Some("nameValuePair"), [name, value]) =
+        (function_name(&function).as_deref(), args.as_slice())
+    {
+        return Some((Some(name.clone()), value.clone()));
+    }
+    let set = AttrSet::cast(body)?;
+    let (mut name, mut value) = (None, None);
+    for kv in set.entries() {
+        let key = kv.key()?;
+        let mut path = key.path();
+        let name_of_key = attr_name(&path.next()?)?;
+        if path.next().is_some() {
+            return None;
+        }
+        match name_of_key.as_str() {
+            "name" => name = Some(kv.value()?),
+            "value" => value = Some(kv.value()?),
+            _ => return None,
+        }
+    }
+    for inherit in set.inherits() {
+        for ident in inherit.idents() {
+            match ident.as_str() {
+                "name" if inherit.from().is_none() && arg == "name" => {
+                    name = Some(ident.node().clone())
+                }
+                _ => return None,
+            }
+        }
+    }
+    Some((Some(name?), value?))
+}
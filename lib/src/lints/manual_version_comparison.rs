@@ -0,0 +1,174 @@
+use crate::{
+    make, scope,
+    session::{Mode, SessionInfo},
+    utils::{call, function_name, string_literal, unparenthesized},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{BinOp, BinOpKind, TypedNode, UnaryOp, UnaryOpKind},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for comparisons of the result of `compareVersions` with a
+/// number, and for `<`, `<=`, `>` and `>=` between a string and a
+/// version such as `"1.2"`, and replaces them with `lib.versionOlder`
+/// and `lib.versionAtLeast`.
+///
+/// ## Why is this bad?
+/// `versionAtLeast` says what is being checked, where the comparison
+/// of `compareVersions` with a number has to be worked out. Strings are
+/// compared character by character rather than as versions, so
+/// `"1.10" < "1.9"`.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   patches = lib.optional (builtins.compareVersions version "2.0" >= 0) ./fix.patch;
+///   legacy = version < "1.10";
+/// }
+/// ```
+///
+/// Use `lib.versionAtLeast` and `lib.versionOlder`:
+///
+/// ```nix
+/// {
+///   patches = lib.optional (lib.versionAtLeast version "2.0") ./fix.patch;
+///   legacy = lib.versionOlder version "1.10";
+/// }
+/// ```
+#[lint(
+    name = "manual_version_comparison",
+    note = "Found a manual version comparison",
+    code = 77,
+    match_with = SyntaxKind::NODE_BIN_OP,
+    modes = [Mode::Nixpkgs]
+)]
+struct ManualVersionComparison;
+
+impl Rule for ManualVersionComparison {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let op = BinOp::cast(node.clone())?;
+        let (lhs, rhs) = (op.lhs()?, op.rhs()?);
+        let kind = op.operator()?;
+
+        // `compareVersions a b >= 0`, or `0 <= compareVersions a b`
+        let compared = match (compare_versions(&lhs), compare_versions(&rhs)) {
+            (Some(args), _) => Some((kind, &rhs, args)),
+            (None, Some(args)) => Some((flipped(kind)?, &lhs, args)),
+            (None, None) => None,
+        };
+        let (function, a, b, message) = if let Some((kind, n, (a, b))) = compared {
+            let (function, a, b) = from_comparison(kind, integer(n)?, a, b)?;
+            let message = format!(
+                "Use `{}` instead of comparing the result of `compareVersions`",
+                function
+            );
+            (function, a, b, message)
+        } else {
+            // `version < "1.10"`
+            let (version, literal, kind) = match (is_version(&lhs), is_version(&rhs)) {
+                (false, true) => (lhs, rhs, kind),
+                (true, false) => (rhs, lhs, flipped(kind)?),
+                _ => return None,
+            };
+            let (function, a, b) = match kind {
+                BinOpKind::Less => ("versionOlder", version, literal),
+                BinOpKind::MoreOrEq => ("versionAtLeast", version, literal),
+                BinOpKind::More => ("versionOlder", literal, version),
+                BinOpKind::LessOrEq => ("versionAtLeast", literal, version),
+                _ => return None,
+            };
+            let message = format!(
+                "Strings are compared character by character, use `{}` to compare versions",
+                function
+            );
+            (function, a, b, message)
+        };
+
+        let at = node.text_range();
+        let has_lib = scope::lookup(node, "lib").is_some();
+        if !has_lib {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let function = make::select(make::ident("lib").node(), make::ident(function).node());
+        let replacement = make::apply(make::apply(function.node(), &a).node(), &b);
+        let fix = Suggestion::new(at, make::replacing(replacement.node(), node))
+            .description(format!("replace with `{}`", replacement.node()));
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// The arguments of `compareVersions a b`
+fn compare_versions(node: &SyntaxNode) -> Option<(SyntaxNode, SyntaxNode)> {
+    let (function, args) = call(&unparenthesized(node));
+    match args.as_slice() {
+        [a, b] if function_name(&function).as_deref() == Some("compareVersions") => {
+            Some((a.clone(), b.clone()))
+        }
+        _ => None,
+    }
+}
+
+// The function and arguments checking `compareVersions a b <kind> n`
+fn from_comparison(
+    kind: BinOpKind,
+    n: i64,
+    a: SyntaxNode,
+    b: SyntaxNode,
+) -> Option<(&'static str, SyntaxNode, SyntaxNode)> {
+    use BinOpKind::*;
+    match (kind, n) {
+        (MoreOrEq, 0) | (More, -1) | (NotEqual, -1) => Some(("versionAtLeast", a, b)),
+        (Less, 0) | (LessOrEq, -1) | (Equal, -1) => Some(("versionOlder", a, b)),
+        (More, 0) | (MoreOrEq, 1) | (Equal, 1) => Some(("versionOlder", b, a)),
+        (LessOrEq, 0) | (Less, 1) | (NotEqual, 1) => Some(("versionAtLeast", b, a)),
+        _ => None,
+    }
+}
+
+// The comparison with its operands swapped, `a < b` for `b > a`
+fn flipped(kind: BinOpKind) -> Option<BinOpKind> {
+    use BinOpKind::*;
+    match kind {
+        Less => Some(More),
+        LessOrEq => Some(MoreOrEq),
+        More => Some(Less),
+        MoreOrEq => Some(LessOrEq),
+        Equal | NotEqual => Some(kind),
+        _ => None,
+    }
+}
+
+// The value of an integer literal, or of its negation
+fn integer(node: &SyntaxNode) -> Option<i64> {
+    if let Some(unary) = UnaryOp::cast(node.clone()) {
+        if unary.operator() != UnaryOpKind::Negate {
+            return None;
+        }
+        return integer(&unary.value()?).map(|n| -n);
+    }
+    match node.first_token() {
+        Some(token) if node.kind() == SyntaxKind::NODE_LITERAL => token.text().parse().ok(),
+        _ => None,
+    }
+}
+
+// Whether `node` is a string of a version, such as `"1.2.3"`
+fn is_version(node: &SyntaxNode) -> bool {
+    match string_literal(node) {
+        Some(s) => {
+            s.contains('.')
+                && s.split('.')
+                    .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+        }
+        None => false,
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{
+    make, session::SessionInfo, utils::with_preceeding_whitespace, Metadata, Report, Rule,
+    Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Inherit, TypedNode, Wrapper},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for consecutive `inherit (x)` statements that inherit from
+/// the same expression.
+///
+/// ## Why is this bad?
+/// One statement reads better, and makes it obvious that the names
+/// come from the same place.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   inherit (pkgs) hello;
+///   inherit (pkgs) cowsay;
+/// }
+/// ```
+///
+/// Merge the statements:
+///
+/// ```nix
+/// {
+///   inherit (pkgs) hello cowsay;
+/// }
+/// ```
+#[lint(
+    name = "mergeable_inherit",
+    note = "Found inherits that can be merged",
+    code = 36,
+    match_with = [
+        SyntaxKind::NODE_ATTR_SET,
+        SyntaxKind::NODE_LET_IN,
+        SyntaxKind::NODE_LEGACY_LET
+    ]
+)]
+struct MergeableInherit;
+
+impl Rule for MergeableInherit {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        // runs of neighbouring statements inheriting from the same source
+        let mut runs: Vec<Vec<Inherit>> = Vec::new();
+        let mut previous: Option<String> = None;
+        for child in node.children() {
+            let source = Inherit::cast(child.clone()).and_then(|i| Some((source(&i)?, i)));
+            match (source, runs.last_mut()) {
+                (Some((source, inherit)), Some(run)) if previous.as_ref() == Some(&source) => {
+                    run.push(inherit);
+                }
+                (Some((source, inherit)), _) => {
+                    runs.push(vec![inherit]);
+                    previous = Some(source);
+                }
+                (None, _) => previous = None,
+            }
+        }
+        let mut report = self.report();
+        for run in runs.iter().filter(|run| run.len() > 1) {
+            let from = run[0].from()?.inner()?;
+            let idents = run.iter().flat_map(|i| i.idents()).collect::<Vec<_>>();
+            let merged = make::inherit_from_stmt(from.clone(), &idents);
+            let first = run[0].node().text_range();
+            let description = format!("replace with `{}`", merged.node());
+            report = report.suggest(
+                first,
+                format!("Merge the statements inheriting from `{}` ...", from),
+                Suggestion::new(first, merged.node().clone()).description(description),
+            );
+            for inherit in &run[1..] {
+                let at = inherit.node().text_range();
+                let removed = with_preceeding_whitespace(inherit.node());
+                report = report.suggest(
+                    at,
+                    "... into the first one",
+                    Suggestion::new(removed, make::empty().node().clone())
+                        .description("merge into the first statement"),
+                );
+            }
+        }
+        if report.diagnostics.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+
+// the expression a statement inherits from, if it only inherits plain
+// identifiers, `inherit (x) "a";` is left alone
+fn source(inherit: &Inherit) -> Option<String> {
+    let from = inherit.from()?.inner()?;
+    let plain = inherit
+        .node()
+        .children()
+        .filter(|c| c.kind() != SyntaxKind::NODE_INHERIT_FROM)
+        .all(|c| c.kind() == SyntaxKind::NODE_IDENT);
+    if plain {
+        Some(normalized(&from))
+    } else {
+        None
+    }
+}
+
+// the text of an expression, without whitespace and comments
+fn normalized(node: &SyntaxNode) -> String {
+    node.descendants_with_tokens()
+        .filter_map(|e| e.into_token())
+        .filter(|t| {
+            !matches!(
+                t.kind(),
+                SyntaxKind::TOKEN_WHITESPACE | SyntaxKind::TOKEN_COMMENT
+            )
+        })
+        .map(|t| t.text().to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
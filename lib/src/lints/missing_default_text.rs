@@ -0,0 +1,122 @@
+use crate::{
+    make, scope,
+    session::{Mode, SessionInfo},
+    utils::{attr_name, bool_literal, function_name, indentation},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, AttrSet, EntryHolder, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
+};
+
+/// ## What it does
+/// Checks for options declared with `mkOption` whose `default` is not a
+/// literal, such as `pkgs.hello`, and that have no `defaultText`.
+///
+/// ## Why is this bad?
+/// The manual shows the default of each option. Without `defaultText`,
+/// it is evaluated when building the manual, which fails or shows a
+/// store path rather than the expression.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   options.services.hello.package = lib.mkOption {
+///     type = lib.types.package;
+///     default = pkgs.hello;
+///   };
+/// }
+/// ```
+///
+/// Add `defaultText`:
+///
+/// ```nix
+/// {
+///   options.services.hello.package = lib.mkOption {
+///     type = lib.types.package;
+///     default = pkgs.hello;
+///     defaultText = lib.literalExpression "pkgs.hello";
+///   };
+/// }
+/// ```
+#[lint(
+    name = "missing_default_text",
+    note = "Found an option default without `defaultText`",
+    code = 59,
+    match_with = SyntaxKind::NODE_APPLY,
+    modes = [Mode::NixosModule]
+)]
+struct MissingDefaultText;
+
+impl Rule for MissingDefaultText {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        if function_name(&apply.lambda()?)? != "mkOption" {
+            return None;
+        }
+        let set = AttrSet::cast(apply.value()?)?;
+        let mut default = None;
+        for kv in set.entries() {
+            match attr_name(&kv.key()?.path().next()?)?.as_str() {
+                "defaultText" => return None,
+                "default" => default = Some(kv),
+                _ => {}
+            }
+        }
+        let default = default?;
+        let value = default.value()?;
+        if is_literal(&value) {
+            return None;
+        }
+        let at = value.text_range();
+        let message = format!("The manual would evaluate `{}`, add `defaultText`", value);
+        let lib = scope::lookup(node, "lib").is_some();
+        let text = value.to_string();
+        if !lib || text.contains('\n') {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let escaped = text
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace("${", "\\${");
+        let separator = if set.node().to_string().contains('\n') {
+            format!("{}{}", sess.style().eol(), indentation(default.node()))
+        } else {
+            " ".to_owned()
+        };
+        let entry = format!(
+            "{}defaultText = lib.literalExpression \"{}\";",
+            separator, escaped
+        );
+        let end = TextRange::empty(default.node().text_range().end());
+        let replacement = make::edited(&set, &[(end, entry)]);
+        let fix = Suggestion::new(set.node().text_range(), replacement.node().clone())
+            .description("add `defaultText`");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// values the manual can show as they are written
+fn is_literal(node: &SyntaxNode) -> bool {
+    match node.kind() {
+        SyntaxKind::NODE_LITERAL => true,
+        SyntaxKind::NODE_STRING => !node
+            .children()
+            .any(|c| c.kind() == SyntaxKind::NODE_STRING_INTERPOL),
+        SyntaxKind::NODE_IDENT => bool_literal(node).is_some() || node.text() == "null",
+        SyntaxKind::NODE_PAREN | SyntaxKind::NODE_LIST => node.children().all(|c| is_literal(&c)),
+        SyntaxKind::NODE_ATTR_SET => node.children().all(|entry| {
+            entry.kind() == SyntaxKind::NODE_KEY_VALUE
+                && matches!(entry.last_child(), Some(value) if is_literal(&value))
+        }),
+        SyntaxKind::NODE_UNARY_OP => node.children().all(|c| is_literal(&c)),
+        _ => false,
+    }
+}
@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use crate::{project::is_relative_path, session::SessionInfo, Metadata, Report, Rule};
+
+use if_chain::if_chain;
+use macros::lint;
+use rnix::{NodeOrToken, SyntaxElement, SyntaxKind};
+
+/// ## What it does
+/// Checks for relative paths that do not exist, such as
+/// `import ./missing.nix`, `src = ./gone;` or `imports = [ ./nope.nix ];`.
+/// Paths produced by builds, like `result`, are skipped; set them with
+/// `generated` in `statix.toml`.
+///
+/// ## Why is this bad?
+/// Evaluation fails as soon as the path is used. Usually the file was
+/// renamed or moved and the reference was not updated.
+///
+/// ## Example
+///
+/// ```nix
+/// { imports = [ ./hardware-configuration.nix ./nope.nix ]; }
+/// ```
+///
+/// Remove the path or point it at an existing file:
+///
+/// ```nix
+/// { imports = [ ./hardware-configuration.nix ]; }
+/// ```
+#[lint(
+    name = "missing_path",
+    note = "Found a relative path that does not exist",
+    code = 27,
+    match_with = SyntaxKind::NODE_LITERAL
+)]
+struct MissingPath;
+
+impl Rule for MissingPath {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        if_chain! {
+            if let NodeOrToken::Node(node) = node;
+            if is_relative_path(node);
+            if let Some(dir) = sess.file().and_then(Path::parent);
+            let path = node.text().to_string();
+            if !sess.is_generated(Path::new(&path));
+            if !dir.join(&path).exists();
+            then {
+                let at = node.text_range();
+                let message = format!("`{}` does not exist", path);
+                Some(self.report().diagnostic(at, message))
+            } else {
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,308 @@
+use std::path::Path;
+
+use crate::{
+    project::{Project, ProjectReport},
+    session::{Mode, SessionInfo},
+    utils::{
+        attr_name, call, edit_distance, first_key, function_name, is_reference, unparenthesized,
+    },
+    Metadata, Report, Rule,
+};
+
+use macros::lint;
+use rnix::{
+    types::{AttrSet, EntryHolder, Ident, Lambda, LetIn, List, Select, TokenWrapper, TypedNode},
+    SyntaxElement, SyntaxNode, TextRange,
+};
+
+/// ## What it does
+/// Checks the options of the NixOS modules of a project: options that
+/// are set in `config` but declared nowhere next to the options of the
+/// same module (usually a typo), and options that are declared but never
+/// read through `config`. Runs with `statix check --project`, on files
+/// in the `nixos` mode.
+///
+/// ## Why is this bad?
+/// Setting a misspelled option fails only when the system is evaluated,
+/// far from the typo. An option that is never read does nothing, users
+/// setting it will wonder why it has no effect.
+///
+/// ## Example
+///
+/// ```nix
+/// { config, lib, ... }: {
+///   options.services.hello.enable = lib.mkEnableOption "hello";
+///   options.services.hello.greeting = lib.mkOption { type = lib.types.str; };
+///   config.services.hello.enabel = true;
+/// }
+/// ```
+///
+/// Set the declared option, and use or remove options that are not read:
+///
+/// ```nix
+/// { config, lib, ... }: {
+///   options.services.hello.enable = lib.mkEnableOption "hello";
+///   config.services.hello.enable = true;
+/// }
+/// ```
+#[lint(
+    name = "module_options",
+    note = "Found an option that is not declared or never read",
+    code = 30
+)]
+struct ModuleOptions;
+
+// an option declared, set or read in a module
+struct Site {
+    file: usize,
+    path: Vec<String>,
+    at: TextRange,
+}
+
+// keys of a module that are not shorthand for `config`
+static MODULE_KEYS: &[&str] = &["_file", "disabledModules", "imports", "key", "meta"];
+
+static DECLARE: &[&str] = &[
+    "mkEnableOption",
+    "mkOption",
+    "mkPackageOption",
+    "mkPackageOptionMD",
+];
+
+impl Rule for ModuleOptions {
+    fn validate(&self, _node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        None
+    }
+
+    fn check_project(&self, project: &Project, _sess: &SessionInfo) -> Vec<ProjectReport> {
+        let (mut declared, mut set, mut read) = (Vec::new(), Vec::new(), Vec::new());
+        for (idx, file) in project.files().iter().enumerate() {
+            if file.sess.mode() != Mode::NixosModule {
+                continue;
+            }
+            let module = match file.expr().and_then(module_body) {
+                Some(m) => m,
+                None => continue,
+            };
+            let explicit = module
+                .entries()
+                .any(|kv| matches!(first_key(&kv).as_deref(), Some("options") | Some("config")));
+            for kv in module.entries() {
+                let (key, value) = match (kv.key(), kv.value()) {
+                    (Some(k), Some(v)) => (k, v),
+                    _ => continue,
+                };
+                let path = match key_path(key.node()) {
+                    Some(p) => p,
+                    None => continue,
+                };
+                let at = key.node().text_range();
+                match path.split_first() {
+                    Some((first, rest)) if first == "options" => {
+                        options(idx, rest.to_vec(), at, &value, &mut declared)
+                    }
+                    Some((first, rest)) if first == "config" => {
+                        settings(idx, rest.to_vec(), at, &value, &mut set)
+                    }
+                    Some((first, _)) if !explicit && !MODULE_KEYS.contains(&first.as_str()) => {
+                        settings(idx, path, at, &value, &mut set)
+                    }
+                    _ => (),
+                }
+            }
+            reads(idx, &file.root, &mut read);
+        }
+
+        let mut reports = Vec::new();
+        let mut push = |file: usize, report: Report| match reports
+            .iter_mut()
+            .find(|r: &&mut ProjectReport| r.file == file)
+        {
+            Some(r) => r.report.diagnostics.extend(report.diagnostics),
+            None => reports.push(ProjectReport { file, report }),
+        };
+
+        // set, not declared, but next to declared options
+        for site in &set {
+            let (parent, name) = site.path.split_at(site.path.len() - 1);
+            let siblings = declared
+                .iter()
+                .filter(|d| d.path.len() == site.path.len() && d.path.starts_with(parent))
+                .collect::<Vec<_>>();
+            let is_declared = declared
+                .iter()
+                .any(|d| site.path.starts_with(&d.path) || d.path.starts_with(&site.path));
+            if siblings.is_empty() || is_declared {
+                continue;
+            }
+            let option = site.path.join(".");
+            let closest = siblings
+                .iter()
+                .map(|d| (edit_distance(&name[0], d.path.last().unwrap()), d))
+                .filter(|(d, _)| *d <= 2)
+                .min_by_key(|(d, _)| *d);
+            match closest {
+                // the declaration is pointed at when it is in the same file,
+                // and its file is named otherwise
+                Some((_, closest)) if closest.file == site.file => {
+                    let suggestion = closest.path.join(".");
+                    let message = format!(
+                        "`{}` is not declared, did you mean `{}`?",
+                        option, suggestion
+                    );
+                    let declared = format!("`{}` is declared here", suggestion);
+                    let report = self
+                        .report()
+                        .diagnostic(site.at, message)
+                        .related(closest.at, declared);
+                    push(site.file, report);
+                }
+                Some((_, closest)) => {
+                    let files = project.files();
+                    let message = format!(
+                        "`{}` is not declared, did you mean `{}`, declared in `{}`?",
+                        option,
+                        closest.path.join("."),
+                        relative_path(&files[site.file].path, &files[closest.file].path)
+                    );
+                    push(site.file, self.report().diagnostic(site.at, message));
+                }
+                None => {
+                    let message = format!("`{}` is not declared", option);
+                    push(site.file, self.report().diagnostic(site.at, message));
+                }
+            }
+        }
+
+        // declared, never read
+        for site in &declared {
+            let is_read = read
+                .iter()
+                .any(|r| site.path.starts_with(&r.path) || r.path.starts_with(&site.path));
+            if !is_read {
+                let message = format!("`{}` is never read", site.path.join("."));
+                push(site.file, self.report().diagnostic(site.at, message));
+            }
+        }
+
+        reports
+    }
+}
+
+// the attribute set of a module, past its arguments and `let`s
+fn module_body(expr: SyntaxNode) -> Option<AttrSet> {
+    let mut expr = expr;
+    loop {
+        expr = if let Some(lambda) = Lambda::cast(expr.clone()) {
+            lambda.body()?
+        } else if let Some(let_in) = LetIn::cast(expr.clone()) {
+            let_in.body()?
+        } else {
+            return AttrSet::cast(expr);
+        }
+    }
+}
+
+// `to` as a path relative to the directory of `from`, when it is in there
+fn relative_path(from: &Path, to: &Path) -> String {
+    let dir = from.parent().unwrap_or_else(|| Path::new(""));
+    match to.strip_prefix(dir) {
+        Ok(relative) => format!("./{}", relative.display()),
+        Err(_) => to.display().to_string(),
+    }
+}
+
+fn key_path(key: &SyntaxNode) -> Option<Vec<String>> {
+    key.children().map(|p| attr_name(&p)).collect()
+}
+
+fn nested(
+    file: usize,
+    path: Vec<String>,
+    value: &AttrSet,
+    out: &mut Vec<Site>,
+    each: fn(usize, Vec<String>, TextRange, &SyntaxNode, &mut Vec<Site>),
+) {
+    for kv in value.entries() {
+        let (key, value) = match (kv.key(), kv.value()) {
+            (Some(k), Some(v)) => (k, v),
+            _ => continue,
+        };
+        if let Some(rest) = key_path(key.node()) {
+            let path = path.iter().cloned().chain(rest).collect();
+            each(file, path, key.node().text_range(), &value, out);
+        }
+    }
+}
+
+fn options(file: usize, path: Vec<String>, at: TextRange, value: &SyntaxNode, out: &mut Vec<Site>) {
+    let value = unparenthesized(value);
+    if let Some(set) = AttrSet::cast(value.clone()) {
+        nested(file, path, &set, out, options);
+    } else if let Some(name) = function_name(&call(&value).0) {
+        if DECLARE.contains(&name.as_str()) && !path.is_empty() {
+            out.push(Site { file, path, at });
+        }
+    }
+}
+
+fn settings(
+    file: usize,
+    path: Vec<String>,
+    at: TextRange,
+    value: &SyntaxNode,
+    out: &mut Vec<Site>,
+) {
+    let value = unparenthesized(value);
+    if let Some(set) = AttrSet::cast(value.clone()) {
+        return nested(file, path, &set, out, settings);
+    }
+    let (function, args) = call(&value);
+    match (function_name(&function).as_deref(), args.as_slice()) {
+        (Some("mkIf"), [_, body]) => settings(file, path, at, body, out),
+        (Some("mkMerge"), [list]) => {
+            for item in List::cast(unparenthesized(list))
+                .iter()
+                .flat_map(|l| l.items())
+            {
+                settings(file, path.clone(), at, &item, out);
+            }
+        }
+        _ if !path.is_empty() => out.push(Site { file, path, at }),
+        _ => (),
+    }
+}
+
+// `config.a.b` anywhere in a module, read as the option `a.b`
+fn reads(file: usize, root: &SyntaxNode, out: &mut Vec<Site>) {
+    for node in root.descendants() {
+        let select = match Select::cast(node.clone()) {
+            Some(s) => s,
+            None => continue,
+        };
+        // only the outermost select of `config.a.b`
+        if node.parent().and_then(Select::cast).is_some() {
+            continue;
+        }
+        let mut path = Vec::new();
+        let mut set = Some(select);
+        let mut base = None;
+        while let Some(s) = set {
+            match s.index().as_ref().and_then(attr_name) {
+                Some(name) => path.push(name),
+                None => path.clear(),
+            }
+            base = s.set();
+            set = base.clone().and_then(Select::cast);
+        }
+        let is_config = base
+            .filter(is_reference)
+            .and_then(Ident::cast)
+            .is_some_and(|i| i.as_str() == "config");
+        if is_config {
+            path.reverse();
+            let at = node.text_range();
+            out.push(Site { file, path, at });
+        }
+    }
+}
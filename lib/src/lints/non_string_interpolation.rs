@@ -0,0 +1,87 @@
+use crate::{
+    make,
+    session::SessionInfo,
+    utils::{bool_literal, unparenthesized},
+    Metadata, Report, Rule, Severity, Suggestion,
+};
+
+use macros::lint;
+use rnix::{types::TypedNode, NodeOrToken, SyntaxElement, SyntaxKind};
+
+/// ## What it does
+/// Checks for interpolations of integers, floats, booleans and lists
+/// written out, such as `"${8080}"`, and converts numbers with
+/// `toString` and booleans with `builtins.toJSON`.
+///
+/// ## Why is this bad?
+/// Interpolations only take strings, paths, and sets that turn into
+/// strings, such as derivations: anything else fails evaluation with
+/// `cannot coerce an integer to a string`. `toString` converts the rest,
+/// but not always as expected: `toString true` is `"1"`, `toString
+/// false` is `""`, and `toString [ "a" [ "b" ] ]` joins the items with
+/// spaces, as `"a b"`. `builtins.toJSON` gives `"true"` and `"false"`,
+/// and writes lists as JSON.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   listen = "127.0.0.1:${8080}";
+/// }
+/// ```
+///
+/// Convert the number:
+///
+/// ```nix
+/// {
+///   listen = "127.0.0.1:${toString 8080}";
+/// }
+/// ```
+#[lint(
+    name = "non_string_interpolation",
+    note = "Found a value interpolated into a string that is not a string",
+    code = 85,
+    severity = Severity::Error,
+    match_with = SyntaxKind::NODE_STRING_INTERPOL
+)]
+struct NonStringInterpolation;
+
+impl Rule for NonStringInterpolation {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let value = node.first_child()?;
+        let inner = unparenthesized(&value);
+        let at = node.text_range();
+        if inner.kind() == SyntaxKind::NODE_LIST {
+            let message = "Lists cannot be interpolated, use `toString` to join the items with spaces, or `builtins.toJSON` for JSON";
+            return Some(self.report().diagnostic(at, message));
+        }
+        let (message, function) = if let Some(b) = bool_literal(&inner) {
+            let message = format!(
+                "Booleans cannot be interpolated, use `builtins.toJSON {0}` for `\"{0}\"`, as `toString {0}` is `\"{1}\"`",
+                b,
+                if b { "1" } else { "" }
+            );
+            let to_json =
+                make::select(make::ident("builtins").node(), make::ident("toJSON").node());
+            (message, to_json.node().clone())
+        } else {
+            let kind = match inner.first_token()?.kind() {
+                _ if inner.kind() != SyntaxKind::NODE_LITERAL => return None,
+                SyntaxKind::TOKEN_INTEGER => "Integers",
+                SyntaxKind::TOKEN_FLOAT => "Floats",
+                _ => return None,
+            };
+            let message = format!("{} cannot be interpolated, use `toString {}`", kind, inner);
+            (message, make::ident("toString").node().clone())
+        };
+        // `${ 8080 }` keeps its spaces
+        let replacement = make::apply(&function, &value);
+        let fix = Suggestion::new(value.text_range(), replacement.node().clone())
+            .description(format!("convert with `{}`", function));
+        Some(self.report().suggest(at, message, fix))
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{
+    make,
+    session::{SessionInfo, Since, Version},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, Ident, Select, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// The nixpkgs release from which option descriptions are Markdown by default
+const MARKDOWN_SINCE: Version = Version::new(23, 11, None);
+
+/// ## What it does
+/// Checks for `lib.mdDoc`, which marks option descriptions as Markdown.
+///
+/// ## Why is this bad?
+/// Option descriptions are Markdown by default since nixpkgs 23.11,
+/// where `lib.mdDoc` does nothing, and it is deprecated since.
+///
+/// ## Example
+///
+/// ```nix
+/// lib.mkOption {
+///   description = lib.mdDoc "Whether to greet the `world`.";
+/// }
+/// ```
+///
+/// Use the string:
+///
+/// ```nix
+/// lib.mkOption {
+///   description = "Whether to greet the `world`.";
+/// }
+/// ```
+#[lint(
+    name = "obsolete_md_doc",
+    note = "Found an unneeded `mdDoc`",
+    code = 57,
+    match_with = SyntaxKind::NODE_APPLY
+)]
+struct ObsoleteMdDoc;
+
+impl Rule for ObsoleteMdDoc {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        if !is_md_doc(&apply.lambda()?) {
+            return None;
+        }
+        let description = apply.value()?;
+        let at = node.text_range();
+        let message = "Descriptions are Markdown by default, `mdDoc` does nothing";
+        let replacement = make::replacing(&description, node);
+        let fix = Suggestion::new(at, replacement).description("remove `mdDoc`");
+        Some(self.report().suggest_newest(
+            at,
+            message,
+            sess,
+            vec![(Since::Nixpkgs(MARKDOWN_SINCE), fix)],
+        ))
+    }
+}
+
+// `lib.mdDoc`, and `mdDoc` brought in scope
+fn is_md_doc(function: &SyntaxNode) -> bool {
+    match Select::cast(function.clone()) {
+        Some(select) => matches!(select.index(), Some(index) if index.text() == "mdDoc"),
+        None => matches!(Ident::cast(function.clone()), Some(i) if i.as_str() == "mdDoc"),
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    make, scope,
+    session::SessionInfo,
+    utils::{call, function_name},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{IfElse, List, Select, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for `if` expressions with an empty list in one branch and a
+/// list in the other, and replaces them with `lib.optional` or
+/// `lib.optionals` when `lib` is in scope. Also checks for
+/// `lib.optional` given a list, which gives a list holding that list.
+///
+/// ## Why is this bad?
+/// `lib.optional` and `lib.optionals` say what the expression does,
+/// without a branch to read for the empty case. `lib.optional` of a
+/// list is usually meant to be `lib.optionals`.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   buildInputs = if withGui then [ gtk3 ] else [ ];
+///   nativeBuildInputs = lib.optional withDocs [ sphinx ];
+/// }
+/// ```
+///
+/// Use `lib.optional` and `lib.optionals`:
+///
+/// ```nix
+/// {
+///   buildInputs = lib.optional withGui gtk3;
+///   nativeBuildInputs = lib.optionals withDocs [ sphinx ];
+/// }
+/// ```
+#[lint(
+    name = "optional_list",
+    note = "Found a conditional list",
+    code = 70,
+    match_with = [SyntaxKind::NODE_IF_ELSE, SyntaxKind::NODE_APPLY]
+)]
+struct OptionalList;
+
+impl Rule for OptionalList {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        match node.kind() {
+            SyntaxKind::NODE_IF_ELSE => self.if_else(node),
+            _ => self.nested_list(node),
+        }
+    }
+}
+
+impl OptionalList {
+    // `if c then [ x ] else [ ]`
+    fn if_else(&self, node: &SyntaxNode) -> Option<Report> {
+        let if_else = IfElse::cast(node.clone())?;
+        let (condition, body, else_body) =
+            (if_else.condition()?, if_else.body()?, if_else.else_body()?);
+        let (body, else_body) = (List::cast(body)?, List::cast(else_body)?);
+        let (condition, list) = match (is_empty(&body), is_empty(&else_body)) {
+            (false, true) => (condition, body),
+            (true, false) => (make::negation(&condition).node().clone(), else_body),
+            _ => return None,
+        };
+        let elements = list.items().collect::<Vec<_>>();
+        let (function, argument) = match elements.as_slice() {
+            [element] => ("optional", element.clone()),
+            _ => ("optionals", list.node().clone()),
+        };
+        let at = node.text_range();
+        let message = format!("Use `lib.{}` instead", function);
+        let lib = scope::lookup(node, "lib").is_some();
+        if !lib {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let description = format!("replace with `lib.{}`", function);
+        let function = make::select(make::ident("lib").node(), make::ident(function).node());
+        let call = make::apply(make::apply(function.node(), &condition).node(), &argument);
+        let replacement = make::replacing(call.node(), node);
+        let fix = Suggestion::new(at, replacement).description(description);
+        Some(self.report().suggest(at, message, fix))
+    }
+
+    // `lib.optional c [ x ]`
+    fn nested_list(&self, node: &SyntaxNode) -> Option<Report> {
+        let (function, args) = call(node);
+        if function_name(&function)? != "optional"
+            || args.len() != 2
+            || args[1].kind() != SyntaxKind::NODE_LIST
+        {
+            return None;
+        }
+        let at = node.text_range();
+        let message = "This is a list holding a list, use `optionals` for its elements";
+        let index = match Select::cast(function.clone()) {
+            Some(select) => select.index()?,
+            None => function,
+        };
+        let fix = Suggestion::new(index.text_range(), make::ident("optionals").node().clone())
+            .description("replace with `optionals`");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+fn is_empty(list: &List) -> bool {
+    list.items().next().is_none()
+}
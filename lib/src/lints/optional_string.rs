@@ -0,0 +1,79 @@
+use crate::{
+    make, scope,
+    session::{Mode, SessionInfo},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{IfElse, Str, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for `if` expressions with an empty string in one branch and
+/// a string in the other, and replaces them with `lib.optionalString`
+/// when `lib` is in scope.
+///
+/// ## Why is this bad?
+/// `lib.optionalString` says what the expression does, without a
+/// branch to read for the empty case.
+///
+/// ## Example
+///
+/// ```nix
+/// "make ${if withDocs then "docs" else ""}"
+/// ```
+///
+/// Use `lib.optionalString`:
+///
+/// ```nix
+/// "make ${lib.optionalString withDocs "docs"}"
+/// ```
+#[lint(
+    name = "optional_string",
+    note = "Found an `if` with an empty string in a branch",
+    code = 69,
+    match_with = SyntaxKind::NODE_IF_ELSE,
+    modes = [Mode::Nixpkgs]
+)]
+struct OptionalString;
+
+impl Rule for OptionalString {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let if_else = IfElse::cast(node.clone())?;
+        let (condition, body, else_body) =
+            (if_else.condition()?, if_else.body()?, if_else.else_body()?);
+        if body.kind() != SyntaxKind::NODE_STRING || else_body.kind() != SyntaxKind::NODE_STRING {
+            return None;
+        }
+        let (condition, string) = match (is_empty(&body), is_empty(&else_body)) {
+            (false, true) => (condition, body),
+            (true, false) => (make::negation(&condition).node().clone(), else_body),
+            _ => return None,
+        };
+        let at = node.text_range();
+        let message = "Use `lib.optionalString` instead";
+        let lib = scope::lookup(node, "lib").is_some();
+        if !lib {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let function = make::select(
+            make::ident("lib").node(),
+            make::ident("optionalString").node(),
+        );
+        let call = make::apply(make::apply(function.node(), &condition).node(), &string);
+        let replacement = make::replacing(call.node(), node);
+        let fix = Suggestion::new(at, replacement).description("replace with `lib.optionalString`");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// `""` and `''''`
+fn is_empty(node: &SyntaxNode) -> bool {
+    matches!(Str::cast(node.clone()), Some(s) if s.parts().is_empty())
+}
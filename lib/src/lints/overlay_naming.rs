@@ -0,0 +1,142 @@
+use crate::{
+    make,
+    scope::Scopes,
+    session::SessionInfo,
+    utils::{attr_name, function_name, is_reference},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, KeyValue, Lambda, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// Functions taking an overlay
+const OVERLAY_FUNCTIONS: &[&str] = &["overrideScope", "overrideScope'", "extend", "extends"];
+
+/// ## What it does
+/// Checks for overlays with arguments named `self` and `super`, and
+/// renames them to `final` and `prev`, everywhere they are used. A
+/// function is taken for an overlay when it is in an `overlays` list
+/// or attribute, or is passed to `overrideScope` or `extend`.
+///
+/// ## Why is this bad?
+/// `final` and `prev` are the names used in nixpkgs and in the manual,
+/// and they say what each set is: the package set after and before the
+/// overlay is applied.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   nixpkgs.overlays = [
+///     (self: super: {
+///       hello = super.hello.override { inherit (self) stdenv; };
+///     })
+///   ];
+/// }
+/// ```
+///
+/// Rename the arguments:
+///
+/// ```nix
+/// {
+///   nixpkgs.overlays = [
+///     (final: prev: {
+///       hello = prev.hello.override { inherit (final) stdenv; };
+///     })
+///   ];
+/// }
+/// ```
+#[lint(
+    name = "overlay_naming",
+    note = "Found an overlay with arguments named `self` and `super`",
+    code = 63,
+    match_with = SyntaxKind::NODE_LAMBDA
+)]
+struct OverlayNaming;
+
+impl Rule for OverlayNaming {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let outer = Lambda::cast(node.clone())?;
+        let inner = Lambda::cast(outer.body()?)?;
+        let (self_arg, super_arg) = (outer.arg()?, inner.arg()?);
+        if self_arg.kind() != SyntaxKind::NODE_IDENT
+            || self_arg.text() != "self"
+            || super_arg.kind() != SyntaxKind::NODE_IDENT
+            || super_arg.text() != "super"
+            || !is_overlay(node)
+        {
+            return None;
+        }
+        let at = self_arg.text_range().cover(super_arg.text_range());
+        let message = "Name the arguments `final` and `prev`";
+        let scopes = Scopes::new(node);
+        let body = inner.body()?;
+        // `final` and `prev` may not be taken, and `inherit self;` would
+        // rename the attribute too
+        let conflict = body.descendants().any(|n| {
+            n.kind() == SyntaxKind::NODE_IDENT
+                && is_reference(&n)
+                && (n.text() == "final" || n.text() == "prev")
+        });
+        let mut edits = vec![
+            (self_arg.text_range(), "final".to_owned()),
+            (super_arg.text_range(), "prev".to_owned()),
+        ];
+        let mut inherited = false;
+        for (idx, binding) in scopes.bindings.iter().enumerate() {
+            let renamed = match binding.ident {
+                ref ident if *ident == self_arg => "final",
+                ref ident if *ident == super_arg => "prev",
+                _ => continue,
+            };
+            for ident in scopes.uses(idx) {
+                inherited |= ident.parent().map(|p| p.kind()) == Some(SyntaxKind::NODE_INHERIT);
+                edits.push((ident.text_range(), renamed.to_owned()));
+            }
+        }
+        if conflict || inherited {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let replacement = make::edited(&outer, &edits);
+        let fix = Suggestion::new(node.text_range(), replacement.node().clone())
+            .description("rename to `final` and `prev`");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// whether `lambda` is an element of an `overlays` list, the value of an
+// `overlays` attribute, or the argument of a function taking an overlay
+fn is_overlay(lambda: &SyntaxNode) -> bool {
+    let mut node = lambda.clone();
+    while let Some(parent) = node.parent().filter(|p| p.kind() == SyntaxKind::NODE_PAREN) {
+        node = parent;
+    }
+    let parent = match node.parent() {
+        Some(parent) => parent,
+        None => return false,
+    };
+    let is_overlays_key = |kv: &SyntaxNode| {
+        matches!(
+            KeyValue::cast(kv.clone()).and_then(|kv| kv.key()),
+            Some(key) if key
+                .path()
+                .any(|n| matches!(attr_name(&n).as_deref(), Some("overlays" | "overlay")))
+        )
+    };
+    match parent.kind() {
+        SyntaxKind::NODE_LIST => matches!(parent.parent(), Some(kv) if is_overlays_key(&kv)),
+        SyntaxKind::NODE_KEY_VALUE => is_overlays_key(&parent),
+        SyntaxKind::NODE_APPLY => matches!(
+            Apply::cast(parent).and_then(|apply| function_name(&apply.lambda()?)),
+            Some(name) if OVERLAY_FUNCTIONS.contains(&name.as_str())
+        ),
+        _ => false,
+    }
+}
@@ -0,0 +1,142 @@
+use crate::{
+    make, scope,
+    session::{SessionInfo, Since, Version},
+    utils::string_literal,
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{BinOp, BinOpKind, ParsedType, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange, TextSize,
+};
+use std::convert::TryFrom;
+
+/// The nixpkgs release `lib.path.append` is available since
+const APPEND_SINCE: Version = Version::new(23, 5, None);
+
+/// ## What it does
+/// Checks for strings appended to path literals with `+`, such as
+/// `./dir + "/file"`. A constant subpath is written into the path
+/// literal, others are appended with `lib.path.append` where the
+/// targeted nixpkgs has it.
+///
+/// ## Why is this bad?
+/// The result is normalized as a path: a trailing `/` is dropped, `..`
+/// leaves the directory, and a string without a leading `/` is appended
+/// to the name of the last component rather than below it.
+/// `lib.path.append` checks that it is given a relative subpath.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   src = ./. + "/src";
+///   config = ./config + "/${name}.nix";
+/// }
+/// ```
+///
+/// Use a path literal, or `lib.path.append`:
+///
+/// ```nix
+/// {
+///   src = ./src;
+///   config = lib.path.append ./config "${name}.nix";
+/// }
+/// ```
+#[lint(
+    name = "path_concatenation",
+    note = "Found a string appended to a path",
+    code = 76,
+    match_with = SyntaxKind::NODE_BIN_OP
+)]
+struct PathConcatenation;
+
+impl Rule for PathConcatenation {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let op = BinOp::cast(node.clone())?;
+        if op.operator() != Some(BinOpKind::Add) {
+            return None;
+        }
+        let (path, string) = (op.lhs()?, op.rhs()?);
+        let path_text = path_literal(&path)?;
+        if string.kind() != SyntaxKind::NODE_STRING {
+            return None;
+        }
+        let at = node.text_range();
+        let starts_with_slash = string
+            .first_child_or_token()
+            .and_then(|start| start.next_sibling_or_token()?.into_token())
+            .filter(|t| t.kind() == SyntaxKind::TOKEN_STRING_CONTENT);
+        let starts_with_slash = matches!(starts_with_slash, Some(t) if t.text().starts_with('/'));
+        if !starts_with_slash {
+            let message = "Without a leading `/`, the string is appended to the name of the last component of the path";
+            return Some(self.report().diagnostic(at, message));
+        }
+
+        // `./dir/file`
+        if let Some(subpath) = string_literal(&string).filter(|s| is_path_literal(s)) {
+            // `./.` is the directory itself
+            let base = path_text
+                .strip_suffix('.')
+                .filter(|b| b.ends_with('/'))
+                .unwrap_or(&path_text);
+            let joined = format!("{}{}", base.trim_end_matches('/'), subpath);
+            let message = format!("Write the path `{}` directly", joined);
+            let fix = Suggestion::new(at, make::path(&joined).node().clone())
+                .description(format!("replace with `{}`", joined));
+            return Some(self.report().suggest(at, message, fix));
+        }
+
+        // `lib.path.append ./dir "file"`
+        let message = "Use `lib.path.append` to append a subpath";
+        let has_lib = scope::lookup(node, "lib").is_some();
+        if !has_lib {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let slash = string.first_token()?.text_range().end();
+        let subpath = make::edited(
+            &ParsedType::try_from(string).ok()?,
+            &[(TextRange::at(slash, TextSize::from(1)), String::new())],
+        );
+        let append = make::select(
+            make::select(make::ident("lib").node(), make::ident("path").node()).node(),
+            make::ident("append").node(),
+        );
+        let replacement = make::apply(make::apply(append.node(), &path).node(), subpath.node());
+        let fix =
+            Suggestion::new(at, replacement.node().clone()).description("use `lib.path.append`");
+        Some(self.report().suggest_newest(
+            at,
+            message,
+            sess,
+            vec![(Since::Nixpkgs(APPEND_SINCE), fix)],
+        ))
+    }
+}
+
+// The text of a path literal, other than a lookup path such as `<nixpkgs>`
+fn path_literal(node: &SyntaxNode) -> Option<String> {
+    if node.kind() != SyntaxKind::NODE_LITERAL {
+        return None;
+    }
+    let token = node.first_token()?;
+    if token.kind() != SyntaxKind::TOKEN_PATH || token.text().starts_with('<') {
+        return None;
+    }
+    Some(token.text().to_string())
+}
+
+// Whether `/sub/path` can be written as part of a path literal
+fn is_path_literal(subpath: &str) -> bool {
+    subpath.len() > 1
+        && !subpath.ends_with('/')
+        && !subpath.contains("//")
+        && subpath
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '/' | '.' | '_' | '-' | '+'))
+}
@@ -0,0 +1,122 @@
+use crate::{
+    session::SessionInfo,
+    utils::{expects_path, lone_interpolation},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, Ident, Select, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for path literals turned into strings with `toString ./foo`
+/// or `"${./foo}"`.
+///
+/// ## Why is this bad?
+/// The two are easily confused: `"${./foo}"` copies `./foo` to the
+/// store and gives the store path, while `toString ./foo` gives the
+/// absolute path of `./foo` on the machine evaluating the expression,
+/// which is impure and not available in builds. Where a path is
+/// expected, such as `src` and `imports`, the path itself is
+/// suggested.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   src = toString ./src;
+/// }
+/// ```
+///
+/// Use the path:
+///
+/// ```nix
+/// {
+///   src = ./src;
+/// }
+/// ```
+#[lint(
+    name = "path_to_string",
+    note = "Found a path converted to a string",
+    code = 45,
+    match_with = [SyntaxKind::NODE_APPLY, SyntaxKind::NODE_STRING]
+)]
+struct PathToString;
+
+impl Rule for PathToString {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let expects_path = expects_path(node);
+        let (path, message) = if let Some(apply) = Apply::cast(node.clone()) {
+            let path = path_literal(apply.value()?)?;
+            if !is_to_string(&apply.lambda()?) {
+                return None;
+            }
+            let message = if expects_path {
+                format!(
+                    "`toString` gives where `{}` is on this machine, use the path instead",
+                    path
+                )
+            } else {
+                format!(
+                    "This is where `{}` is on this machine, and is not copied to the store, try `\"${{{}}}\"` for a store path",
+                    path, path
+                )
+            };
+            (path, message)
+        } else {
+            let path = path_literal(lone_interpolation(node)?)?;
+            let message = if expects_path {
+                format!(
+                    "This copies `{}` to the store as a string, use the path instead",
+                    path
+                )
+            } else {
+                format!(
+                    "This copies `{}` to the store, try `{}` to keep a path, or `toString {}` for where it is on this machine",
+                    path, path, path
+                )
+            };
+            (path, message)
+        };
+        let at = node.text_range();
+        if expects_path {
+            let description = format!("replace with `{}`", path);
+            let fix = Suggestion::new(at, path).description(description);
+            Some(self.report().suggest(at, message, fix))
+        } else {
+            Some(self.report().diagnostic(at, message))
+        }
+    }
+}
+
+// `./foo` and `./foo/${bar}`, lookup paths such as `<nixpkgs>` are left
+// alone
+fn path_literal(node: SyntaxNode) -> Option<SyntaxNode> {
+    let token = node.first_token()?;
+    let is_path = matches!(
+        node.kind(),
+        SyntaxKind::NODE_LITERAL | SyntaxKind::NODE_PATH_WITH_INTERPOL
+    ) && token.kind() == SyntaxKind::TOKEN_PATH;
+    if is_path && !token.text().starts_with('<') {
+        Some(node)
+    } else {
+        None
+    }
+}
+
+// `toString` and `builtins.toString`
+fn is_to_string(function: &SyntaxNode) -> bool {
+    match Select::cast(function.clone()) {
+        Some(select) => {
+            matches!(select.set(), Some(set) if set.text() == "builtins")
+                && matches!(select.index(), Some(index) if index.text() == "toString")
+        }
+        None => matches!(Ident::cast(function.clone()), Some(i) if i.as_str() == "toString"),
+    }
+}
@@ -0,0 +1,278 @@
+use crate::{
+    make,
+    session::{Mode, SessionInfo},
+    utils::{
+        attr_name, derivation_attrs, function_name, string_literal, with_preceeding_whitespace,
+        BUILDERS,
+    },
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, EntryHolder, Str, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, TextRange, TextSize,
+};
+
+/// Phases, and the name of their hooks, as in `preInstall`
+const PHASES: &[(&str, &str)] = &[
+    ("unpackPhase", "Unpack"),
+    ("patchPhase", "Patch"),
+    ("configurePhase", "Configure"),
+    ("buildPhase", "Build"),
+    ("checkPhase", "Check"),
+    ("installPhase", "Install"),
+    ("fixupPhase", "Fixup"),
+    ("installCheckPhase", "InstallCheck"),
+    ("distPhase", "Dist"),
+];
+
+/// What the phases of `stdenv.mkDerivation` run, for a phase written
+/// out with the same command
+const DEFAULTS: &[(&str, &str)] = &[
+    ("configurePhase", "./configure --prefix=$out"),
+    ("buildPhase", "make"),
+    ("checkPhase", "make check"),
+    ("installPhase", "make install"),
+];
+
+/// ## What it does
+/// Checks derivations for phases that are overridden only to add
+/// commands to what the phase does anyway, which a `pre` or `post` hook
+/// such as `postPatch` does, and for phases that do not run their hooks
+/// with `runHook`.
+///
+/// ## Why is this bad?
+/// An overridden phase replaces everything the phase does: a
+/// `patchPhase` no longer applies `patches`, and an `installPhase`
+/// written out as `make install` no longer passes `installFlags` when
+/// they are added later. Overrides of the derivation, and setup hooks
+/// of its inputs, add to the `pre` and `post` hooks, which are skipped
+/// when the phase does not call `runHook`.
+///
+/// ## Example
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   patchPhase = ''
+///     runHook prePatch
+///     substituteInPlace Makefile --replace-fail gcc cc
+///     runHook postPatch
+///   '';
+/// }
+/// ```
+///
+/// Add to the phase with a hook:
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   postPatch = ''
+///     substituteInPlace Makefile --replace-fail gcc cc
+///   '';
+/// }
+/// ```
+#[lint(
+    name = "phase_override",
+    note = "Found a build phase overridden instead of extended",
+    code = 84,
+    match_with = SyntaxKind::NODE_APPLY,
+    modes = [Mode::Nixpkgs]
+)]
+struct PhaseOverride;
+
+impl Rule for PhaseOverride {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        let builder = function_name(&apply.lambda()?)?;
+        if !BUILDERS.contains(&builder.as_str()) {
+            return None;
+        }
+        let set = derivation_attrs(&apply.value()?)?;
+        let names = set
+            .entries()
+            .filter_map(|kv| attr_name(&kv.key()?.path().next()?))
+            .collect::<Vec<_>>();
+        let mut report = self.report();
+        for kv in set.entries() {
+            let key = match kv.key() {
+                Some(key) if key.path().count() == 1 => key,
+                _ => continue,
+            };
+            let phase = match attr_name(&key.path().next()?) {
+                Some(phase) => phase,
+                None => continue,
+            };
+            let hook = match PHASES.iter().find(|(p, _)| *p == phase) {
+                Some((_, hook)) => *hook,
+                None => continue,
+            };
+            let body = match kv.value().and_then(Str::cast) {
+                Some(body) => body,
+                None => continue,
+            };
+            // `installPhase = "true";` to skip the phase
+            if matches!(
+                string_literal(body.node()).as_deref().map(str::trim),
+                Some("" | "true" | ":")
+            ) {
+                continue;
+            }
+            let at = key.node().text_range();
+            let pre = format!("runHook pre{}", hook);
+            let post = format!("runHook post{}", hook);
+            let text = body.node().to_string();
+            let missing = [&pre, &post]
+                .iter()
+                .filter(|call| !text.contains(call.as_str()))
+                .map(|call| format!("`{}`", call))
+                .collect::<Vec<_>>();
+            if !missing.is_empty() {
+                let message = format!(
+                    "`{}` does not call {}, so hooks added to it are skipped",
+                    phase,
+                    missing.join(" and ")
+                );
+                report = match with_run_hooks(&body, &pre, &post, sess) {
+                    Some(fixed) => {
+                        let fix = Suggestion::new(body.node().text_range(), fixed.node().clone())
+                            .description("add `runHook`");
+                        report.suggest(at, message, fix)
+                    }
+                    None => report.diagnostic(at, message),
+                };
+                continue;
+            }
+            let lines = match content_lines(&body) {
+                Some(lines) => lines,
+                None => continue,
+            };
+            let commands = lines
+                .iter()
+                .enumerate()
+                .filter(|(_, (_, line))| !line.trim().is_empty())
+                .map(|(i, (_, line))| (i, line.trim()))
+                .collect::<Vec<_>>();
+            let middle = match commands.as_slice() {
+                [(_, first), middle @ .., (_, last)] if *first == pre && *last == post => middle,
+                _ => continue,
+            };
+            // the default `patchPhase` only applies `patches`
+            let default = match DEFAULTS.iter().find(|(p, _)| *p == phase) {
+                Some((_, command)) if builder == "mkDerivation" => Some(*command),
+                _ if phase == "patchPhase" && !names.iter().any(|n| n == "patches") => None,
+                _ => continue,
+            };
+            let redundant = match (middle, default) {
+                ([], None) => true,
+                ([(_, only)], Some(command)) => *only == command,
+                _ => false,
+            };
+            if redundant {
+                let message = format!("`{}` does what the phase does anyway, remove it", phase);
+                let fix = Suggestion::new(
+                    with_preceeding_whitespace(kv.node()),
+                    make::empty().node().clone(),
+                )
+                .description(format!("remove `{}`", phase));
+                report = report.suggest(at, message, fix);
+                continue;
+            }
+            let (added, extended) = match (middle, default) {
+                ([], _) => continue,
+                (added, None) => (added, format!("post{}", hook)),
+                ([(_, first), added @ ..], Some(command)) if *first == command => {
+                    (added, format!("post{}", hook))
+                }
+                ([added @ .., (_, last)], Some(command)) if *last == command => {
+                    (added, format!("pre{}", hook))
+                }
+                _ => continue,
+            };
+            let message = format!(
+                "`{}` only adds to what the phase does anyway, use `{}` instead",
+                phase, extended
+            );
+            if names.contains(&extended) {
+                report = report.diagnostic(at, message);
+                continue;
+            }
+            // keep the lines from the first to the last command added
+            let (first, last) = (added.first()?.0, added.last()?.0);
+            let edits = [
+                (
+                    TextRange::new(lines.first()?.0.start(), lines[first].0.start()),
+                    String::new(),
+                ),
+                (
+                    TextRange::new(lines[last].0.end(), lines.last()?.0.end()),
+                    String::new(),
+                ),
+            ];
+            let replacement = make::key_value(&extended, make::edited(&body, &edits).node());
+            let fix = Suggestion::new(kv.node().text_range(), replacement.node().clone())
+                .description(format!("move to `{}`", extended));
+            report = report.suggest(at, message, fix);
+        }
+        if report.diagnostics.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+
+// The lines between the quotes of a `''` string, with their newline
+fn content_lines(body: &Str) -> Option<Vec<(TextRange, String)>> {
+    let text = body.node().to_string();
+    let mut offset = body.node().text_range().start();
+    let mut lines = Vec::new();
+    for line in text.split_inclusive('\n') {
+        let len = TextSize::of(line);
+        lines.push((TextRange::at(offset, len), line.to_owned()));
+        offset += len;
+    }
+    // `''` and the closing `''`
+    match lines.as_slice() {
+        [(_, open), content @ .., (_, close)]
+            if open.trim() == "''" && close.trim() == "''" && !content.is_empty() =>
+        {
+            Some(content.to_vec())
+        }
+        _ => None,
+    }
+}
+
+// `body` with `runHook` around the commands, where missing
+fn with_run_hooks(body: &Str, pre: &str, post: &str, sess: &SessionInfo) -> Option<Str> {
+    let lines = content_lines(body)?;
+    let mut commands = lines.iter().filter(|(_, line)| !line.trim().is_empty());
+    let first = commands.next()?;
+    let last = commands.next_back().unwrap_or(first);
+    let eol = sess.style().eol();
+    let indent = first
+        .1
+        .chars()
+        .take_while(|c| *c == ' ' || *c == '\t')
+        .collect::<String>();
+    let text = body.node().to_string();
+    let mut edits = Vec::new();
+    if !text.contains(pre) {
+        edits.push((
+            TextRange::empty(first.0.start()),
+            format!("{}{}{}{}", indent, pre, eol, eol),
+        ));
+    }
+    if !text.contains(post) {
+        edits.push((
+            TextRange::empty(last.0.end()),
+            format!("{}{}{}{}", eol, indent, post, eol),
+        ));
+    }
+    Some(make::edited(body, &edits))
+}
@@ -0,0 +1,159 @@
+use std::fmt::Write;
+
+use crate::{
+    make,
+    scope::{BindingKind, Scopes},
+    session::SessionInfo,
+    utils::indentation,
+    Metadata, Report, Rule, Severity, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, AttrSet, KeyValue, Paren, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for `rec` attribute sets whose attributes refer to each
+/// other, and suggests binding the referenced attributes with `let`.
+/// Sets passed to a function, such as `stdenv.mkDerivation rec { ... }`,
+/// are left alone.
+///
+/// ## Why is this bad?
+/// In a `rec` set, every attribute is in scope of every other, which
+/// makes accidental infinite recursion easy, and helpers are exposed
+/// as attributes of the set. A `let` makes the dependencies explicit,
+/// and the `inherit` can be dropped for helpers that need not be
+/// exposed.
+///
+/// ## Example
+///
+/// ```nix
+/// rec {
+///   base = "https://example.org";
+///   api = "${base}/api";
+/// }
+/// ```
+///
+/// Bind the referenced attributes with `let`:
+///
+/// ```nix
+/// let
+///   base = "https://example.org";
+/// in
+/// {
+///   inherit base;
+///   api = "${base}/api";
+/// }
+/// ```
+#[lint(
+    name = "rec_instead_of_let",
+    note = "Found a `rec` set better written with `let`",
+    code = 38,
+    severity = Severity::Hint,
+    match_with = SyntaxKind::NODE_ATTR_SET
+)]
+struct RecInsteadOfLet;
+
+impl Rule for RecInsteadOfLet {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        if !AttrSet::cast(node.clone())?.recursive() || is_argument(node) {
+            return None;
+        }
+        let scopes = Scopes::new(node);
+        let referenced = scopes
+            .bindings_of(node)
+            .filter(|(_, b)| b.kind == BindingKind::Rec)
+            .filter(|(idx, _)| scopes.uses(*idx).next().is_some())
+            .map(|(_, b)| b)
+            .collect::<Vec<_>>();
+        // only plain `a = ...;` can be moved to the `let`
+        let hoistable = referenced.iter().all(|b| {
+            matches!(
+                KeyValue::cast(b.entry.clone()).and_then(|kv| kv.key()),
+                Some(key) if key.path().count() == 1
+            )
+        });
+        if referenced.is_empty() || !hoistable {
+            return None;
+        }
+
+        let rec = node.first_token()?;
+        let at = rec.text_range();
+        let names = referenced
+            .iter()
+            .map(|b| format!("`{}`", b.name))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let message = format!("Bind {} with `let` instead", names);
+        // comments among the attributes would be lost
+        let has_comments = node
+            .children_with_tokens()
+            .any(|c| c.kind() == SyntaxKind::TOKEN_COMMENT);
+        if has_comments {
+            return Some(self.report().diagnostic(at, message));
+        }
+
+        let style = sess.style();
+        let (unit, eol) = (style.indent_unit(), style.eol());
+        let base = indentation(node);
+        let mut text = format!("let{}", eol);
+        for binding in &referenced {
+            write!(text, "{}{}{}{}", base, unit, binding.entry, eol).unwrap();
+        }
+        write!(text, "{}in{}{}{{{}", base, eol, base, eol).unwrap();
+        let inherited = referenced
+            .iter()
+            .map(|b| b.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(text, "{}{}inherit {};{}", base, unit, inherited, eol).unwrap();
+        for entry in node.children() {
+            if !referenced.iter().any(|b| b.entry == entry) {
+                write!(text, "{}{}{}{}", base, unit, entry, eol).unwrap();
+            }
+        }
+        write!(text, "{}}}", base).unwrap();
+
+        let replacement = rnix::parse(&text).node().first_child()?;
+        let replacement = if needs_parens(node) {
+            make::parenthesize(&replacement).node().clone()
+        } else {
+            replacement
+        };
+        let fix = Suggestion::new(node.text_range(), replacement).description("rewrite with `let`");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// `f rec { ... }` and `f (rec { ... })`
+fn is_argument(set: &SyntaxNode) -> bool {
+    let mut node = set.clone();
+    while let Some(paren) = node.parent().and_then(Paren::cast) {
+        node = paren.node().clone();
+    }
+    matches!(
+        node.parent().and_then(Apply::cast).and_then(|a| a.value()),
+        Some(value) if value == node
+    )
+}
+
+// where `let` can not appear without parentheses
+fn needs_parens(set: &SyntaxNode) -> bool {
+    matches!(
+        set.parent().map(|p| p.kind()),
+        Some(
+            SyntaxKind::NODE_LIST
+                | SyntaxKind::NODE_APPLY
+                | SyntaxKind::NODE_SELECT
+                | SyntaxKind::NODE_BIN_OP
+                | SyntaxKind::NODE_UNARY_OP
+                | SyntaxKind::NODE_OR_DEFAULT
+        )
+    )
+}
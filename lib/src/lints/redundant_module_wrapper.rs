@@ -0,0 +1,129 @@
+use crate::{
+    make,
+    session::{Mode, SessionInfo},
+    utils::{bool_literal, call, function_name, unparenthesized},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, List, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind,
+};
+
+/// Functions setting the priority of a definition, with the number of
+/// arguments they take
+const PRIORITIES: &[(&str, usize)] = &[
+    ("mkOverride", 2),
+    ("mkOptionDefault", 1),
+    ("mkDefault", 1),
+    ("mkImageMediaOverride", 1),
+    ("mkForce", 1),
+    ("mkVMOverride", 1),
+];
+
+/// ## What it does
+/// Checks NixOS modules for wrappers of definitions that have no
+/// effect: a priority such as `mkForce` around another priority,
+/// `mkMerge` of a single definition, and `mkIf true`.
+///
+/// ## Why is this bad?
+/// Only the outermost priority of a definition is applied, a merge of a
+/// single definition is that definition, and a condition that is always
+/// true does not make the definition conditional. The wrappers suggest
+/// otherwise to readers.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   services.nginx.enable = lib.mkForce (lib.mkDefault true);
+///   environment.systemPackages = lib.mkMerge [ [ pkgs.git ] ];
+/// }
+/// ```
+///
+/// Remove the wrappers:
+///
+/// ```nix
+/// {
+///   services.nginx.enable = lib.mkForce true;
+///   environment.systemPackages = [ pkgs.git ];
+/// }
+/// ```
+#[lint(
+    name = "redundant_module_wrapper",
+    note = "Found a redundant wrapper of a definition",
+    code = 78,
+    match_with = SyntaxKind::NODE_APPLY,
+    modes = [Mode::NixosModule]
+)]
+struct RedundantModuleWrapper;
+
+impl Rule for RedundantModuleWrapper {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        // only the outermost application of `f a b`
+        if matches!(node.parent().and_then(Apply::cast), Some(p) if p.lambda().as_ref() == Some(node))
+        {
+            return None;
+        }
+        let (function, args) = call(node);
+        let name = function_name(&function)?;
+        let at = node.text_range();
+
+        if is_priority(&name, args.len()) {
+            // `mkForce (mkDefault x)`
+            let value = args.last()?;
+            let (inner_function, inner_args) = call(&unparenthesized(value));
+            let inner_name = function_name(&inner_function)?;
+            if !is_priority(&inner_name, inner_args.len()) {
+                return None;
+            }
+            let outer = Apply::cast(node.clone())?.lambda()?;
+            let replacement = make::apply(&outer, inner_args.last()?);
+            let message = if inner_name == name {
+                format!("`{}` is applied twice", name)
+            } else {
+                format!(
+                    "`{}` has no effect inside `{}`, only the outer priority applies",
+                    inner_name, name
+                )
+            };
+            let fix = Suggestion::new(at, make::replacing(replacement.node(), node))
+                .description(format!("remove the inner `{}`", inner_name));
+            return Some(self.report().suggest(at, message, fix));
+        }
+
+        let (message, replacement) = match (name.as_str(), args.as_slice()) {
+            // `mkMerge [ x ]`
+            ("mkMerge", [list]) => {
+                let list = List::cast(unparenthesized(list))?;
+                let mut items = list.items();
+                let item = items.next()?;
+                if items.next().is_some() {
+                    return None;
+                }
+                ("`mkMerge` of a single definition is that definition", item)
+            }
+            // `mkIf true x`
+            ("mkIf", [condition, value])
+                if bool_literal(&unparenthesized(condition)) == Some(true) =>
+            {
+                ("`mkIf true` always applies its definition", value.clone())
+            }
+            _ => return None,
+        };
+        let fix = Suggestion::new(at, make::replacing(&replacement, node))
+            .description(format!("remove `{}`", name));
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+fn is_priority(name: &str, args: usize) -> bool {
+    PRIORITIES
+        .iter()
+        .any(|&(n, arity)| n == name && arity == args)
+}
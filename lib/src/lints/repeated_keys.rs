@@ -1,15 +1,24 @@
-use crate::{session::SessionInfo, Metadata, Report, Rule};
+use crate::{
+    make, session::SessionInfo, utils::with_preceeding_whitespace, Metadata, Report, Rule,
+    Suggestion,
+};
 
 use if_chain::if_chain;
 use macros::lint;
 use rnix::{
     types::{AttrSet, EntryHolder, Ident, KeyValue, TokenWrapper, TypedNode},
-    NodeOrToken, SyntaxElement, SyntaxKind,
+    NodeOrToken, SyntaxElement, SyntaxKind, TextRange,
 };
 
 /// ## What it does
 /// Checks for keys in attribute sets with repetitive keys, and suggests using
-/// an attribute set instead.
+/// an attribute set instead. Keys repeated 3 times are reported, set another
+/// threshold in statix.toml:
+///
+/// ```toml
+/// [options.repeated_keys]
+/// threshold = 2
+/// ```
 ///
 /// ## Why is this bad?
 /// Avoiding repetetion helps improve readibility.
@@ -43,7 +52,7 @@ use rnix::{
 struct RepeatedKeys;
 
 impl Rule for RepeatedKeys {
-    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
         if_chain! {
             if let NodeOrToken::Node(node) = node;
             if let Some(key_value) = KeyValue::cast(node.clone());
@@ -65,7 +74,7 @@ impl Rule for RepeatedKeys {
                 let kv_scrutinee_ident = Ident::cast(kv_scrutinee_first_component)?;
                 if kv_scrutinee_ident.as_str() == first_component_ident.as_str() {
                     Some((
-                            kv_scrutinee.key()?.node().text_range(),
+                            kv_scrutinee,
                             kv_scrutinee_components
                                 .map(|n| n.to_string())
                                 .collect::<Vec<_>>()
@@ -76,39 +85,91 @@ impl Rule for RepeatedKeys {
                 }
             }).collect::<Vec<_>>();
 
-            if occurrences.first()?.0 == key.node().text_range();
-            if occurrences.len() >= 3;
+            if occurrences.first()?.0.node() == node;
+            if occurrences.len() >= threshold(sess);
 
             then {
-                let mut iter = occurrences.into_iter();
-
-                let (first_annotation, first_subkey) = iter.next().unwrap();
-                let first_message = format!("The key `{}` is first assigned here ...", first_component_ident.as_str());
-
-                let (second_annotation, second_subkey) = iter.next().unwrap();
-                let second_message = "... repeated here ...";
-
-                let (third_annotation, third_subkey) = iter.next().unwrap();
-                let third_message = {
-                    let remaining_occurrences = iter.count();
-                    let mut message = match remaining_occurrences {
-                        0 => "... and here.".to_string(),
-                        1 => "... and here (`1` occurrence omitted).".to_string(),
-                        n => format!("... and here (`{}` occurrences omitted).", n),
-                    };
-                    message.push_str(&format!(" Try `{} = {{ {}=...; {}=...; {}=...; }}` instead.", first_component_ident.as_str(), first_subkey, second_subkey, third_subkey));
-                    message
+                let name = first_component_ident.as_str();
+                let mut annotations = occurrences
+                    .iter()
+                    .map(|(kv, _)| kv.key().unwrap().node().text_range());
+                let subkeys = occurrences
+                    .iter()
+                    .take(3)
+                    .map(|(_, subkey)| format!("{}=...;", subkey))
+                    .collect::<Vec<_>>()
+                    .join(" ");
+                let first_message = format!("The key `{}` is first assigned here ...", name);
+                let mut report = match nested_set(&occurrences, name, sess) {
+                    Some((fix, removed)) => {
+                        let report = self.report().suggest(annotations.next().unwrap(), first_message, fix);
+                        removed.into_iter().fold(report, |report, (at, fix)| {
+                            report.suggest(at, "... repeated here ...", fix)
+                        })
+                    }
+                    None => {
+                        let report = self.report().diagnostic(annotations.next().unwrap(), first_message);
+                        annotations.take(occurrences.len().min(3) - 1).fold(report, |report, at| {
+                            report.diagnostic(at, "... repeated here ...")
+                        })
+                    }
                 };
-
-                Some(
-                    self.report()
-                    .diagnostic(first_annotation, first_message)
-                    .diagnostic(second_annotation, second_message)
-                    .diagnostic(third_annotation, third_message),
-                )
+                // the last annotation carries the hint
+                let remaining_occurrences = occurrences.len() - report.diagnostics.len();
+                let mut message = match remaining_occurrences {
+                    0 => "... and here.".to_string(),
+                    1 => "... and here (`1` occurrence omitted).".to_string(),
+                    n => format!("... and here (`{}` occurrences omitted).", n),
+                };
+                message.push_str(&format!(" Try `{} = {{ {} }}` instead.", name, subkeys));
+                report.diagnostics.last_mut().unwrap().message = message;
+                Some(report)
             } else {
                 None
             }
         }
     }
 }
+
+// number of repetitions to report, 3 unless set with the `threshold` option
+fn threshold(sess: &SessionInfo) -> usize {
+    sess.option("repeated_keys", "threshold")
+        .and_then(|t| t.first()?.parse().ok())
+        .map_or(3, |t: usize| t.max(2))
+}
+
+// rewrite the assignments into one nested set at the first of them, when
+// they all assign through an attribute path
+#[allow(clippy::type_complexity)]
+fn nested_set(
+    occurrences: &[(KeyValue, String)],
+    name: &str,
+    sess: &SessionInfo,
+) -> Option<(Suggestion, Vec<(TextRange, Suggestion)>)> {
+    let mut entries = Vec::new();
+    for (kv, _) in occurrences {
+        let second = kv.key()?.path().nth(1)?;
+        let start = second.text_range().start() - kv.node().text_range().start();
+        entries.push(kv.node().to_string()[usize::from(start)..].to_owned());
+    }
+    let first = occurrences.first()?.0.node();
+    let indent = first
+        .prev_sibling_or_token()
+        .filter(|t| t.kind() == SyntaxKind::TOKEN_WHITESPACE)
+        .and_then(|t| Some(t.to_string().rsplit('\n').next()?.to_owned()))
+        .unwrap_or_default();
+    let replacement = make::nested_key_value(name, &entries, &indent, sess.style());
+    let fix = Suggestion::new(first.text_range(), replacement.node().clone())
+        .description(format!("merge into `{} = {{ ... }};`", name));
+    let removed = occurrences[1..]
+        .iter()
+        .map(|(kv, _)| {
+            let at = kv.key().unwrap().node().text_range();
+            let removed = with_preceeding_whitespace(kv.node());
+            let fix = Suggestion::new(removed, make::empty().node().clone())
+                .description(format!("move into `{}`", name));
+            (at, fix)
+        })
+        .collect();
+    Some((fix, removed))
+}
@@ -0,0 +1,108 @@
+use crate::{
+    scope::{self, Binding},
+    session::SessionInfo,
+    Metadata, Report, Rule, Severity,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Inherit, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind,
+};
+
+/// ## What it does
+/// Checks for bindings of a `let`, a `rec` set or a function argument
+/// that shadow a variable of the same name bound around them. Names
+/// starting with `_` are left alone, other names can be allowed in
+/// statix.toml:
+///
+/// ```toml
+/// [options.shadowed_binding]
+/// allow = [ "self", "super" ]
+/// ```
+///
+/// ## Why is this bad?
+/// The outer variable can no longer be referred to, and a reader may
+/// mistake one for the other.
+///
+/// ## Example
+///
+/// ```nix
+/// { lib, pkgs }:
+/// map (pkgs: pkgs.hello) [ pkgs ]
+/// ```
+///
+/// Pick another name:
+///
+/// ```nix
+/// { lib, pkgs }:
+/// map (p: p.hello) [ pkgs ]
+/// ```
+#[lint(
+    name = "shadowed_binding",
+    note = "Found a binding shadowing another",
+    code = 34,
+    severity = Severity::Hint,
+    match_with = [
+        SyntaxKind::NODE_LET_IN,
+        SyntaxKind::NODE_LEGACY_LET,
+        SyntaxKind::NODE_ATTR_SET,
+        SyntaxKind::NODE_LAMBDA
+    ]
+)]
+struct ShadowedBinding;
+
+impl Rule for ShadowedBinding {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) if scope::is_scope(node) => node,
+            _ => return None,
+        };
+        let allowed = sess.option(self.name(), "allow").unwrap_or_default();
+        let outer = node
+            .ancestors()
+            .skip(1)
+            .filter(scope::is_scope)
+            .map(|a| scope::bindings(&a))
+            .collect::<Vec<_>>();
+        let mut report = self.report();
+        for binding in first_bindings(scope::bindings(node)) {
+            // `inherit a;` binds the very same value
+            let reinherited =
+                matches!(Inherit::cast(binding.entry.clone()), Some(i) if i.from().is_none());
+            if reinherited || binding.name.starts_with('_') || allowed.contains(&binding.name) {
+                continue;
+            }
+            let original = outer
+                .iter()
+                .find_map(|bindings| bindings.iter().find(|b| b.name == binding.name));
+            if let Some(original) = original {
+                report = report
+                    .diagnostic(
+                        binding.ident.text_range(),
+                        format!("`{}` shadows an outer binding", binding.name),
+                    )
+                    .related(
+                        original.ident.text_range(),
+                        format!("`{}` is first bound here", original.name),
+                    );
+            }
+        }
+        if report.diagnostics.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+
+// a name bound twice by the same scope is bound by its first binding
+fn first_bindings(bindings: Vec<Binding>) -> Vec<Binding> {
+    let mut first = Vec::<Binding>::new();
+    for binding in bindings {
+        if !first.iter().any(|b| b.name == binding.name) {
+            first.push(binding);
+        }
+    }
+    first
+}
@@ -0,0 +1,75 @@
+use crate::{
+    make,
+    session::SessionInfo,
+    utils::{call, function_name, is_string, unparenthesized},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{List, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind,
+};
+
+/// ## What it does
+/// Checks for `concatStrings` and `concatStringsSep` of a list with a
+/// single element, and replaces them with that element when it is
+/// known to be a string. `concatLists` of a single list is left to
+/// `empty_list_concat`.
+///
+/// ## Why is this bad?
+/// There is nothing to concatenate, the result is the element itself,
+/// converted to a string.
+///
+/// ## Example
+///
+/// ```nix
+/// lib.concatStringsSep ", " [ "${pname}-${version}" ]
+/// ```
+///
+/// Use the element:
+///
+/// ```nix
+/// "${pname}-${version}"
+/// ```
+#[lint(
+    name = "single_element_concat",
+    note = "Found a concatenation of a single string",
+    code = 73,
+    match_with = SyntaxKind::NODE_APPLY
+)]
+struct SingleElementConcat;
+
+impl Rule for SingleElementConcat {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let (function, args) = call(node);
+        let name = function_name(&function)?;
+        let list = match (name.as_str(), args.as_slice()) {
+            ("concatStrings", [list]) | ("concatStringsSep", [_, list]) => {
+                List::cast(list.clone())?
+            }
+            _ => return None,
+        };
+        let mut items = list.items();
+        let element = match (items.next(), items.next()) {
+            (Some(element), None) => element,
+            _ => return None,
+        };
+        let at = node.text_range();
+        if !is_string(&element) {
+            let message = format!(
+                "Use `{}` directly if it is a string, or `toString` otherwise",
+                element
+            );
+            return Some(self.report().diagnostic(at, message));
+        }
+        let message = format!("`{}` of a single string is that string", name);
+        let replacement = make::replacing(&unparenthesized(&element), node);
+        let fix = Suggestion::new(at, replacement).description("replace with the element");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
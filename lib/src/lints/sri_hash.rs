@@ -0,0 +1,116 @@
+use crate::{
+    make,
+    session::{Mode, SessionInfo, Since, Version},
+    utils::{attr_name, function_name, string_literal},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, AttrSet, EntryHolder, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// Fetchers of nixpkgs taking `hash` as well as `sha256`
+const FETCHERS: &[&str] = &[
+    "fetchurl",
+    "fetchzip",
+    "fetchpatch",
+    "fetchgit",
+    "fetchFromGitHub",
+    "fetchFromGitLab",
+    "fetchFromGitea",
+    "fetchFromBitbucket",
+    "fetchFromSourcehut",
+    "fetchFromCodeberg",
+    "fetchCrate",
+    "fetchPypi",
+];
+
+/// The nixpkgs release all of `FETCHERS` take `hash` since
+const HASH_SINCE: Version = Version::new(21, 11, None);
+
+/// ## What it does
+/// Checks for `sha256 = "...";` in the arguments of nixpkgs fetchers.
+/// Hashes already in SRI form, `sha256-...`, are moved to `hash`,
+/// others are reported with how to convert them.
+///
+/// ## Why is this bad?
+/// `hash` takes hashes of any algorithm in the SRI form, which is what
+/// Nix prints on a hash mismatch, and what nixpkgs uses throughout.
+///
+/// ## Example
+///
+/// ```nix
+/// fetchurl {
+///   url = "https://example.org/hello.tar.gz";
+///   sha256 = "sha256-jZkUKv2SV28wsM18tCqNxoCZmLxdYH2Idh9RLibH2yA=";
+/// }
+/// ```
+///
+/// Use `hash`:
+///
+/// ```nix
+/// fetchurl {
+///   url = "https://example.org/hello.tar.gz";
+///   hash = "sha256-jZkUKv2SV28wsM18tCqNxoCZmLxdYH2Idh9RLibH2yA=";
+/// }
+/// ```
+#[lint(
+    name = "sri_hash",
+    note = "Found `sha256` in the arguments of a fetcher",
+    code = 53,
+    match_with = SyntaxKind::NODE_APPLY,
+    modes = [Mode::Nixpkgs]
+)]
+struct SriHash;
+
+impl Rule for SriHash {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        if !is_fetcher(&apply.lambda()?) {
+            return None;
+        }
+        let set = AttrSet::cast(apply.value()?)?;
+        let mut sha256 = None;
+        for kv in set.entries() {
+            let key = kv.key()?;
+            let name = attr_name(&key.path().next()?);
+            match name.as_deref() {
+                Some("hash") => return None,
+                Some("sha256") if key.path().count() == 1 => sha256 = Some((key, kv.value()?)),
+                _ => {}
+            }
+        }
+        let (key, value) = sha256?;
+        let hash = string_literal(&value)?;
+        let at = key.node().text_range();
+        if hash.starts_with("sha256-") {
+            let message = "This hash is in SRI form already, pass it as `hash`";
+            let fix = Suggestion::new(at, make::ident("hash").node().clone())
+                .description("rename to `hash`");
+            Some(self.report().suggest_newest(
+                at,
+                message,
+                sess,
+                vec![(Since::Nixpkgs(HASH_SINCE), fix)],
+            ))
+        } else if !hash.is_empty() {
+            let message = format!(
+                "Convert this hash with `nix hash to-sri --type sha256 {}`, and pass it as `hash`",
+                hash
+            );
+            Some(self.report().diagnostic(at, message))
+        } else {
+            None
+        }
+    }
+}
+
+fn is_fetcher(function: &SyntaxNode) -> bool {
+    matches!(function_name(function), Some(name) if FETCHERS.contains(&name.as_str()))
+}
@@ -0,0 +1,237 @@
+use crate::{
+    scope,
+    session::{Mode, SessionInfo},
+    utils::{attr_name, string_literal},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{KeyValue, List, TypedNode, With},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// SPDX identifiers, and the attributes of `lib.licenses` they are
+const LICENSES: &[(&str, &str)] = &[
+    ("0BSD", "bsd0"),
+    ("AGPL-3.0-only", "agpl3Only"),
+    ("AGPL-3.0-or-later", "agpl3Plus"),
+    ("Apache-2.0", "asl20"),
+    ("Artistic-2.0", "artistic2"),
+    ("BSD-2-Clause", "bsd2"),
+    ("BSD-3-Clause", "bsd3"),
+    ("BSL-1.0", "boost"),
+    ("CC-BY-4.0", "cc-by-40"),
+    ("CC-BY-SA-4.0", "cc-by-sa-40"),
+    ("CC0-1.0", "cc0"),
+    ("EPL-2.0", "epl20"),
+    ("EUPL-1.2", "eupl12"),
+    ("GPL-2.0-only", "gpl2Only"),
+    ("GPL-2.0-or-later", "gpl2Plus"),
+    ("GPL-3.0-only", "gpl3Only"),
+    ("GPL-3.0-or-later", "gpl3Plus"),
+    ("ISC", "isc"),
+    ("LGPL-2.1-only", "lgpl21Only"),
+    ("LGPL-2.1-or-later", "lgpl21Plus"),
+    ("LGPL-3.0-only", "lgpl3Only"),
+    ("LGPL-3.0-or-later", "lgpl3Plus"),
+    ("MIT", "mit"),
+    ("MPL-2.0", "mpl20"),
+    ("OFL-1.1", "ofl"),
+    ("PostgreSQL", "postgresql"),
+    ("Python-2.0", "psfl"),
+    ("Unlicense", "unlicense"),
+    ("WTFPL", "wtfpl"),
+    ("Zlib", "zlib"),
+];
+
+/// Names that do not say which license is meant, compared without case
+/// and punctuation, with the licenses they may be
+const AMBIGUOUS: &[(&str, &[&str])] = &[
+    ("apache", &["asl20"]),
+    ("apache2", &["asl20"]),
+    ("apachelicense20", &["asl20"]),
+    ("bsd", &["bsd2", "bsd3"]),
+    ("gpl", &["gpl2Only", "gpl2Plus", "gpl3Only", "gpl3Plus"]),
+    ("gpl2", &["gpl2Only", "gpl2Plus"]),
+    ("gplv2", &["gpl2Only", "gpl2Plus"]),
+    ("gpl20", &["gpl2Only", "gpl2Plus"]),
+    ("gpl3", &["gpl3Only", "gpl3Plus"]),
+    ("gplv3", &["gpl3Only", "gpl3Plus"]),
+    ("gpl30", &["gpl3Only", "gpl3Plus"]),
+    ("lgpl21", &["lgpl21Only", "lgpl21Plus"]),
+    ("lgplv21", &["lgpl21Only", "lgpl21Plus"]),
+    ("lgpl3", &["lgpl3Only", "lgpl3Plus"]),
+    ("lgplv3", &["lgpl3Only", "lgpl3Plus"]),
+    ("lgpl30", &["lgpl3Only", "lgpl3Plus"]),
+    ("agpl3", &["agpl3Only", "agpl3Plus"]),
+    ("agplv3", &["agpl3Only", "agpl3Plus"]),
+    ("agpl30", &["agpl3Only", "agpl3Plus"]),
+    ("mpl", &["mpl20"]),
+    ("mpl2", &["mpl20"]),
+    ("publicdomain", &["publicDomain"]),
+];
+
+/// ## What it does
+/// Checks for licenses in `meta` given as strings, such as
+/// `license = "MIT";`, and replaces SPDX identifiers with the attribute
+/// of `lib.licenses`. Names that do not say which version or variant is
+/// meant, such as `"GPL"`, are pointed to the attributes they may be,
+/// without a fix.
+///
+/// ## Why is this bad?
+/// nixpkgs only understands licenses of `lib.licenses`: a string is
+/// neither checked for being free, so `allowUnfree` does not apply to
+/// it, nor shown as a license by search.nixos.org.
+///
+/// ## Example
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   meta.license = "MIT";
+/// }
+/// ```
+///
+/// Use `lib.licenses`:
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   meta.license = lib.licenses.mit;
+/// }
+/// ```
+#[lint(
+    name = "string_license",
+    note = "Found a license given as a string",
+    code = 81,
+    match_with = SyntaxKind::NODE_KEY_VALUE,
+    modes = [Mode::Nixpkgs]
+)]
+struct StringLicense;
+
+impl Rule for StringLicense {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let kv = KeyValue::cast(node.clone())?;
+        if !is_meta_license(&kv) {
+            return None;
+        }
+        // `license = [ "MIT" "Apache-2.0" ];`
+        let value = kv.value()?;
+        let strings = match List::cast(value.clone()) {
+            Some(list) => list.items().collect(),
+            None => vec![value],
+        };
+        let prefix = licenses_prefix(node);
+        let mut report = self.report();
+        for string in strings {
+            let name = match string_literal(&string) {
+                Some(name) => name,
+                None => continue,
+            };
+            let at = string.text_range();
+            if let Some(attr) = exact(&name) {
+                let message = format!("Use `licenses.{}` instead of a string", attr);
+                report = match &prefix {
+                    Some(prefix) => {
+                        let replacement = format!("{}{}", prefix, attr);
+                        let fix =
+                            Suggestion::new(at, rnix::parse(&replacement).node().first_child()?)
+                                .description(format!("replace with `{}`", replacement));
+                        report.suggest(at, message, fix)
+                    }
+                    None => report.diagnostic(at, message),
+                };
+            } else {
+                let message = match ambiguous(&name) {
+                    Some([attr]) => format!(
+                        "`{}` is likely `licenses.{}`, check the version of the license",
+                        name, attr
+                    ),
+                    Some(attrs) => format!(
+                        "`{}` may be any of {}, use the one of `licenses` that applies",
+                        name,
+                        attrs
+                            .iter()
+                            .map(|a| format!("`licenses.{}`", a))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                    None => format!("Use the attribute of `licenses` for `{}`", name),
+                };
+                report = report.diagnostic(at, message);
+            }
+        }
+        if report.diagnostics.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+
+// `meta.license = ...;`, or `license = ...;` in `meta = { ... };`
+fn is_meta_license(kv: &KeyValue) -> bool {
+    let path = match kv.key() {
+        Some(key) => key.path().map(|k| attr_name(&k)).collect::<Vec<_>>(),
+        None => return false,
+    };
+    match path.as_slice() {
+        [.., Some(meta), Some(license)] if meta == "meta" && license == "license" => true,
+        [Some(license)] if license == "license" => {
+            // through `meta = with lib; { ... };` and parentheses
+            let mut parent = kv.node().parent().and_then(|set| set.parent());
+            while let Some(p) = parent.clone() {
+                if !matches!(p.kind(), SyntaxKind::NODE_WITH | SyntaxKind::NODE_PAREN) {
+                    break;
+                }
+                parent = p.parent();
+            }
+            matches!(
+                parent.and_then(KeyValue::cast).and_then(|meta| meta.key()?.path().last()),
+                Some(meta) if attr_name(&meta).as_deref() == Some("meta")
+            )
+        }
+        _ => false,
+    }
+}
+
+// How `lib.licenses` is referred to where `node` is
+fn licenses_prefix(node: &SyntaxNode) -> Option<String> {
+    for with in node.ancestors().filter_map(With::cast) {
+        match with.namespace().map(|n| n.to_string()).as_deref() {
+            Some("lib.licenses") => return Some(String::new()),
+            Some("lib") => return Some("licenses.".to_owned()),
+            _ => {}
+        }
+    }
+    let has_lib = scope::lookup(node, "lib").is_some();
+    if has_lib {
+        Some("lib.licenses.".to_owned())
+    } else {
+        None
+    }
+}
+
+fn exact(name: &str) -> Option<&'static str> {
+    LICENSES
+        .iter()
+        .find(|(spdx, attr)| spdx.eq_ignore_ascii_case(name) || *attr == name)
+        .map(|(_, attr)| *attr)
+}
+
+fn ambiguous(name: &str) -> Option<&'static [&'static str]> {
+    let normalized = name
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .collect::<String>()
+        .to_ascii_lowercase();
+    AMBIGUOUS
+        .iter()
+        .find(|(n, _)| *n == normalized)
+        .map(|(_, attrs)| *attrs)
+}
@@ -0,0 +1,89 @@
+use crate::{make, session::SessionInfo, utils::expects_path, Metadata, Report, Rule, Suggestion};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, Ident, Select, Str, TokenWrapper, TypedNode},
+    NodeOrToken, StrPart, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for relative paths written as strings, such as
+/// `"./overlays/default.nix"`, where a path is expected: as argument of
+/// `import` or `callPackage`, as `src`, or in `imports`.
+///
+/// ## Why is this bad?
+/// A string is not a path: it is not resolved relative to the file it
+/// is written in, and is not copied to the store. `import
+/// "./default.nix"` fails to evaluate.
+///
+/// ## Example
+///
+/// ```nix
+/// import "./overlays/default.nix"
+/// ```
+///
+/// Use a path literal:
+///
+/// ```nix
+/// import ./overlays/default.nix
+/// ```
+#[lint(
+    name = "string_path",
+    note = "Found a relative path written as a string",
+    code = 46,
+    match_with = SyntaxKind::NODE_STRING
+)]
+struct StringPath;
+
+impl Rule for StringPath {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let text = match Str::cast(node.clone())?.parts().as_slice() {
+            [StrPart::Literal(text)] => text.clone(),
+            _ => return None,
+        };
+        if !(text.starts_with("./") || text.starts_with("../")) {
+            return None;
+        }
+        if !expects_path(node) && !is_imported(node) {
+            return None;
+        }
+        let at = node.text_range();
+        if is_path_literal(&text) {
+            let message = format!("This is a string, not a path, try `{}`", text);
+            let replacement = make::path(&text).node().clone();
+            let description = format!("replace with `{}`", text);
+            let fix = Suggestion::new(at, replacement).description(description);
+            Some(self.report().suggest(at, message, fix))
+        } else {
+            // `./with space.nix` is not a path literal
+            let message = "This is a string, not a path";
+            Some(self.report().diagnostic(at, message))
+        }
+    }
+}
+
+// the argument of `import`, `builtins.import` or `callPackage`
+fn is_imported(node: &SyntaxNode) -> bool {
+    let apply = match node.parent().and_then(Apply::cast) {
+        Some(apply) if apply.value().as_ref() == Some(node) => apply,
+        _ => return false,
+    };
+    let name = match apply.lambda() {
+        Some(function) => match Select::cast(function.clone()) {
+            Some(select) => select.index().map(|i| i.to_string()),
+            None => Ident::cast(function).map(|i| i.as_str().to_owned()),
+        },
+        None => None,
+    };
+    matches!(name.as_deref(), Some("import") | Some("callPackage"))
+}
+
+// whether `text` can be written as a path literal
+fn is_path_literal(text: &str) -> bool {
+    let allowed = |c: char| c.is_ascii_alphanumeric() || "._-+/".contains(c);
+    text.chars().all(allowed) && !text.ends_with('/') && !text.contains("//")
+}
@@ -0,0 +1,112 @@
+use crate::{
+    make,
+    scope::{self, Scopes},
+    session::{Mode, SessionInfo},
+    utils::{is_top_level, GLOBALS},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{ParsedType, TypedNode, With},
+    NodeOrToken, SyntaxElement, SyntaxKind, TextRange,
+};
+use std::convert::TryFrom;
+
+/// ## What it does
+/// Checks for `with lib;` over a whole NixOS module, and rewrites the
+/// variables it provides to `lib.` references.
+///
+/// ## Why is this bad?
+/// Variables of a `with` are resolved at evaluation time, so neither
+/// readers nor tools can tell where a name comes from, and a typo is
+/// only caught when the module is evaluated. Over a whole module, this
+/// applies to every name in it.
+///
+/// ## Example
+///
+/// ```nix
+/// { config, lib, ... }:
+/// with lib;
+/// {
+///   options.services.hello.enable = mkEnableOption "hello";
+/// }
+/// ```
+///
+/// Refer to `lib` explicitly:
+///
+/// ```nix
+/// { config, lib, ... }:
+/// {
+///   options.services.hello.enable = lib.mkEnableOption "hello";
+/// }
+/// ```
+#[lint(
+    name = "top_level_with",
+    note = "Found `with lib;` over a whole module",
+    code = 60,
+    match_with = SyntaxKind::NODE_WITH,
+    modes = [Mode::NixosModule]
+)]
+struct TopLevelWith;
+
+impl Rule for TopLevelWith {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let with = With::cast(node.clone())?;
+        let namespace = with.namespace()?;
+        if namespace.kind() != SyntaxKind::NODE_IDENT
+            || namespace.text() != "lib"
+            || !is_top_level(node)
+        {
+            return None;
+        }
+        let body = with.body()?;
+        let at = TextRange::new(node.text_range().start(), body.text_range().start());
+        let message = "Refer to `lib` explicitly instead";
+        if scope::lookup(node, "lib").is_none() {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let scopes = Scopes::new(&body);
+        let free = scopes
+            .references
+            .iter()
+            .filter(|(_, binding)| binding.is_none())
+            .map(|(ident, _)| ident)
+            .filter(|ident| {
+                let name = ident.text().to_string();
+                !GLOBALS.contains(&name.as_str())
+                    && !name.starts_with("__")
+                    && scope::lookup(node, &name).is_none()
+            })
+            .collect::<Vec<_>>();
+        // under another `with`, or in `inherit a;`, a variable cannot
+        // simply be prefixed
+        let ambiguous = free.iter().any(|ident| {
+            ident.parent().map(|p| p.kind()) == Some(SyntaxKind::NODE_INHERIT)
+                || ident
+                    .ancestors()
+                    .take_while(|a| *a != body)
+                    .any(|a| a.kind() == SyntaxKind::NODE_WITH)
+        });
+        if ambiguous {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let edits = free
+            .iter()
+            .map(|ident| {
+                (
+                    TextRange::empty(ident.text_range().start()),
+                    "lib.".to_owned(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let replacement = make::edited(&ParsedType::try_from(body).ok()?, &edits);
+        let fix = Suggestion::new(node.text_range(), replacement.node().clone())
+            .description("prefix the variables with `lib.`");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
@@ -0,0 +1,197 @@
+use crate::{
+    make,
+    scope::{self, BindingKind, Scopes},
+    session::SessionInfo,
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{KeyValue, LetIn, ParsedType, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
+};
+use std::convert::TryFrom;
+
+/// ## What it does
+/// Checks for `let` expressions whose body is one of their bindings,
+/// and replaces them with the value of that binding. It can also
+/// inline a `let` with a single binding used once, where doing so does
+/// not evaluate it more often, that is, outside of a function. This is
+/// enabled in statix.toml:
+///
+/// ```toml
+/// [options.trivial_let]
+/// inline_single_use = true
+/// ```
+///
+/// ## Why is this bad?
+/// The name adds nothing to the value it is given, and takes a `let`
+/// to read through.
+///
+/// ## Example
+///
+/// ```nix
+/// let
+///   drv = pkgs.callPackage ./hello.nix { };
+/// in
+/// drv
+/// ```
+///
+/// Use the value directly:
+///
+/// ```nix
+/// pkgs.callPackage ./hello.nix { }
+/// ```
+#[lint(
+    name = "trivial_let",
+    note = "Found a `let` that can be inlined",
+    code = 71,
+    match_with = SyntaxKind::NODE_LET_IN
+)]
+struct TrivialLet;
+
+impl Rule for TrivialLet {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let body = LetIn::cast(node.clone())?.body()?;
+        let scopes = Scopes::new(node);
+        let own = scopes
+            .bindings_of(node)
+            .filter(|(_, b)| b.kind == BindingKind::Let)
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        // whether `value` refers to a binding of this `let`
+        let is_recursive = |value: &SyntaxNode| {
+            scopes.references.iter().any(|(ident, binding)| {
+                value.text_range().contains_range(ident.text_range())
+                    && matches!(binding, Some(b) if own.contains(b))
+            })
+        };
+        let at = node.text_range();
+
+        // `let x = v; in x`
+        let aliased = scopes
+            .references
+            .iter()
+            .find(|(ident, _)| *ident == body)
+            .and_then(|(_, binding)| *binding)
+            .filter(|b| own.contains(b));
+        if let Some(binding) = aliased {
+            let name = &scopes.bindings[binding].name;
+            let value = value(&scopes.bindings[binding].entry, name, node)?;
+            if is_recursive(&value) {
+                return None;
+            }
+            let message = format!("The `let` only names this value `{}`", name);
+            let fix = Suggestion::new(at, make::replacing(&value, node))
+                .description(format!("replace with the value of `{}`", name));
+            return Some(self.report().suggest(value.text_range(), message, fix));
+        }
+
+        // `let x = v; in f x`
+        let inline = matches!(
+            sess.option(self.name(), "inline_single_use"),
+            Some([value]) if value == "true"
+        );
+        if !inline || own.len() != 1 {
+            return None;
+        }
+        let binding = &scopes.bindings[own[0]];
+        let value = value(&binding.entry, &binding.name, node)?;
+        let uses = scopes.uses(own[0]).collect::<Vec<_>>();
+        let usage = match uses.as_slice() {
+            [usage] => *usage,
+            _ => return None,
+        };
+        if is_recursive(&value) || !is_inlinable(usage, &value, &body) {
+            return None;
+        }
+        let edits = [spliced(usage, &value).unwrap_or_else(|| {
+            (
+                usage.text_range(),
+                make::replacing(&value, usage).to_string(),
+            )
+        })];
+        let body = make::edited(&ParsedType::try_from(body).ok()?, &edits);
+        let message = format!("`{}` is used once, and can be inlined", binding.name);
+        let fix = Suggestion::new(at, make::replacing(body.node(), node))
+            .description(format!("inline `{}`", binding.name));
+        Some(
+            self.report()
+                .suggest(binding.ident.text_range(), message, fix),
+        )
+    }
+}
+
+// the value of `entry`, when it binds `name` on its own: not
+// `inherit x;` nor `x.a = 1;`
+fn value(entry: &SyntaxNode, name: &str, let_in: &SyntaxNode) -> Option<SyntaxNode> {
+    let kv = KeyValue::cast(entry.clone())?;
+    if kv.key()?.path().count() != 1 {
+        return None;
+    }
+    let entries = scope::bindings(let_in)
+        .into_iter()
+        .filter(|b| b.name == name)
+        .count();
+    if entries != 1 {
+        return None;
+    }
+    kv.value()
+}
+
+// `"${x}"` with `x = "a";` is `"a"`, rather than `"${"a"}"`
+fn spliced(usage: &SyntaxNode, value: &SyntaxNode) -> Option<(TextRange, String)> {
+    let interpolation = usage
+        .parent()
+        .filter(|p| p.kind() == SyntaxKind::NODE_STRING_INTERPOL)?;
+    let string = interpolation.parent()?;
+    let literal = value.to_string();
+    let has_interpolation = value
+        .children()
+        .any(|c| c.kind() == SyntaxKind::NODE_STRING_INTERPOL);
+    // escapes differ between `"` and `''` strings
+    let quote = if literal.starts_with('"') { "\"" } else { "''" };
+    if value.kind() != SyntaxKind::NODE_STRING
+        || has_interpolation
+        || !string.to_string().starts_with(quote)
+        || !literal.starts_with(quote)
+    {
+        return None;
+    }
+    let contents = &literal[quote.len()..literal.len() - quote.len()];
+    Some((interpolation.text_range(), contents.to_owned()))
+}
+
+// whether `value` can replace `usage`: not under a function, which would
+// evaluate it on each call, nor where its variables are bound otherwise
+fn is_inlinable(usage: &SyntaxNode, value: &SyntaxNode, body: &SyntaxNode) -> bool {
+    if usage.parent().map(|p| p.kind()) == Some(SyntaxKind::NODE_INHERIT) {
+        return false;
+    }
+    let variables = Scopes::new(value)
+        .references
+        .into_iter()
+        .map(|(ident, _)| ident.text().to_string())
+        .collect::<Vec<_>>();
+    for ancestor in usage.ancestors() {
+        let rebinds = |scope: &SyntaxNode| {
+            scope::bindings(scope)
+                .iter()
+                .any(|b| variables.contains(&b.name))
+        };
+        match ancestor.kind() {
+            SyntaxKind::NODE_LAMBDA | SyntaxKind::NODE_WITH => return false,
+            _ if scope::is_scope(&ancestor) && rebinds(&ancestor) => return false,
+            _ => {}
+        }
+        // the body itself may be a scope, checked above
+        if ancestor == *body {
+            return true;
+        }
+    }
+    true
+}
@@ -0,0 +1,150 @@
+use crate::{
+    session::{Mode, SessionInfo},
+    utils::{attr_name, bool_literal},
+    Metadata, Report, Rule, Severity,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, AttrSet, EntryHolder, Ident, KeyValue, Select, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for options declared with `mkOption` without a
+/// `description`. Options with `internal = true;` are left alone. It
+/// can also require a `type`, report with another severity, and allow
+/// options by name or path, in statix.toml:
+///
+/// ```toml
+/// [options.undocumented_option]
+/// require_type = true
+/// severity = "hint"
+/// allow = [ "services.hello.extra" ]
+/// ```
+///
+/// ## Why is this bad?
+/// Options without a description are rendered in the manual without
+/// saying what they do, and options without a type accept anything.
+///
+/// ## Example
+///
+/// ```nix
+/// {
+///   options.services.hello.greeting = lib.mkOption {
+///     type = lib.types.str;
+///   };
+/// }
+/// ```
+///
+/// Describe the option:
+///
+/// ```nix
+/// {
+///   options.services.hello.greeting = lib.mkOption {
+///     type = lib.types.str;
+///     description = "The greeting to print.";
+///   };
+/// }
+/// ```
+#[lint(
+    name = "undocumented_option",
+    note = "Found an option without a description",
+    code = 58,
+    match_with = SyntaxKind::NODE_APPLY,
+    modes = [Mode::NixosModule]
+)]
+struct UndocumentedOption;
+
+impl Rule for UndocumentedOption {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        if !is_mk_option(&apply.lambda()?) {
+            return None;
+        }
+        let set = AttrSet::cast(apply.value()?)?;
+        let mut keys = Vec::new();
+        for kv in set.entries() {
+            let name = attr_name(&kv.key()?.path().next()?)?;
+            if name == "internal" && matches!(kv.value(), Some(v) if bool_literal(&v) == Some(true))
+            {
+                return None;
+            }
+            keys.push(name);
+        }
+        let option = option_path(node);
+        let allowed = sess.option(self.name(), "allow").unwrap_or_default();
+        if let Some(option) = &option {
+            let name = option.rsplit('.').next().unwrap_or(option);
+            if allowed.iter().any(|a| a == option || a == name) {
+                return None;
+            }
+        }
+        let require_type = matches!(
+            sess.option(self.name(), "require_type"),
+            Some([value]) if value == "true"
+        );
+        let missing = ["description", "type"]
+            .iter()
+            .filter(|&&key| key == "description" || require_type)
+            .filter(|&&key| !keys.iter().any(|k| k == key))
+            .map(|key| format!("`{}`", key))
+            .collect::<Vec<_>>();
+        if missing.is_empty() {
+            return None;
+        }
+        let severity = sess
+            .option(self.name(), "severity")
+            .and_then(|values| values.first()?.parse::<Severity>().ok())
+            .unwrap_or_else(|| self.severity());
+        let message = match &option {
+            Some(option) => format!("`{}` has no {}", option, missing.join(" and no ")),
+            None => format!("This option has no {}", missing.join(" and no ")),
+        };
+        Some(
+            self.report()
+                .severity(severity)
+                .diagnostic(node.text_range(), message),
+        )
+    }
+}
+
+// `lib.mkOption`, and `mkOption` brought in scope
+fn is_mk_option(function: &SyntaxNode) -> bool {
+    match Select::cast(function.clone()) {
+        Some(select) => matches!(select.index(), Some(index) if index.text() == "mkOption"),
+        None => matches!(Ident::cast(function.clone()), Some(i) if i.as_str() == "mkOption"),
+    }
+}
+
+// the path the option is declared at, without `options.`, from the keys
+// of the sets around it: `services.hello.greeting`
+fn option_path(option: &SyntaxNode) -> Option<String> {
+    let mut path = Vec::new();
+    let mut node = option.clone();
+    while let Some(kv) = node.parent().and_then(KeyValue::cast) {
+        let names = kv
+            .key()?
+            .path()
+            .map(|n| attr_name(&n))
+            .collect::<Option<Vec<_>>>()?;
+        path.splice(0..0, names);
+        // up to the key-value around the set holding this one
+        node = match kv.node().parent() {
+            Some(set) if set.kind() == SyntaxKind::NODE_ATTR_SET => set,
+            _ => break,
+        };
+    }
+    if path.first().map(String::as_str) == Some("options") {
+        path.remove(0);
+    }
+    if path.is_empty() {
+        None
+    } else {
+        Some(path.join("."))
+    }
+}
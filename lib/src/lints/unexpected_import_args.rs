@@ -0,0 +1,109 @@
+use crate::{
+    project::{ImportKind, Project, ProjectReport},
+    session::SessionInfo,
+    Metadata, Report, Rule, Severity,
+};
+
+use macros::lint;
+use rnix::{
+    types::{AttrSet, EntryHolder, Ident, Lambda, Pattern, TokenWrapper, TypedNode},
+    SyntaxElement, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for arguments passed to an imported file (with `import` or
+/// `callPackage`) that the function in that file does not accept. Runs
+/// with `statix check --project`.
+///
+/// ## Why is this bad?
+/// Functions with a closed argument pattern fail to evaluate when called
+/// with an unexpected argument. Usually the argument was renamed or
+/// removed in the imported file, and the call site was not updated.
+///
+/// ## Example
+///
+/// ```nix
+/// # ./hello.nix
+/// { stdenv, fetchurl }: stdenv.mkDerivation { /* ... */ }
+///
+/// # ./default.nix
+/// callPackage ./hello.nix { fetchgit = fetchgit; }
+/// ```
+///
+/// Pass only the arguments that `./hello.nix` accepts:
+///
+/// ```nix
+/// callPackage ./hello.nix { }
+/// ```
+#[lint(
+    name = "unexpected_import_args",
+    note = "Imported function does not accept this argument",
+    code = 24,
+    severity = Severity::Error
+)]
+struct UnexpectedImportArgs;
+
+impl Rule for UnexpectedImportArgs {
+    fn validate(&self, _node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        None
+    }
+
+    fn check_project(&self, project: &Project, _sess: &SessionInfo) -> Vec<ProjectReport> {
+        let mut reports = Vec::new();
+        for (idx, file) in project.files().iter().enumerate() {
+            let pattern = match file.expr().and_then(closed_pattern) {
+                Some(p) => p,
+                None => continue,
+            };
+            let accepted = pattern
+                .entries()
+                .filter_map(|e| Some(e.name()?.as_str().to_owned()))
+                .collect::<Vec<_>>();
+            for (from, import) in project.importers(idx) {
+                if import.kind == ImportKind::Module {
+                    continue;
+                }
+                let arg = match import.arg().and_then(AttrSet::cast) {
+                    Some(a) => a,
+                    None => continue,
+                };
+                let name = import.path.to_string();
+                let report = passed(&arg)
+                    .filter(|i| !accepted.iter().any(|a| a == i.as_str()))
+                    .fold(self.report(), |report, ident| {
+                        let at = ident.node().text_range();
+                        let message = format!(
+                            "`{}` does not accept an argument named `{}`",
+                            name,
+                            ident.as_str()
+                        );
+                        report.diagnostic(at, message)
+                    });
+                if !report.diagnostics.is_empty() {
+                    reports.push(ProjectReport { file: from, report });
+                }
+            }
+        }
+        reports
+    }
+}
+
+// the argument pattern of a function without an ellipsis
+fn closed_pattern(expr: SyntaxNode) -> Option<Pattern> {
+    let lambda = Lambda::cast(expr)?;
+    let pattern = Pattern::cast(lambda.arg()?)?;
+    if pattern.ellipsis() {
+        None
+    } else {
+        Some(pattern)
+    }
+}
+
+// names bound by an attribute set, by `name = ...` or `inherit name`
+fn passed(set: &AttrSet) -> impl Iterator<Item = Ident> {
+    let keys = set
+        .entries()
+        .filter_map(|kv| Ident::cast(kv.key()?.path().next()?));
+    let inherited = set.inherits().flat_map(|i| i.idents().collect::<Vec<_>>());
+    keys.chain(inherited)
+}
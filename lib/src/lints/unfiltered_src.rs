@@ -0,0 +1,112 @@
+use crate::{
+    make, scope,
+    session::{Mode, SessionInfo},
+    utils::{attr_name, derivation_attrs, function_name, unparenthesized, BUILDERS},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Apply, EntryHolder, Ident, Select, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for derivations whose `src` is the whole directory of the
+/// project, `./.`, or the whole flake, `self`, and wraps `./.` in
+/// `lib.cleanSource`.
+///
+/// ## Why is this bad?
+/// Every file of the directory becomes part of the source, so that
+/// changing any of them, such as a README, the CI configuration or the
+/// Nix files themselves, rebuilds the package. Build results such as
+/// `result` links end up in the source as well. `lib.cleanSource`
+/// leaves out version control files and build results, and
+/// `lib.fileset` or explicit paths such as `./src` list only the files
+/// the build needs.
+///
+/// ## Example
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   version = "1.0";
+///   src = ./.;
+/// }
+/// ```
+///
+/// Filter the source:
+///
+/// ```nix
+/// stdenv.mkDerivation {
+///   pname = "hello";
+///   version = "1.0";
+///   src = lib.fileset.toSource {
+///     root = ./.;
+///     fileset = lib.fileset.unions [ ./src ./Makefile ];
+///   };
+/// }
+/// ```
+#[lint(
+    name = "unfiltered_src",
+    note = "Found the whole project directory as source",
+    code = 79,
+    match_with = SyntaxKind::NODE_APPLY,
+    modes = [Mode::Nixpkgs, Mode::Flake]
+)]
+struct UnfilteredSrc;
+
+impl Rule for UnfilteredSrc {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let apply = Apply::cast(node.clone())?;
+        if !BUILDERS.contains(&function_name(&apply.lambda()?)?.as_str()) {
+            return None;
+        }
+        let set = derivation_attrs(&apply.value()?)?;
+        let value = set.entries().find_map(|kv| {
+            let key = kv.key()?;
+            let mut path = key.path();
+            match (path.next().and_then(|k| attr_name(&k)), path.next()) {
+                (Some(name), None) if name == "src" => kv.value(),
+                _ => None,
+            }
+        })?;
+        let src = unparenthesized(&value);
+        let at = value.text_range();
+        if is_self(&src) {
+            let message =
+                "`self` is the whole flake, filter it with `lib.fileset` or `lib.cleanSource`";
+            return Some(self.report().diagnostic(at, message));
+        }
+        if src.kind() != SyntaxKind::NODE_LITERAL || src.text() != "./." {
+            return None;
+        }
+        let message =
+            "`./.` is the whole directory, filter it with `lib.fileset` or `lib.cleanSource`, or use the paths the build needs";
+        let has_lib = scope::lookup(node, "lib").is_some();
+        if !has_lib {
+            return Some(self.report().diagnostic(at, message));
+        }
+        let clean_source =
+            make::select(make::ident("lib").node(), make::ident("cleanSource").node());
+        let replacement = make::apply(clean_source.node(), &src);
+        let fix = Suggestion::new(at, replacement.node().clone())
+            .description("wrap in `lib.cleanSource`");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// `self`, or `inputs.self`
+fn is_self(node: &SyntaxNode) -> bool {
+    match Select::cast(node.clone()) {
+        Some(select) => {
+            matches!(select.index(), Some(index) if index.text() == "self")
+                && matches!(select.set().and_then(Ident::cast), Some(set) if set.as_str() == "inputs")
+        }
+        None => matches!(Ident::cast(node.clone()), Some(ident) if ident.as_str() == "self"),
+    }
+}
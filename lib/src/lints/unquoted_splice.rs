@@ -48,7 +48,8 @@ impl Rule for UnquotedSplice {
                 let at = node.text_range();
                 let replacement = make::quote(node).node().clone();
                 let message = "Consider quoting this splice expression";
-                Some(self.report().suggest(at, message, Suggestion::new(at, replacement)))
+                let description = format!("replace with `{}`", replacement);
+                Some(self.report().suggest(at, message, Suggestion::new(at, replacement).description(description)))
             } else {
                 None
             }
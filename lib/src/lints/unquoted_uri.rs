@@ -18,7 +18,9 @@ use rnix::{types::TypedNode, NodeOrToken, SyntaxElement, SyntaxKind};
 /// URL can be mistaken for a part of URL by language-agnostic tools
 /// such as terminal emulators.
 ///
-/// See RFC 00045 [1] for more.
+/// See RFC 00045 [1] for more. The syntax is deprecated since Nix 2.4,
+/// where the `no-url-literals` experimental feature turns it into an
+/// error, so the lint is only active from that version on.
 ///
 /// [1]: https://github.com/NixOS/rfcs/blob/master/rfcs/0045-deprecate-url-syntax.md
 ///
@@ -41,6 +43,7 @@ use rnix::{types::TypedNode, NodeOrToken, SyntaxElement, SyntaxKind};
     name = "unquoted_uri",
     note = "Found unquoted URI expression",
     code = 12,
+    min_nix_version = "2.4",
     match_with = SyntaxKind::TOKEN_URI
 )]
 struct UnquotedUri;
@@ -54,7 +57,8 @@ impl Rule for UnquotedUri {
                 let at = token.text_range();
                 let replacement = make::quote(&parent_node).node().clone();
                 let message = "Consider quoting this URI expression";
-                Some(self.report().suggest(at, message, Suggestion::new(at, replacement)))
+                let description = format!("replace with `{}`", replacement);
+                Some(self.report().suggest(at, message, Suggestion::new(at, replacement).description(description)))
             } else {
                 None
             }
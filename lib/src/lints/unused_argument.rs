@@ -0,0 +1,227 @@
+use crate::{
+    make,
+    scope::{BindingKind, Scopes},
+    session::{Mode, SessionInfo},
+    utils::first_key,
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{KeyValue, Lambda, Pattern, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange, TextSize,
+};
+use rowan::Direction;
+
+/// ## What it does
+/// Checks for function arguments, and entries of argument patterns,
+/// that are never used in the body of the function. Entries of a
+/// pattern bound with `@` are left alone when the binding is used, as
+/// are names starting with `_`, other prefixes can be allowed in
+/// statix.toml:
+///
+/// ```toml
+/// [options.unused_argument]
+/// allow_prefix = [ "_", "unused" ]
+/// ```
+///
+/// Entries are only removed from patterns that end in `...`, as callers
+/// that pass them explicitly would fail otherwise, and the `outputs`
+/// function of a flake is never rewritten.
+///
+/// ## Why is this bad?
+/// An unused argument is noise, and for a pattern entry, makes callers
+/// such as `callPackage` pass something the function does not need.
+///
+/// ## Example
+///
+/// ```nix
+/// { stdenv, lib, fetchurl, ... }:
+/// stdenv.mkDerivation {
+///   pname = "hello";
+/// }
+/// ```
+///
+/// Remove the pattern entries:
+///
+/// ```nix
+/// { stdenv, ... }:
+/// stdenv.mkDerivation {
+///   pname = "hello";
+/// }
+/// ```
+#[lint(
+    name = "unused_argument",
+    note = "Found an unused function argument",
+    code = 32,
+    match_with = SyntaxKind::NODE_LAMBDA
+)]
+struct UnusedArgument;
+
+impl Rule for UnusedArgument {
+    fn validate(&self, node: &SyntaxElement, sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let default_prefixes = ["_".to_owned()];
+        let prefixes = sess
+            .option(self.name(), "allow_prefix")
+            .unwrap_or(&default_prefixes);
+        let scopes = Scopes::new(node);
+        // entries of `{ a, ... } @ args` may be used through `args`
+        let bind_used = scopes
+            .bindings_of(node)
+            .any(|(idx, b)| b.kind == BindingKind::PatBind && scopes.uses(idx).next().is_some());
+        let unused = scopes
+            .bindings_of(node)
+            .filter(|(_, b)| !(bind_used && b.kind == BindingKind::PatEntry))
+            .filter(|(_, b)| !prefixes.iter().any(|p| b.name.starts_with(p.as_str())))
+            .filter(|(idx, _)| scopes.uses(*idx).next().is_none())
+            .map(|(_, binding)| binding)
+            .collect::<Vec<_>>();
+        let is_unused = |entry: &SyntaxNode| unused.iter().any(|b| b.entry == *entry);
+        let lambda = Lambda::cast(node.clone())?;
+        // callers may pass the entries of a pattern without `...`
+        let removable = lambda
+            .arg()
+            .and_then(Pattern::cast)
+            .is_some_and(|p| p.ellipsis());
+        let fixable = !is_flake_outputs(node, sess);
+        let mut report = self.report();
+        for binding in &unused {
+            let at = binding.ident.text_range();
+            let message = format!("`{}` is never used", binding.name);
+            report = match binding.kind {
+                BindingKind::Arg | BindingKind::PatEntry if !fixable => {
+                    report.diagnostic(at, message)
+                }
+                BindingKind::Arg => {
+                    let fix = Suggestion::new(at, make::ident("_").node().clone())
+                        .description("replace with `_`");
+                    report.suggest(at, message, fix)
+                }
+                // neighbouring unused entries are removed at once, by the
+                // first of them
+                BindingKind::PatEntry
+                    if removable
+                        && !previous_entry(&binding.entry).is_some_and(|e| is_unused(&e)) =>
+                {
+                    let mut run = vec![binding.entry.clone()];
+                    while let Some(next) = next_entry(run.last().unwrap()).filter(|e| is_unused(e))
+                    {
+                        run.push(next);
+                    }
+                    let names = run
+                        .iter()
+                        .filter_map(|e| unused.iter().find(|b| b.entry == *e))
+                        .map(|b| format!("`{}`", b.name))
+                        .collect::<Vec<_>>()
+                        .join(", ");
+                    let removed = entries_range(&run[0], run.last().unwrap());
+                    let fix = Suggestion::new(removed, make::empty().node().clone())
+                        .description(format!("remove {}", names));
+                    report.suggest(at, message, fix)
+                }
+                BindingKind::PatEntry => report.diagnostic(at, message),
+                _ => continue,
+            };
+        }
+        if report.diagnostics.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+
+// `outputs = { self, ... }: { };` in a flake, whose arguments are the
+// inputs of the flake
+fn is_flake_outputs(lambda: &SyntaxNode, sess: &SessionInfo) -> bool {
+    sess.mode() == Mode::Flake
+        && lambda
+            .parent()
+            .and_then(KeyValue::cast)
+            .is_some_and(|kv| first_key(&kv).as_deref() == Some("outputs"))
+}
+
+fn previous_entry(entry: &SyntaxNode) -> Option<SyntaxNode> {
+    entry
+        .siblings(Direction::Prev)
+        .skip(1)
+        .find(|n| n.kind() == SyntaxKind::NODE_PAT_ENTRY)
+}
+
+fn next_entry(entry: &SyntaxNode) -> Option<SyntaxNode> {
+    entry
+        .siblings(Direction::Next)
+        .skip(1)
+        .find(|n| n.kind() == SyntaxKind::NODE_PAT_ENTRY)
+}
+
+// the entries of a pattern from `first` to `last`, along with one of the
+// commas around them, and the whitespace that would be left over:
+//
+//   { a, b, c }    { a\n, b\n, c\n}    {\n  a,\n  b,\n}
+fn entries_range(first: &SyntaxNode, last: &SyntaxNode) -> TextRange {
+    let is_trivia = |e: &SyntaxElement| {
+        matches!(
+            e.kind(),
+            SyntaxKind::TOKEN_WHITESPACE | SyntaxKind::TOKEN_COMMENT
+        )
+    };
+    let neighbour = |entry: &SyntaxNode, direction| {
+        entry
+            .siblings_with_tokens(direction)
+            .skip(1)
+            .find(|e| !is_trivia(e))
+    };
+    let (prev, next) = (
+        neighbour(first, Direction::Prev),
+        neighbour(last, Direction::Next),
+    );
+    let range = first.text_range().cover(last.text_range());
+    let comma = |e: &Option<SyntaxElement>| match e {
+        Some(e) if e.kind() == SyntaxKind::TOKEN_COMMA => Some(e.text_range()),
+        _ => None,
+    };
+    // the end of whatever precedes `at`, skipping whitespace
+    let after_previous = |at: TextSize| {
+        first
+            .parent()
+            .and_then(|p| {
+                p.children_with_tokens()
+                    .filter(|e| e.text_range().end() <= at && !is_trivia(e))
+                    .last()
+            })
+            .map_or(at, |e| e.text_range().end())
+    };
+    match (comma(&prev), comma(&next)) {
+        // `a, b` on one line: the entry with the comma after it
+        (_, Some(next)) if !has_newline(last, range.end(), next.start()) => {
+            TextRange::new(after_previous(range.start()), next.end())
+        }
+        // `a\n, b`, or the last entry: the comma before it
+        (Some(prev), _) => TextRange::new(after_previous(prev.start()), range.end()),
+        // the first entry in `{ a\n, b }`: up to the next entry
+        (None, Some(next)) => {
+            let end = last
+                .siblings_with_tokens(Direction::Next)
+                .find(|e| e.text_range().start() >= next.end() && !is_trivia(e))
+                .map_or(next.end(), |e| e.text_range().start());
+            TextRange::new(range.start(), end)
+        }
+        (None, None) => TextRange::new(after_previous(range.start()), range.end()),
+    }
+}
+
+fn has_newline(entry: &SyntaxNode, from: TextSize, to: TextSize) -> bool {
+    let parent = match entry.parent() {
+        Some(p) => p,
+        None => return false,
+    };
+    parent
+        .children_with_tokens()
+        .filter(|e| e.text_range().start() >= from && e.text_range().end() <= to)
+        .any(|e| e.to_string().contains('\n'))
+}
@@ -0,0 +1,113 @@
+use crate::{
+    make,
+    scope::{BindingKind, Scopes},
+    session::SessionInfo,
+    utils::{first_key, with_preceeding_whitespace},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Inherit, KeyValue, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode,
+};
+
+/// ## What it does
+/// Checks for bindings of a `let` expression that are referenced neither
+/// by its body nor by the other bindings. Names starting with `_` are
+/// left alone.
+///
+/// ## Why is this bad?
+/// An unused binding is noise, and usually left over from a refactor or
+/// a sign that something else was meant to use it.
+///
+/// ## Example
+///
+/// ```nix
+/// let
+///   pname = "hello";
+///   version = "2.12";
+/// in
+/// pname
+/// ```
+///
+/// Remove the binding:
+///
+/// ```nix
+/// let
+///   pname = "hello";
+/// in
+/// pname
+/// ```
+#[lint(
+    name = "unused_let_binding",
+    note = "Found an unused binding",
+    code = 31,
+    match_with = SyntaxKind::NODE_LET_IN
+)]
+struct UnusedLetBinding;
+
+impl Rule for UnusedLetBinding {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let scopes = Scopes::new(node);
+        let unused = scopes
+            .bindings_of(node)
+            .filter(|(_, binding)| binding.kind == BindingKind::Let)
+            // `_` and names starting with it are unused on purpose
+            .filter(|(_, binding)| !binding.name.starts_with('_'))
+            .filter(|(idx, _)| scopes.uses(*idx).next().is_none())
+            .map(|(_, binding)| binding)
+            .collect::<Vec<_>>();
+        let mut report = self.report();
+        for binding in &unused {
+            let at = binding.ident.text_range();
+            let message = format!("`{}` is never used", binding.name);
+            let removed = if let Some(inherit) = Inherit::cast(binding.entry.clone()) {
+                let all_unused = inherit
+                    .idents()
+                    .all(|i| unused.iter().any(|b| b.ident == *i.node()));
+                // the statement goes with its first ident, the others are
+                // removed along with it
+                match inherit.idents().next() {
+                    Some(first) if all_unused && *first.node() == binding.ident => {
+                        Some(with_preceeding_whitespace(&binding.entry))
+                    }
+                    _ if all_unused => None,
+                    _ => Some(with_preceeding_whitespace(&binding.ident)),
+                }
+            } else if defined_once(node, &binding.name) {
+                Some(with_preceeding_whitespace(&binding.entry))
+            } else {
+                None
+            };
+            report = match removed {
+                Some(removed) => {
+                    let fix = Suggestion::new(removed, make::empty().node().clone())
+                        .description(format!("remove `{}`", binding.name));
+                    report.suggest(at, message, fix)
+                }
+                None => report.diagnostic(at, message),
+            };
+        }
+        if report.diagnostics.is_empty() {
+            None
+        } else {
+            Some(report)
+        }
+    }
+}
+
+// `a.b = 1; a.c = 2;` binds `a` through several entries, removing one of
+// them does not remove the binding
+fn defined_once(let_in: &SyntaxNode, name: &str) -> bool {
+    let_in
+        .children()
+        .filter_map(KeyValue::cast)
+        .filter(|kv| first_key(kv).as_deref() == Some(name))
+        .count()
+        == 1
+}
@@ -0,0 +1,120 @@
+use crate::{
+    make,
+    scope::{BindingKind, Scopes},
+    session::SessionInfo,
+    utils::{attr_name, with_preceeding_whitespace},
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{Lambda, Pattern, Select, TokenWrapper, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
+};
+
+/// ## What it does
+/// Checks for `@` bindings of argument patterns that are never used,
+/// or only used to select attributes the pattern already binds.
+///
+/// ## Why is this bad?
+/// The binding is noise, the pattern entries can be used directly.
+///
+/// ## Example
+///
+/// ```nix
+/// { stdenv, ... } @ args:
+/// args.stdenv.mkDerivation { }
+/// ```
+///
+/// Use the pattern entry, and drop the binding:
+///
+/// ```nix
+/// { stdenv, ... }:
+/// stdenv.mkDerivation { }
+/// ```
+#[lint(
+    name = "unused_pattern_bind",
+    note = "Found an unneeded pattern binding",
+    code = 33,
+    match_with = SyntaxKind::NODE_LAMBDA
+)]
+struct UnusedPatternBind;
+
+impl Rule for UnusedPatternBind {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let lambda = Lambda::cast(node.clone())?;
+        let pattern = Pattern::cast(lambda.arg()?)?;
+        // `args @ { ... }` is left to `redundant_pattern_bind`
+        pattern.entries().next()?;
+        let scopes = Scopes::new(node);
+        let (idx, binding) = scopes
+            .bindings_of(node)
+            .find(|(_, b)| b.kind == BindingKind::PatBind)?;
+        if binding.name.starts_with('_') {
+            return None;
+        }
+        let bind = binding.ident.parent()?;
+        let removed = if pattern.node().first_child().as_ref() == Some(&bind) {
+            // `args @ { ... }`, along with the whitespace after it
+            let end = bind
+                .next_sibling_or_token()
+                .filter(|t| t.kind() == SyntaxKind::TOKEN_WHITESPACE)
+                .map_or(bind.text_range().end(), |t| t.text_range().end());
+            TextRange::new(bind.text_range().start(), end)
+        } else {
+            with_preceeding_whitespace(&bind)
+        };
+        let at = binding.ident.text_range();
+        let uses = scopes.uses(idx).collect::<Vec<_>>();
+        if uses.is_empty() {
+            let message = format!("`{}` is never used", binding.name);
+            let fix = Suggestion::new(removed, make::empty().node().clone())
+                .description(format!("remove `{}`", binding.name));
+            return Some(self.report().suggest(at, message, fix));
+        }
+
+        // entries without a default, that `args.x` can be replaced with
+        let entries = pattern
+            .entries()
+            .filter(|e| e.default().is_none())
+            .filter_map(|e| Some(e.name()?.as_str().to_owned()))
+            .collect::<Vec<_>>();
+        let mut edits = vec![(removed, String::new())];
+        for reference in uses {
+            let select = reference.parent().and_then(Select::cast)?;
+            let name = attr_name(&select.index()?)?;
+            let is_or_default = matches!(
+                select.node().parent(),
+                Some(p) if p.kind() == SyntaxKind::NODE_OR_DEFAULT
+            );
+            if !entries.contains(&name)
+                || is_or_default
+                || shadowed(&scopes, node, reference, &name)
+            {
+                return None;
+            }
+            edits.push((select.node().text_range(), name));
+        }
+        let message = format!(
+            "`{}` is only used for attributes the pattern binds",
+            binding.name
+        );
+        let fix = Suggestion::new(
+            node.text_range(),
+            make::edited(&lambda, &edits).node().clone(),
+        )
+        .description("use the pattern entries instead");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
+
+// whether `name` is bound again between `lambda` and `reference`
+fn shadowed(scopes: &Scopes, lambda: &SyntaxNode, reference: &SyntaxNode, name: &str) -> bool {
+    scopes.bindings.iter().any(|b| {
+        b.name == name && b.scope != *lambda && reference.ancestors().any(|a| a == b.scope)
+    })
+}
@@ -1,15 +1,17 @@
-use crate::{make, session::SessionInfo, Metadata, Report, Rule, Suggestion};
+use crate::{make, session::SessionInfo, utils::bool_literal, Metadata, Report, Rule, Suggestion};
 
 use if_chain::if_chain;
 use macros::lint;
 use rnix::{
     types::{BinOp, BinOpKind, IfElse, Select, TypedNode},
-    NodeOrToken, SyntaxElement, SyntaxKind,
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
 };
 
 /// ## What it does
 /// Checks for expressions that use the "has attribute" operator: `?`,
-/// where the `or` operator would suffice.
+/// where the `or` operator would suffice: `if x ? a then x.a else d`,
+/// `x ? a && x.a` and `x ? a && x.a == v`, when `v` is a literal other
+/// than `null`.
 ///
 /// ## Why is this bad?
 /// The `or` operator is more readable.
@@ -24,18 +26,34 @@ use rnix::{
 /// ```nix
 /// x.a or some_default
 /// ```
+///
+/// Likewise for guarded comparisons:
+///
+/// ```nix
+/// x ? a && x.a == "foo"
+/// ```
+///
+/// can be written as:
+///
+/// ```nix
+/// (x.a or null) == "foo"
+/// ```
 #[lint(
     name = "useless_has_attr",
-    note = "This `if` expression can be simplified with `or`",
+    note = "This expression can be simplified with `or`",
     code = 19,
-    match_with = SyntaxKind::NODE_IF_ELSE
+    priority = 1,
+    match_with = [SyntaxKind::NODE_IF_ELSE, SyntaxKind::NODE_BIN_OP]
 )]
 struct UselessHasAttr;
 
 impl Rule for UselessHasAttr {
     fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
         if_chain! {
-            if let NodeOrToken::Node(node) = node;
             if let Some(if_else_expr) = IfElse::cast(node.clone());
             if let Some(condition_expr) = if_else_expr.condition();
             if let Some(default_expr) = if_else_expr.else_body();
@@ -72,13 +90,77 @@ impl Rule for UselessHasAttr {
                     "Consider using `{}` instead of this `if` expression",
                     replacement
                 );
+                let description = format!("replace with `{}`", replacement);
                 Some(
                     self.report()
-                        .suggest(at, message, Suggestion::new(at, replacement)),
+                        .suggest(at, message, Suggestion::new(at, replacement).description(description)),
                 )
             } else {
-                None
+                guarded_select(node).map(|(replacement, at)| {
+                    let message = format!(
+                        "Consider using `{}` instead of checking for the attribute",
+                        replacement
+                    );
+                    let description = format!("replace with `{}`", replacement);
+                    self.report()
+                        .suggest(at, message, Suggestion::new(at, replacement).description(description))
+                })
             }
         }
     }
 }
+
+// `set ? attr_path && set.attr_path`, or `set ? attr_path && set.attr_path == v`,
+// along with its replacement
+fn guarded_select(node: &SyntaxNode) -> Option<(SyntaxNode, TextRange)> {
+    let and_expr = BinOp::cast(node.clone())?;
+    if and_expr.operator()? != BinOpKind::And {
+        return None;
+    }
+    let guard = BinOp::cast(and_expr.lhs()?)?;
+    if guard.operator()? != BinOpKind::IsSet {
+        return None;
+    }
+    let (set, attr_path) = (guard.lhs()?, guard.rhs()?);
+    let select = make::select(&set, &attr_path);
+    let is_select = |expr: &SyntaxNode| expr.text() == select.node().text();
+
+    let rhs = and_expr.rhs()?;
+    let replacement = if is_select(&rhs) {
+        // `x.a` is a boolean wherever `x ? a && x.a` evaluates
+        let default = make::ident("false");
+        make::or_default(&set, &attr_path, default.node())
+            .node()
+            .clone()
+    } else {
+        let comparison = BinOp::cast(rhs)?;
+        if comparison.operator()? != BinOpKind::Equal {
+            return None;
+        }
+        let (lhs, rhs) = (comparison.lhs()?, comparison.rhs()?);
+        let value = if is_select(&lhs) {
+            rhs
+        } else if is_select(&rhs) {
+            lhs
+        } else {
+            return None;
+        };
+        // `null == v` would be true for a missing attribute
+        if !is_non_null_literal(&value) {
+            return None;
+        }
+        let null = make::ident("null");
+        let or_default = make::or_default(&set, &attr_path, null.node());
+        let or_default = make::parenthesize(or_default.node());
+        make::binary(or_default.node(), "==", &value).node().clone()
+    };
+    Some((make::replacing(&replacement, node), node.text_range()))
+}
+
+fn is_non_null_literal(node: &SyntaxNode) -> bool {
+    match node.kind() {
+        SyntaxKind::NODE_LITERAL | SyntaxKind::NODE_STRING | SyntaxKind::NODE_LIST => true,
+        SyntaxKind::NODE_IDENT => bool_literal(node).is_some(),
+        _ => false,
+    }
+}
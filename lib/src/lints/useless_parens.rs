@@ -73,7 +73,7 @@ fn do_thing(parsed_type_node: ParsedType) -> Option<Diagnostic> {
                 let at = value_range;
                 let message = "Useless parentheses around value in binding";
                 let replacement = inner;
-                Some(Diagnostic::suggest(at, message, Suggestion::new(at, replacement)))
+                Some(Diagnostic::suggest(at, message, Suggestion::new(at, replacement).description("remove the parentheses")))
             } else {
                 None
             }
@@ -87,7 +87,7 @@ fn do_thing(parsed_type_node: ParsedType) -> Option<Diagnostic> {
                 let at = body_range;
                 let message = "Useless parentheses around body of `let` expression";
                 let replacement = inner;
-                Some(Diagnostic::suggest(at, message, Suggestion::new(at, replacement)))
+                Some(Diagnostic::suggest(at, message, Suggestion::new(at, replacement).description("remove the parentheses")))
             } else {
                 None
             }
@@ -119,7 +119,7 @@ fn do_thing(parsed_type_node: ParsedType) -> Option<Diagnostic> {
                 let at = paren_expr_range;
                 let message = "Useless parentheses around primitive expression";
                 let replacement = parsed_inner.node().clone();
-                Some(Diagnostic::suggest(at, message, Suggestion::new(at, replacement)))
+                Some(Diagnostic::suggest(at, message, Suggestion::new(at, replacement).description("remove the parentheses")))
             } else {
                 None
             }
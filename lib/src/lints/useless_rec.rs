@@ -0,0 +1,80 @@
+use crate::{
+    make,
+    scope::{BindingKind, Scopes},
+    session::SessionInfo,
+    Metadata, Report, Rule, Suggestion,
+};
+
+use macros::lint;
+use rnix::{
+    types::{AttrSet, TypedNode},
+    NodeOrToken, SyntaxElement, SyntaxKind, TextRange,
+};
+
+/// ## What it does
+/// Checks for `rec` attribute sets where no attribute refers to
+/// another.
+///
+/// ## Why is this bad?
+/// `rec` makes a reader look for references between the attributes,
+/// and lets an attribute silently shadow a variable of the same name
+/// around the set.
+///
+/// ## Example
+///
+/// ```nix
+/// rec {
+///   pname = "hello";
+///   version = "2.12";
+/// }
+/// ```
+///
+/// Remove `rec`:
+///
+/// ```nix
+/// {
+///   pname = "hello";
+///   version = "2.12";
+/// }
+/// ```
+#[lint(
+    name = "useless_rec",
+    note = "Found an unneeded `rec`",
+    code = 37,
+    match_with = SyntaxKind::NODE_ATTR_SET
+)]
+struct UselessRec;
+
+impl Rule for UselessRec {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        if !AttrSet::cast(node.clone())?.recursive() {
+            return None;
+        }
+        let scopes = Scopes::new(node);
+        let used = scopes
+            .bindings_of(node)
+            .filter(|(_, b)| b.kind == BindingKind::Rec)
+            .any(|(idx, _)| scopes.uses(idx).next().is_some());
+        if used {
+            return None;
+        }
+        let rec = node
+            .first_token()
+            .filter(|t| t.kind() == SyntaxKind::TOKEN_REC)?;
+        // `rec` along with the whitespace after it
+        let end = rec
+            .next_token()
+            .filter(|t| t.kind() == SyntaxKind::TOKEN_WHITESPACE)
+            .map_or(rec.text_range().end(), |t| t.text_range().end());
+        let at = rec.text_range();
+        let removed = TextRange::new(at.start(), end);
+        let message = "No attribute of this set refers to another";
+        let fix =
+            Suggestion::new(removed, make::empty().node().clone()).description("remove `rec`");
+        Some(self.report().suggest(at, message, fix))
+    }
+}
@@ -0,0 +1,143 @@
+use crate::{
+    scope::{self, Binding, BindingKind, Scopes},
+    session::SessionInfo,
+    utils::attr_name,
+    Metadata, Report, Rule,
+};
+
+use macros::lint;
+use rnix::{
+    types::{AttrSet, EntryHolder, KeyValue, TokenWrapper, TypedNode, With},
+    NodeOrToken, SyntaxElement, SyntaxKind, SyntaxNode, TextRange,
+};
+
+/// ## What it does
+/// Checks for variables in the body of a `with` that are an attribute
+/// of its set, but are bound by a `let`, a `rec` set or a function
+/// argument, which take precedence over the `with`. This is only known
+/// when the set is written out, or is a variable bound to a set that
+/// is written out.
+///
+/// ## Why is this bad?
+/// A `with` never shadows a variable bound otherwise, however close it
+/// is to the variable, so the attribute of the set is not used.
+///
+/// ## Example
+///
+/// ```nix
+/// { version }:
+/// let
+///   release = { version = "1.0"; date = "2022-01-01"; };
+/// in
+/// with release; "${version} (${date})"
+/// ```
+///
+/// Select the attribute:
+///
+/// ```nix
+/// { version }:
+/// let
+///   release = { version = "1.0"; date = "2022-01-01"; };
+/// in
+/// "${release.version} (${release.date})"
+/// ```
+#[lint(
+    name = "with_shadowed",
+    note = "Found a variable bound by both a `with` and a binding",
+    code = 62,
+    match_with = SyntaxKind::NODE_WITH
+)]
+struct WithShadowed;
+
+impl Rule for WithShadowed {
+    fn validate(&self, node: &SyntaxElement, _sess: &SessionInfo) -> Option<Report> {
+        let node = match node {
+            NodeOrToken::Node(node) => node,
+            _ => return None,
+        };
+        let with = With::cast(node.clone())?;
+        let namespace = with.namespace()?;
+        let body = with.body()?;
+        let attrs = attributes(&namespace)?;
+        let set = if namespace.kind() == SyntaxKind::NODE_IDENT {
+            format!("`{}`", namespace)
+        } else {
+            "this set".to_owned()
+        };
+        let scopes = Scopes::new(&body);
+        let mut related = Vec::new();
+        let mut names = Vec::new();
+        for (ident, binding) in &scopes.references {
+            let name = ident.text().to_string();
+            if !attrs.contains(&name) {
+                continue;
+            }
+            let kind = match binding {
+                Some(binding) => scopes.bindings[*binding].kind,
+                None => match outer_binding(node, &name) {
+                    Some(binding) => binding.kind,
+                    None => continue,
+                },
+            };
+            let bound_by = match kind {
+                BindingKind::Let => "a `let` binding",
+                BindingKind::Rec => "a `rec` binding",
+                _ => "a function argument",
+            };
+            related.push((
+                ident.text_range(),
+                format!("This is {}, not the attribute of {}", bound_by, set),
+            ));
+            let name = format!("`{}`", name);
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        if names.is_empty() {
+            return None;
+        }
+        let at = TextRange::new(node.text_range().start(), body.text_range().start());
+        let subject = if namespace.kind() == SyntaxKind::NODE_IDENT {
+            set
+        } else {
+            "This set".to_owned()
+        };
+        let message = format!("{} does not provide {} here", subject, names.join(", "));
+        let report = self.report().diagnostic(at, message);
+        Some(
+            related
+                .into_iter()
+                .fold(report, |report, (at, message)| report.related(at, message)),
+        )
+    }
+}
+
+// the names of the attributes of `namespace`, when it is a set written
+// out, or a variable bound to one
+fn attributes(namespace: &SyntaxNode) -> Option<Vec<String>> {
+    let set = match namespace.kind() {
+        SyntaxKind::NODE_ATTR_SET => AttrSet::cast(namespace.clone())?,
+        SyntaxKind::NODE_IDENT => {
+            let binding = outer_binding(namespace, &namespace.text().to_string())?;
+            let kv = KeyValue::cast(binding.entry)?;
+            if kv.key()?.path().count() != 1 {
+                return None;
+            }
+            AttrSet::cast(kv.value()?)?
+        }
+        _ => return None,
+    };
+    let mut names = Vec::new();
+    for kv in set.entries() {
+        names.push(attr_name(&kv.key()?.path().next()?)?);
+    }
+    for inherit in set.inherits() {
+        names.extend(inherit.idents().map(|i| i.as_str().to_owned()));
+    }
+    Some(names)
+}
+
+// the binding of `name` around `node`
+fn outer_binding(node: &SyntaxNode, name: &str) -> Option<Binding> {
+    scope::lookup(&node.parent()?, name)
+}
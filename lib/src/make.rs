@@ -1,8 +1,9 @@
 use std::{fmt::Write, iter::IntoIterator};
 
+use crate::session::Style;
 use rnix::{
     types::{self, TokenWrapper, TypedNode},
-    SyntaxNode,
+    SyntaxKind, SyntaxNode, TextRange,
 };
 
 fn ast_from_text<N: TypedNode>(text: &str) -> N {
@@ -32,6 +33,84 @@ pub fn unary_not(node: &SyntaxNode) -> types::UnaryOp {
     ast_from_text(&format!("!{}", node))
 }
 
+/// `node` as a replacement for `at`, parenthesized if it would bind looser
+/// than the expression around `at` expects
+pub fn replacing(node: &SyntaxNode, at: &SyntaxNode) -> SyntaxNode {
+    let parent = match at.parent() {
+        Some(p) => p,
+        None => return node.clone(),
+    };
+    let allowed = match parent.kind() {
+        SyntaxKind::NODE_BIN_OP | SyntaxKind::NODE_UNARY_OP => precedence(&parent),
+        SyntaxKind::NODE_APPLY => 2,
+        SyntaxKind::NODE_SELECT | SyntaxKind::NODE_OR_DEFAULT => 1,
+        // list elements can not be applications
+        SyntaxKind::NODE_LIST => 2,
+        _ => u8::MAX,
+    };
+    if precedence(node) >= allowed {
+        parenthesize(node).node().clone()
+    } else {
+        node.clone()
+    }
+}
+
+// how loosely an expression binds, following the table of operators of the
+// nix manual, atoms bind the tightest
+fn precedence(node: &SyntaxNode) -> u8 {
+    use types::{BinOpKind::*, UnaryOpKind};
+    match node.kind() {
+        SyntaxKind::NODE_SELECT | SyntaxKind::NODE_OR_DEFAULT => 1,
+        SyntaxKind::NODE_APPLY => 2,
+        SyntaxKind::NODE_UNARY_OP => match types::UnaryOp::cast(node.clone()).map(|u| u.operator())
+        {
+            Some(UnaryOpKind::Negate) => 3,
+            _ => 8,
+        },
+        SyntaxKind::NODE_BIN_OP => {
+            match types::BinOp::cast(node.clone()).and_then(|b| b.operator()) {
+                Some(IsSet) => 4,
+                Some(Concat) => 5,
+                Some(Mul) | Some(Div) => 6,
+                Some(Add) | Some(Sub) => 7,
+                Some(Update) => 9,
+                Some(Less) | Some(LessOrEq) | Some(More) | Some(MoreOrEq) => 10,
+                Some(Equal) | Some(NotEqual) => 11,
+                Some(And) => 12,
+                Some(Or) => 13,
+                Some(Implication) | None => 14,
+            }
+        }
+        SyntaxKind::NODE_IF_ELSE
+        | SyntaxKind::NODE_LET_IN
+        | SyntaxKind::NODE_WITH
+        | SyntaxKind::NODE_ASSERT
+        | SyntaxKind::NODE_LAMBDA => 15,
+        _ => 0,
+    }
+}
+
+/// `!node`, parenthesizing `node` unless `!` applies to all of it as is
+pub fn negation(node: &SyntaxNode) -> types::UnaryOp {
+    let bare = match node.kind() {
+        SyntaxKind::NODE_APPLY
+        | SyntaxKind::NODE_PAREN
+        | SyntaxKind::NODE_IDENT
+        | SyntaxKind::NODE_SELECT => true,
+        // `!a ? b`
+        SyntaxKind::NODE_BIN_OP => matches!(
+            types::BinOp::cast(node.clone()).and_then(|b| b.operator()),
+            Some(types::BinOpKind::IsSet)
+        ),
+        _ => false,
+    };
+    if bare {
+        unary_not(node)
+    } else {
+        unary_not(parenthesize(node).node())
+    }
+}
+
 pub fn inherit_stmt<'a>(nodes: impl IntoIterator<Item = &'a types::Ident>) -> types::Inherit {
     let inherited_idents = nodes
         .into_iter()
@@ -57,21 +136,48 @@ pub fn attrset(
     inherits: impl IntoIterator<Item = types::Inherit>,
     entries: impl IntoIterator<Item = types::KeyValue>,
     recursive: bool,
+    style: &Style,
 ) -> types::AttrSet {
     let mut buffer = String::new();
+    let (indent, eol) = (style.indent_unit(), style.eol());
 
-    writeln!(buffer, "{}{{", if recursive { "rec " } else { "" }).unwrap();
+    write!(buffer, "{}{{{}", if recursive { "rec " } else { "" }, eol).unwrap();
     for inherit in inherits.into_iter() {
-        writeln!(buffer, "  {}", inherit.node().text()).unwrap();
+        write!(buffer, "{}{}{}", indent, inherit.node().text(), eol).unwrap();
     }
     for entry in entries.into_iter() {
-        writeln!(buffer, "  {}", entry.node().text()).unwrap();
+        write!(buffer, "{}{}{}", indent, entry.node().text(), eol).unwrap();
     }
     write!(buffer, "}}").unwrap();
 
     ast_from_text(&buffer)
 }
 
+/// `key = value;`
+pub fn key_value(key: &str, value: &SyntaxNode) -> types::KeyValue {
+    ast_from_text(&format!("{{ {} = {}; }}", key, value))
+}
+
+/// `key = { ... };` with one of `entries` per line, for a key-value
+/// indented by `indent`
+pub fn nested_key_value(
+    key: &str,
+    entries: &[String],
+    indent: &str,
+    style: &Style,
+) -> types::KeyValue {
+    let mut buffer = String::new();
+    let (unit, eol) = (style.indent_unit(), style.eol());
+
+    write!(buffer, "{} = {{{}", key, eol).unwrap();
+    for entry in entries {
+        write!(buffer, "{}{}{}{}", indent, unit, entry, eol).unwrap();
+    }
+    write!(buffer, "{}}};", indent).unwrap();
+
+    ast_from_text(&format!("{{ {} }}", buffer))
+}
+
 pub fn select(set: &SyntaxNode, index: &SyntaxNode) -> types::Select {
     ast_from_text(&format!("{}.{}", set, index))
 }
@@ -80,6 +186,11 @@ pub fn ident(text: &str) -> types::Ident {
     ast_from_text(text)
 }
 
+/// A path literal, `text` must be a valid path
+pub fn path(text: &str) -> types::Value {
+    ast_from_text(text)
+}
+
 pub fn empty() -> types::Root {
     ast_from_text("")
 }
@@ -89,6 +200,29 @@ pub fn binary(lhs: &SyntaxNode, op: &str, rhs: &SyntaxNode) -> types::BinOp {
     ast_from_text(&format!("{} {} {}", lhs, op, rhs))
 }
 
+/// `function argument`, parenthesizing the argument where needed
+pub fn apply(function: &SyntaxNode, argument: &SyntaxNode) -> types::Apply {
+    if precedence(argument) >= 2 {
+        ast_from_text(&format!("{} {}", function, parenthesize(argument).node()))
+    } else {
+        ast_from_text(&format!("{} {}", function, argument))
+    }
+}
+
 pub fn or_default(set: &SyntaxNode, index: &SyntaxNode, default: &SyntaxNode) -> types::OrDefault {
     ast_from_text(&format!("{}.{} or {}", set, index, default))
 }
+
+/// `node` with each range of `edits`, given as offsets in the file, replaced
+/// by its text
+pub fn edited<N: TypedNode>(node: &N, edits: &[(TextRange, String)]) -> N {
+    let start = node.node().text_range().start();
+    let mut text = node.node().to_string();
+    let mut edits = edits.iter().collect::<Vec<_>>();
+    edits.sort_by_key(|(at, _)| std::cmp::Reverse(at.start()));
+    for (at, replacement) in edits {
+        let at = *at - start;
+        text.replace_range(std::ops::Range::<usize>::from(at), replacement);
+    }
+    ast_from_text(&text)
+}
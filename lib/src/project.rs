@@ -0,0 +1,309 @@
+//! Cross-file analysis. A `Project` is the set of linted files along
+//! with the relative imports between them, lints that need to see
+//! several files implement `Rule::check_project`.
+
+use std::{
+    collections::HashMap,
+    path::{Component, Path, PathBuf},
+};
+
+use crate::{session::SessionInfo, Report};
+
+use rnix::{
+    types::{Apply, Ident, KeyValue, List, TokenWrapper, TypedNode, Value},
+    value::{Anchor, Value as ParsedValue},
+    SyntaxKind, SyntaxNode,
+};
+
+/// How a file is brought in by another
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum ImportKind {
+    /// `import ./foo.nix`
+    Import,
+    /// `callPackage ./foo.nix { }`
+    CallPackage,
+    /// `imports = [ ./foo.nix ];` in a NixOS module
+    Module,
+}
+
+/// A relative path imported by a file
+#[derive(Debug)]
+pub struct Import {
+    pub kind: ImportKind,
+    /// The path literal
+    pub path: SyntaxNode,
+    /// The path with `import` or `callPackage` applied to it, the path
+    /// literal itself for module imports
+    pub expr: SyntaxNode,
+    /// The file of the project this path resolves to, if any
+    pub target: Option<usize>,
+}
+
+impl Import {
+    /// Argument the imported function is called with: the `{ ... }` in
+    /// `import ./foo.nix { ... }` or `callPackage ./foo.nix { ... }`
+    pub fn arg(&self) -> Option<SyntaxNode> {
+        if self.kind == ImportKind::Module {
+            return None;
+        }
+        let apply = Apply::cast(self.expr.parent()?)?;
+        if apply.lambda()? == self.expr {
+            apply.value()
+        } else {
+            None
+        }
+    }
+}
+
+/// A file of the project
+#[derive(Debug)]
+pub struct File {
+    pub path: PathBuf,
+    /// Root of the syntax tree, possibly with errors
+    pub root: SyntaxNode,
+    pub sess: SessionInfo,
+    pub imports: Vec<Import>,
+}
+
+impl File {
+    /// The expression this file evaluates to
+    pub fn expr(&self) -> Option<SyntaxNode> {
+        self.root.first_child()
+    }
+}
+
+/// Findings of a lint in one of the files of a project
+#[derive(Debug)]
+pub struct ProjectReport {
+    /// Index of the file in `Project::files`
+    pub file: usize,
+    pub report: Report,
+}
+
+/// Files being linted and the import graph between them
+#[derive(Debug, Default)]
+pub struct Project {
+    files: Vec<File>,
+    roots: Vec<usize>,
+}
+
+impl Project {
+    /// Parse every file and resolve their relative imports against each other,
+    /// files are indexed in the order they are given
+    pub fn new<'a>(files: impl IntoIterator<Item = (PathBuf, &'a str, SessionInfo)>) -> Self {
+        let mut files = files
+            .into_iter()
+            .map(|(path, src, sess)| {
+                let root = rnix::parse(src).node();
+                let imports = collect_imports(&root);
+                File {
+                    path,
+                    root,
+                    sess,
+                    imports,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let index = files
+            .iter()
+            .enumerate()
+            .map(|(idx, f)| (normalize(&f.path), idx))
+            .collect::<HashMap<_, _>>();
+        for file in files.iter_mut() {
+            let dir = file.path.parent().unwrap_or_else(|| Path::new(""));
+            for import in file.imports.iter_mut() {
+                let target = normalize(&dir.join(import.path.to_string()));
+                import.target = index
+                    .get(&target)
+                    .or_else(|| index.get(&target.join("default.nix")))
+                    .copied();
+            }
+        }
+        Self {
+            files,
+            roots: Vec::new(),
+        }
+    }
+
+    /// Set the entry points of the project, such as `flake.nix` or
+    /// `default.nix`, paths that are not files of the project are ignored
+    pub fn with_roots(mut self, roots: impl IntoIterator<Item = PathBuf>) -> Self {
+        let roots = roots.into_iter().map(|r| normalize(&r)).collect::<Vec<_>>();
+        self.roots = (0..self.files.len())
+            .filter(|&idx| roots.contains(&normalize(&self.files[idx].path)))
+            .collect();
+        self
+    }
+
+    /// Indices of the files that are entry points of the project
+    pub fn roots(&self) -> &[usize] {
+        &self.roots
+    }
+
+    /// Whether each file is imported, directly or not, from a root
+    pub fn reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.files.len()];
+        let mut stack = self.roots.clone();
+        while let Some(idx) = stack.pop() {
+            if !reachable[idx] {
+                reachable[idx] = true;
+                stack.extend(self.files[idx].imports.iter().filter_map(|i| i.target));
+            }
+        }
+        reachable
+    }
+
+    pub fn files(&self) -> &[File] {
+        &self.files
+    }
+
+    pub fn file(&self, idx: usize) -> &File {
+        &self.files[idx]
+    }
+
+    /// Imports that resolve to the file at `idx`, along with the importing file
+    pub fn importers(&self, idx: usize) -> impl Iterator<Item = (usize, &Import)> {
+        self.files.iter().enumerate().flat_map(move |(from, file)| {
+            file.imports
+                .iter()
+                .filter(move |i| i.target == Some(idx))
+                .map(move |i| (from, i))
+        })
+    }
+}
+
+// resolve `.` and `..` without touching the filesystem
+fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => (),
+            Component::ParentDir
+                if matches!(out.components().next_back(), Some(Component::Normal(_))) =>
+            {
+                out.pop();
+            }
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Whether `node` is a relative path literal such as `./foo.nix`
+pub fn is_relative_path(node: &SyntaxNode) -> bool {
+    Value::cast(node.clone())
+        .and_then(|v| v.to_value().ok())
+        .is_some_and(|v| matches!(v, ParsedValue::Path(Anchor::Relative, _)))
+}
+
+fn import_kind(lambda: &str) -> Option<ImportKind> {
+    match lambda {
+        "import" | "builtins.import" => Some(ImportKind::Import),
+        l if l == "callPackage" || l.ends_with(".callPackage") => Some(ImportKind::CallPackage),
+        _ => None,
+    }
+}
+
+fn collect_imports(root: &SyntaxNode) -> Vec<Import> {
+    let mut imports = Vec::new();
+    for node in root.descendants() {
+        match node.kind() {
+            SyntaxKind::NODE_APPLY => {
+                let apply = Apply::cast(node.clone()).unwrap();
+                let (lambda, value) = match (apply.lambda(), apply.value()) {
+                    (Some(l), Some(v)) => (l, v),
+                    _ => continue,
+                };
+                if let Some(kind) = import_kind(&lambda.to_string()) {
+                    if is_relative_path(&value) {
+                        imports.push(Import {
+                            kind,
+                            path: value,
+                            expr: node,
+                            target: None,
+                        });
+                    }
+                }
+            }
+            SyntaxKind::NODE_KEY_VALUE => {
+                let key_value = KeyValue::cast(node.clone()).unwrap();
+                let is_imports = key_value
+                    .key()
+                    .map(|k| k.path().collect::<Vec<_>>())
+                    .is_some_and(|path| {
+                        path.len() == 1
+                            && Ident::cast(path[0].clone()).is_some_and(|i| i.as_str() == "imports")
+                    });
+                if let (true, Some(list)) = (is_imports, key_value.value().and_then(List::cast)) {
+                    for item in list.items().filter(is_relative_path) {
+                        imports.push(Import {
+                            kind: ImportKind::Module,
+                            path: item.clone(),
+                            expr: item,
+                            target: None,
+                        });
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    imports
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::Version;
+
+    fn project(files: &[(&str, &str)]) -> Project {
+        let sess = SessionInfo::from_version(Version::new(2, 4, None));
+        Project::new(
+            files
+                .iter()
+                .map(|(p, s)| (PathBuf::from(p), *s, sess.clone())),
+        )
+    }
+
+    #[test]
+    fn resolves_imports() {
+        let project = project(&[
+            (
+                "./default.nix",
+                "{ a = import ./lib { }; b = pkgs.callPackage ../x.nix { }; }",
+            ),
+            (
+                "./lib/default.nix",
+                "{ imports = [ ./../default.nix ./gone.nix ]; }",
+            ),
+        ]);
+        let imports = &project.file(0).imports;
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].kind, ImportKind::Import);
+        assert_eq!(imports[0].target, Some(1));
+        assert_eq!(
+            imports[0].arg().map(|a| a.to_string()),
+            Some("{ }".to_owned())
+        );
+        assert_eq!(imports[1].kind, ImportKind::CallPackage);
+        assert_eq!(imports[1].target, None);
+        let imports = &project.file(1).imports;
+        assert_eq!(imports.len(), 2);
+        assert_eq!(imports[0].target, Some(0));
+        assert_eq!(imports[1].target, None);
+        assert_eq!(project.importers(0).count(), 1);
+    }
+
+    #[test]
+    fn reachable_from_roots() {
+        let project = project(&[
+            ("default.nix", "import ./a.nix"),
+            ("a.nix", "{ }"),
+            ("b.nix", "import ./a.nix"),
+        ])
+        .with_roots(vec![PathBuf::from("./default.nix")]);
+        assert_eq!(project.roots(), &[0]);
+        assert_eq!(project.reachable(), vec![true, true, false]);
+    }
+}
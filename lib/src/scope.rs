@@ -0,0 +1,302 @@
+//! Resolution of the variables of an expression to the bindings that
+//! introduce them
+
+use crate::utils::{attr_name, is_reference};
+
+use rnix::{
+    types::{AttrSet, Inherit, KeyValue, Lambda, Pattern, TokenWrapper, TypedNode},
+    SyntaxKind, SyntaxNode,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingKind {
+    /// `let a = 1; in a`, or `let { a = 1; body = a; }`
+    Let,
+    /// `rec { a = 1; b = a; }`
+    Rec,
+    /// `a: a`
+    Arg,
+    /// `{ a }: a`
+    PatEntry,
+    /// `{ ... } @ a: a`
+    PatBind,
+}
+
+#[derive(Debug)]
+pub struct Binding {
+    pub name: String,
+    pub kind: BindingKind,
+    /// The identifier naming the variable where it is bound, the first
+    /// attribute of the key for `let` and `rec`
+    pub ident: SyntaxNode,
+    /// The key-value, `inherit`, pattern entry or lambda binding the variable
+    pub entry: SyntaxNode,
+    /// The `let`, `rec` set or lambda bringing the variable in scope
+    pub scope: SyntaxNode,
+}
+
+impl Binding {
+    fn new(
+        name: String,
+        kind: BindingKind,
+        ident: SyntaxNode,
+        entry: &SyntaxNode,
+        scope: &SyntaxNode,
+    ) -> Self {
+        Self {
+            name,
+            kind,
+            ident,
+            entry: entry.clone(),
+            scope: scope.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Scopes {
+    pub bindings: Vec<Binding>,
+    /// Identifiers referring to a variable, with the binding they resolve
+    /// to, or `None` if it is bound outside the analysed expression, by a
+    /// `with`, or is a builtin
+    pub references: Vec<(SyntaxNode, Option<usize>)>,
+}
+
+impl Scopes {
+    /// Resolve every variable of `root`
+    pub fn new(root: &SyntaxNode) -> Self {
+        let mut scopes = Self::default();
+        scopes.walk(root, &mut Vec::new());
+        scopes
+    }
+
+    /// References resolving to `binding`
+    pub fn uses(&self, binding: usize) -> impl Iterator<Item = &SyntaxNode> {
+        self.references
+            .iter()
+            .filter(move |(_, b)| *b == Some(binding))
+            .map(|(ident, _)| ident)
+    }
+
+    /// Bindings brought in scope by `scope`, with their index
+    pub fn bindings_of<'a>(
+        &'a self,
+        scope: &'a SyntaxNode,
+    ) -> impl Iterator<Item = (usize, &'a Binding)> {
+        self.bindings
+            .iter()
+            .enumerate()
+            .filter(move |(_, b)| b.scope == *scope)
+    }
+
+    fn resolve(&self, name: &str, env: &[usize]) -> Option<usize> {
+        env.iter()
+            .rev()
+            .copied()
+            .find(|&b| self.bindings[b].name == name)
+    }
+
+    fn bind(&mut self, env: &mut Vec<usize>, mark: usize, binding: Binding) {
+        // a name bound twice in the same scope keeps its first binding
+        if self.resolve(&binding.name, &env[mark..]).is_some() {
+            return;
+        }
+        env.push(self.bindings.len());
+        self.bindings.push(binding);
+    }
+
+    fn walk(&mut self, node: &SyntaxNode, env: &mut Vec<usize>) {
+        let mark = env.len();
+        match node.kind() {
+            SyntaxKind::NODE_LET_IN | SyntaxKind::NODE_LEGACY_LET | SyntaxKind::NODE_ATTR_SET
+                if is_scope(node) =>
+            {
+                self.walk_recursive(node, env)
+            }
+            SyntaxKind::NODE_LAMBDA => {
+                for binding in bindings(node) {
+                    self.bind(env, mark, binding);
+                }
+                for child in node.children() {
+                    self.walk(&child, env);
+                }
+            }
+            SyntaxKind::NODE_IDENT => {
+                if is_reference(node) {
+                    let binding = self.resolve(&node.text().to_string(), env);
+                    self.references.push((node.clone(), binding));
+                }
+            }
+            _ => {
+                for child in node.children() {
+                    self.walk(&child, env);
+                }
+            }
+        }
+        env.truncate(mark);
+    }
+
+    // `let` and `rec` sets, where bindings are in scope of each other
+    fn walk_recursive(&mut self, node: &SyntaxNode, env: &mut Vec<usize>) {
+        let mark = env.len();
+        for binding in bindings(node) {
+            self.bind(env, mark, binding);
+        }
+        for child in node.children() {
+            match Inherit::cast(child.clone()) {
+                // `inherit a;` refers to the `a` outside of this scope
+                Some(inherit) if inherit.from().is_none() => {
+                    for ident in inherit.idents() {
+                        let binding = self.resolve(ident.as_str(), &env[..mark]);
+                        self.references.push((ident.node().clone(), binding));
+                    }
+                }
+                _ => self.walk(&child, env),
+            }
+        }
+    }
+}
+
+/// Whether `node` brings variables in scope: a `let`, a `rec` set or a
+/// lambda
+pub fn is_scope(node: &SyntaxNode) -> bool {
+    match node.kind() {
+        SyntaxKind::NODE_LET_IN | SyntaxKind::NODE_LEGACY_LET | SyntaxKind::NODE_LAMBDA => true,
+        SyntaxKind::NODE_ATTR_SET => {
+            matches!(AttrSet::cast(node.clone()), Some(s) if s.recursive())
+        }
+        _ => false,
+    }
+}
+
+/// The bindings `node` brings in scope, without looking into its
+/// children, in order. A name bound twice is listed twice.
+pub fn bindings(node: &SyntaxNode) -> Vec<Binding> {
+    let mut bindings = Vec::new();
+    let kind = match node.kind() {
+        SyntaxKind::NODE_LAMBDA => {
+            let arg = match Lambda::cast(node.clone()).and_then(|l| l.arg()) {
+                Some(arg) => arg,
+                None => return bindings,
+            };
+            if arg.kind() == SyntaxKind::NODE_IDENT {
+                let name = arg.text().to_string();
+                bindings.push(Binding::new(name, BindingKind::Arg, arg, node, node));
+            } else if let Some(pattern) = Pattern::cast(arg) {
+                for entry in pattern.entries() {
+                    if let Some(name) = entry.name() {
+                        bindings.push(Binding::new(
+                            name.as_str().to_owned(),
+                            BindingKind::PatEntry,
+                            name.node().clone(),
+                            entry.node(),
+                            node,
+                        ));
+                    }
+                }
+                if let Some(at) = pattern.at() {
+                    bindings.push(Binding::new(
+                        at.as_str().to_owned(),
+                        BindingKind::PatBind,
+                        at.node().clone(),
+                        node,
+                        node,
+                    ));
+                }
+            }
+            return bindings;
+        }
+        SyntaxKind::NODE_ATTR_SET if is_scope(node) => BindingKind::Rec,
+        SyntaxKind::NODE_LET_IN | SyntaxKind::NODE_LEGACY_LET => BindingKind::Let,
+        _ => return bindings,
+    };
+    for child in node.children() {
+        if let Some(kv) = KeyValue::cast(child.clone()) {
+            let ident = kv.key().and_then(|k| k.path().next());
+            if let Some((name, ident)) = ident.and_then(|i| Some((attr_name(&i)?, i))) {
+                bindings.push(Binding::new(name, kind, ident, &child, node));
+            }
+        } else if let Some(inherit) = Inherit::cast(child.clone()) {
+            for ident in inherit.idents() {
+                let name = ident.as_str().to_owned();
+                bindings.push(Binding::new(name, kind, ident.node().clone(), &child, node));
+            }
+        }
+    }
+    bindings
+}
+
+/// The innermost binding of `name` in scope at `node`, found by walking
+/// up from `node` without resolving references: the binding a `let` or
+/// pattern entry defines is in scope in its own value or default
+pub fn lookup(node: &SyntaxNode, name: &str) -> Option<Binding> {
+    node.ancestors()
+        .filter(is_scope)
+        .find_map(|a| bindings(&a).into_iter().find(|b| b.name == name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // the name of the binding each reference of `src` resolves to, with
+    // the kind of that binding
+    fn resolved(src: &str) -> Vec<(String, Option<BindingKind>)> {
+        let root = rnix::parse(src).node();
+        let scopes = Scopes::new(&root);
+        scopes
+            .references
+            .iter()
+            .map(|(ident, b)| {
+                let kind = b.map(|b| scopes.bindings[b].kind);
+                (ident.text().to_string(), kind)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shadowing() {
+        let src = "let a = 1; in { a, b ? a }: [ a b c ]";
+        assert_eq!(
+            resolved(src),
+            vec![
+                ("a".into(), Some(BindingKind::PatEntry)),
+                ("a".into(), Some(BindingKind::PatEntry)),
+                ("b".into(), Some(BindingKind::PatEntry)),
+                ("c".into(), None),
+            ]
+        );
+    }
+
+    #[test]
+    fn inherit_and_keys() {
+        let src = "x: let inherit x; y = { x = 1; }.x; in rec { z = y; w = z; }";
+        assert_eq!(
+            resolved(src),
+            vec![
+                ("x".into(), Some(BindingKind::Arg)),
+                ("y".into(), Some(BindingKind::Let)),
+                ("z".into(), Some(BindingKind::Rec)),
+            ]
+        );
+    }
+
+    #[test]
+    fn lookup_innermost() {
+        let src = "a: let a = 1; b = a; in { a ? b }: a";
+        let root = rnix::parse(src).node();
+        let kind_at = |offset: u32| {
+            let ident = root
+                .descendants()
+                .find(|n| {
+                    n.kind() == SyntaxKind::NODE_IDENT && n.text_range().start() == offset.into()
+                })
+                .unwrap();
+            lookup(&ident, "a").map(|b| b.kind)
+        };
+        // `b = a`, `a ? b` and the body
+        assert_eq!(kind_at(18), Some(BindingKind::Let));
+        assert_eq!(kind_at(30), Some(BindingKind::PatEntry));
+        assert_eq!(kind_at(35), Some(BindingKind::PatEntry));
+    }
+}
@@ -1,4 +1,10 @@
-use std::{cmp::Ordering, str::FromStr};
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fmt,
+    path::{Component, Path, PathBuf},
+    str::FromStr,
+};
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 pub struct Version {
@@ -7,10 +13,30 @@ pub struct Version {
     patch: Option<u16>,
 }
 
+impl Version {
+    pub const fn new(major: u16, minor: u16, patch: Option<u16>) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+        if let Some(patch) = self.patch {
+            write!(f, ".{}", patch)?;
+        }
+        Ok(())
+    }
+}
+
 impl Ord for Version {
     fn cmp(&self, other: &Self) -> Ordering {
-        let score = |v: &Version| v.major * 100 + v.minor * 10 + v.patch.unwrap_or(0);
-        score(self).cmp(&score(other))
+        let key = |v: &Version| (v.major, v.minor, v.patch.unwrap_or(0));
+        key(self).cmp(&key(other))
     }
 }
 
@@ -47,19 +73,229 @@ impl FromStr for Version {
     }
 }
 
+/// The kind of code being linted, lints specific to a kind of code
+/// are enabled only in its mode (see the `modes` key of the `lint` macro)
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub enum Mode {
+    /// Plain Nix expressions
+    #[default]
+    Plain,
+    /// Package expressions in the style of nixpkgs
+    Nixpkgs,
+    /// NixOS modules
+    NixosModule,
+    /// Flakes, i.e. `flake.nix` files
+    Flake,
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Self::Plain => "plain",
+            Self::Nixpkgs => "nixpkgs",
+            Self::NixosModule => "nixos",
+            Self::Flake => "flake",
+        })
+    }
+}
+
+impl FromStr for Mode {
+    type Err = ();
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "plain" => Ok(Self::Plain),
+            "nixpkgs" => Ok(Self::Nixpkgs),
+            "nixos" => Ok(Self::NixosModule),
+            "flake" => Ok(Self::Flake),
+            _ => Err(()),
+        }
+    }
+}
+
+/// What a suggested rewrite relies on, see `Report::suggest_newest`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Since {
+    /// Works with any version of Nix and nixpkgs
+    Always,
+    /// Needs at least this version of Nix
+    Nix(Version),
+    /// Needs at least this version of nixpkgs
+    Nixpkgs(Version),
+}
+
+/// Indentation used by a file, see `Style`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum Indent {
+    Spaces(usize),
+    Tab,
+}
+
+/// Line terminator used by a file, see `Style`
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum EndOfLine {
+    Lf,
+    CrLf,
+    Cr,
+}
+
+impl EndOfLine {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+            Self::Cr => "\r",
+        }
+    }
+}
+
+/// Whitespace conventions of the file being linted (usually read from
+/// `.editorconfig`), followed by the nodes built for suggestions
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct Style {
+    pub indent: Indent,
+    pub end_of_line: EndOfLine,
+    /// Whether the file should end with a newline, `None` leaves it as is
+    pub final_newline: Option<bool>,
+}
+
+impl Default for Style {
+    fn default() -> Self {
+        Self {
+            indent: Indent::Spaces(2),
+            end_of_line: EndOfLine::Lf,
+            final_newline: None,
+        }
+    }
+}
+
+impl Style {
+    /// One level of indentation
+    pub fn indent_unit(&self) -> String {
+        match self.indent {
+            Indent::Spaces(n) => " ".repeat(n),
+            Indent::Tab => "\t".to_owned(),
+        }
+    }
+
+    pub fn eol(&self) -> &'static str {
+        self.end_of_line.as_str()
+    }
+}
+
+/// Options of lints by lint name and option name, every value is a list
+/// of strings, a single value being a list of one
+pub type LintOptions = HashMap<String, HashMap<String, Vec<String>>>;
+
 #[non_exhaustive]
+#[derive(Clone, Debug)]
 pub struct SessionInfo {
     nix_version: Version,
+    nixpkgs_version: Option<Version>,
+    mode: Mode,
+    style: Style,
+    file: Option<PathBuf>,
+    generated: Vec<String>,
+    options: LintOptions,
 }
 
 impl SessionInfo {
     pub fn from_version(nix_version: Version) -> Self {
-        Self { nix_version }
+        Self {
+            nix_version,
+            nixpkgs_version: None,
+            mode: Mode::default(),
+            style: Style::default(),
+            file: None,
+            generated: Vec::new(),
+            options: LintOptions::new(),
+        }
+    }
+
+    pub fn with_nixpkgs_version(mut self, nixpkgs_version: Version) -> Self {
+        self.nixpkgs_version = Some(nixpkgs_version);
+        self
+    }
+
+    pub fn with_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_style(mut self, style: Style) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// The file being linted, unknown when reading from stdin
+    pub fn with_file(mut self, file: &Path) -> Self {
+        self.file = Some(file.to_owned());
+        self
+    }
+
+    /// Paths produced by builds or code generation, that need not exist
+    /// when linting, such as `result`
+    pub fn with_generated(mut self, generated: Vec<String>) -> Self {
+        self.generated = generated;
+        self
+    }
+
+    /// Options set for individual lints, see `option`
+    pub fn with_options(mut self, options: LintOptions) -> Self {
+        self.options = options;
+        self
     }
 
     pub fn version(&self) -> &Version {
         &self.nix_version
     }
+
+    pub fn nixpkgs_version(&self) -> Option<&Version> {
+        self.nixpkgs_version.as_ref()
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn style(&self) -> &Style {
+        &self.style
+    }
+
+    pub fn file(&self) -> Option<&Path> {
+        self.file.as_deref()
+    }
+
+    /// The values of the option `name` of `lint`, if set
+    pub fn option(&self, lint: &str, name: &str) -> Option<&[String]> {
+        self.options.get(lint)?.get(name).map(Vec::as_slice)
+    }
+
+    /// Whether `path` is, or is inside of, one of the generated paths
+    pub fn is_generated(&self, path: &Path) -> bool {
+        let components = |p: &Path| {
+            p.components()
+                .filter_map(|c| match c {
+                    Component::Normal(n) => Some(n.to_owned()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+        let path = components(path);
+        self.generated.iter().any(|g| {
+            let g = components(Path::new(g));
+            !g.is_empty() && path.windows(g.len()).any(|w| w == g.as_slice())
+        })
+    }
+
+    /// Whether the targeted versions support a rewrite, an unknown
+    /// nixpkgs version is assumed to be recent
+    pub fn supports(&self, since: Since) -> bool {
+        match since {
+            Since::Always => true,
+            Since::Nix(v) => self.nix_version >= v,
+            Since::Nixpkgs(v) => self.nixpkgs_version.is_none_or(|n| n >= v),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -91,4 +327,34 @@ mod tests {
         let v2 = "2.4pre20211006_53e4794".parse::<Version>().ok();
         assert!(v2 >= v1);
     }
+
+    #[test]
+    fn supports() {
+        let v = |s: &str| s.parse::<Version>().unwrap();
+        let sess = SessionInfo::from_version(v("2.4"));
+        assert!(sess.supports(Since::Always));
+        assert!(sess.supports(Since::Nix(v("2.3"))));
+        assert!(!sess.supports(Since::Nix(v("2.5"))));
+        assert!(sess.supports(Since::Nixpkgs(v("23.05"))));
+        let sess = sess.with_nixpkgs_version(v("21.11"));
+        assert!(!sess.supports(Since::Nixpkgs(v("23.05"))));
+    }
+
+    #[test]
+    fn generated() {
+        let sess = SessionInfo::from_version(Version::new(2, 4, None))
+            .with_generated(vec!["result".into(), "gen/deps.nix".into()]);
+        assert!(sess.is_generated(Path::new("./result/bin/hello")));
+        assert!(sess.is_generated(Path::new("../gen/deps.nix")));
+        assert!(!sess.is_generated(Path::new("./results.nix")));
+    }
+
+    #[test]
+    fn compare_patch() {
+        let v1 = "2.3.17".parse::<Version>().ok();
+        let v2 = "2.4".parse::<Version>().ok();
+        let v3 = "2.18.1".parse::<Version>().ok();
+        assert!(v1 < v2);
+        assert!(v2 < v3);
+    }
 }
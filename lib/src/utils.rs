@@ -1,4 +1,80 @@
-use rnix::{SyntaxKind, SyntaxNode, TextRange};
+use rnix::{
+    types::{
+        Apply, AttrSet, BinOp, BinOpKind, Ident, KeyValue, Lambda, LetIn, Paren, Select, Str,
+        TokenWrapper, TypedNode, Wrapper,
+    },
+    StrPart, SyntaxKind, SyntaxNode, TextRange,
+};
+
+/// Variables bound without `builtins.`, which a `with` cannot shadow
+pub const GLOBALS: &[&str] = &[
+    "abort",
+    "baseNameOf",
+    "builtins",
+    "derivation",
+    "dirOf",
+    "false",
+    "fetchGit",
+    "fetchMercurial",
+    "fetchTarball",
+    "fetchTree",
+    "fromTOML",
+    "import",
+    "isNull",
+    "map",
+    "null",
+    "placeholder",
+    "removeAttrs",
+    "scopedImport",
+    "throw",
+    "toString",
+    "true",
+];
+
+/// Builders taking the attributes of a derivation
+pub const BUILDERS: &[&str] = &[
+    "mkDerivation",
+    "buildPythonPackage",
+    "buildPythonApplication",
+    "buildGoModule",
+    "buildRustPackage",
+    "buildNpmPackage",
+];
+
+/// Keywords, which are quoted as attribute names
+const KEYWORDS: &[&str] = &[
+    "assert", "else", "if", "in", "inherit", "let", "or", "rec", "then", "with",
+];
+
+/// Functions known to return a string
+const STRING_FUNCTIONS: &[&str] = &[
+    "toString",
+    "concatStrings",
+    "concatStringsSep",
+    "concatMapStrings",
+    "concatMapStringsSep",
+    "optionalString",
+    "replaceStrings",
+    "substring",
+    "toLower",
+    "toUpper",
+    "getExe",
+    "getExe'",
+    "escapeShellArg",
+    "escapeShellArgs",
+    "makeBinPath",
+    "makeLibraryPath",
+    "toJSON",
+    "readFile",
+];
+
+/// Builtins that a sublibrary of nixpkgs' `lib` also has, such as
+/// `lib.lists.length`, besides `lib` itself
+const LIB_BUILTINS: &[(&str, &str)] = &[
+    ("attrNames", "attrsets"),
+    ("attrValues", "attrsets"),
+    ("length", "lists"),
+];
 
 pub fn with_preceeding_whitespace(node: &SyntaxNode) -> TextRange {
     let start = node
@@ -14,3 +90,263 @@ pub fn with_preceeding_whitespace(node: &SyntaxNode) -> TextRange {
     let end = node.text_range().end();
     TextRange::new(start, end)
 }
+
+/// Whether the identifier `ident` refers to a variable, as opposed to
+/// naming an attribute (`{ a = 1; }`, `x.a`, `inherit (x) a;`) or
+/// binding a function argument (`a: a`, `{ a }: a`)
+pub fn is_reference(ident: &SyntaxNode) -> bool {
+    let parent = match ident.parent() {
+        Some(p) => p,
+        None => return true,
+    };
+    let is_first = parent.first_child().as_ref() == Some(ident);
+    match parent.kind() {
+        SyntaxKind::NODE_KEY | SyntaxKind::NODE_PAT_BIND => false,
+        // `x.a`: only `x` is a reference
+        SyntaxKind::NODE_SELECT => is_first,
+        // `{ a ? b }: c` and `a: b`: `a` is bound, the rest are references
+        SyntaxKind::NODE_PAT_ENTRY | SyntaxKind::NODE_LAMBDA => !is_first,
+        SyntaxKind::NODE_INHERIT => !parent
+            .children()
+            .any(|c| c.kind() == SyntaxKind::NODE_INHERIT_FROM),
+        _ => true,
+    }
+}
+
+/// Identifiers in `scope` that refer to a variable named `name`,
+/// ignoring shadowing
+pub fn references<'a>(
+    scope: &'a SyntaxNode,
+    name: &'a str,
+) -> impl Iterator<Item = SyntaxNode> + 'a {
+    scope
+        .descendants()
+        .filter(|n| n.kind() == SyntaxKind::NODE_IDENT)
+        .filter(move |n| n.text() == name && is_reference(n))
+}
+
+/// The value of `true` and `false`, assuming nobody rebinds them
+pub fn bool_literal(node: &SyntaxNode) -> Option<bool> {
+    match Ident::cast(node.clone())?.as_str() {
+        "true" => Some(true),
+        "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Name of an attribute in a key, for identifiers and strings without
+/// interpolations: `foo` and `"foo"` both give `foo`
+pub fn attr_name(node: &SyntaxNode) -> Option<String> {
+    if let Some(ident) = Ident::cast(node.clone()) {
+        return Some(ident.as_str().to_owned());
+    }
+    match Str::cast(node.clone())?.parts().as_slice() {
+        [StrPart::Literal(s)] => Some(s.clone()),
+        _ => None,
+    }
+}
+
+/// Name of the first attribute of the key of `kv`: `a` for `a.b = 1;`
+pub fn first_key(kv: &KeyValue) -> Option<String> {
+    attr_name(&kv.key()?.path().next()?)
+}
+
+/// Name of a function as it is called: `f` for `f` and `lib.f`
+pub fn function_name(function: &SyntaxNode) -> Option<String> {
+    match Select::cast(function.clone()) {
+        Some(select) => Some(select.index()?.to_string()),
+        None => Ident::cast(function.clone()).map(|i| i.as_str().to_owned()),
+    }
+}
+
+/// The function of a call and its arguments: `f` and `[ a b ]` for `f a b`
+pub fn call(node: &SyntaxNode) -> (SyntaxNode, Vec<SyntaxNode>) {
+    let mut function = node.clone();
+    let mut args = Vec::new();
+    while let Some(apply) = Apply::cast(function.clone()) {
+        match (apply.lambda(), apply.value()) {
+            (Some(lambda), Some(value)) => {
+                args.insert(0, value);
+                function = lambda;
+            }
+            _ => break,
+        }
+    }
+    (function, args)
+}
+
+/// The arguments of a call to the builtin `name`, as `builtins.name`,
+/// `lib.name` or `name`: `[ x ]` for `(builtins.length x)`
+pub fn builtin_call(node: &SyntaxNode, name: &str) -> Option<Vec<SyntaxNode>> {
+    let (function, args) = call(&unparenthesized(node));
+    if function_name(&function).as_deref() != Some(name) {
+        return None;
+    }
+    if let Some(select) = Select::cast(function) {
+        let set = select.set()?.to_string();
+        let sublib = LIB_BUILTINS
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, sublib)| format!("lib.{}", sublib));
+        if set != "builtins" && set != "lib" && Some(set) != sublib {
+            return None;
+        }
+    }
+    Some(args)
+}
+
+/// `node` without the parentheses around it: `x` for `((x))`
+pub fn unparenthesized(node: &SyntaxNode) -> SyntaxNode {
+    let mut node = node.clone();
+    while let Some(inner) = Paren::cast(node.clone()).and_then(|p| p.inner()) {
+        node = inner;
+    }
+    node
+}
+
+/// The attributes given to a builder such as `mkDerivation`, as a set
+/// or as a function of the final attributes: `{ }` or `(finalAttrs: { })`
+pub fn derivation_attrs(argument: &SyntaxNode) -> Option<AttrSet> {
+    let mut argument = argument.clone();
+    loop {
+        argument = match argument.kind() {
+            SyntaxKind::NODE_PAREN => argument.first_child()?,
+            SyntaxKind::NODE_LAMBDA => Lambda::cast(argument)?.body()?,
+            _ => return AttrSet::cast(argument),
+        }
+    }
+}
+
+/// Whether `node` is the body of the file, of the function the file
+/// defines, or of a `let` around them
+pub fn is_top_level(node: &SyntaxNode) -> bool {
+    let mut child = node.clone();
+    for parent in node.ancestors().skip(1) {
+        let is_body = match parent.kind() {
+            SyntaxKind::NODE_ROOT | SyntaxKind::NODE_PAREN => true,
+            SyntaxKind::NODE_LAMBDA => parent.first_child().as_ref() != Some(&child),
+            SyntaxKind::NODE_LET_IN => {
+                matches!(LetIn::cast(parent.clone()).and_then(|l| l.body()), Some(b) if b == child)
+            }
+            _ => false,
+        };
+        if !is_body {
+            return false;
+        }
+        child = parent;
+    }
+    true
+}
+
+/// Whether `node` is where a path is expected: the value of `src = ...;`,
+/// or an element of `imports = [ ... ];`
+pub fn expects_path(node: &SyntaxNode) -> bool {
+    let mut node = node.clone();
+    while let Some(parent) = node.parent().filter(|p| p.kind() == SyntaxKind::NODE_PAREN) {
+        node = parent;
+    }
+    let (value, key) = match node.parent() {
+        Some(list) if list.kind() == SyntaxKind::NODE_LIST => (list, "imports"),
+        _ => (node, "src"),
+    };
+    let key_value = match value.parent().and_then(KeyValue::cast) {
+        Some(kv) => kv,
+        None => return false,
+    };
+    matches!(
+        key_value.key().and_then(|k| k.path().last()),
+        Some(last) if last.text() == key
+    ) && matches!(key_value.value(), Some(v) if v == value)
+}
+
+/// The indentation of the line `node` starts on
+pub fn indentation(node: &SyntaxNode) -> String {
+    let root = node.ancestors().last().unwrap_or_else(|| node.clone());
+    let before = &root.to_string()[..usize::from(node.text_range().start())];
+    let line = before.rsplit('\n').next().unwrap_or_default();
+    line.chars().take_while(|c| c.is_whitespace()).collect()
+}
+
+/// Number of single character edits between `a` and `b`, to suggest
+/// corrections for typos
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut row = (0..=b.len()).collect::<Vec<_>>();
+    for (i, ca) in a.chars().enumerate() {
+        let mut prev = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let cur = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                prev
+            } else {
+                1 + prev.min(cur).min(row[j])
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+/// Whether `node` is known to be a string: a string literal, a
+/// concatenation with one, or a call to a function returning strings
+pub fn is_string(node: &SyntaxNode) -> bool {
+    match node.kind() {
+        SyntaxKind::NODE_STRING => true,
+        SyntaxKind::NODE_PAREN => matches!(node.first_child(), Some(c) if is_string(&c)),
+        SyntaxKind::NODE_BIN_OP => match BinOp::cast(node.clone()) {
+            Some(b) if b.operator() == Some(BinOpKind::Add) => {
+                // `"a" + x` is a string, `x + "a"` is a path for a path `x`
+                matches!(b.lhs(), Some(lhs) if is_string(&lhs))
+            }
+            _ => false,
+        },
+        SyntaxKind::NODE_APPLY => matches!(
+            function_name(&call(node).0),
+            Some(name) if STRING_FUNCTIONS.contains(&name.as_str())
+        ),
+        _ => false,
+    }
+}
+
+/// The text of a string without interpolations
+pub fn string_literal(node: &SyntaxNode) -> Option<String> {
+    match Str::cast(node.clone())?.parts().as_slice() {
+        [StrPart::Literal(s)] => Some(s.clone()),
+        [] => Some(String::new()),
+        _ => None,
+    }
+}
+
+/// The expression of a string made of a single interpolation: `x` in
+/// `"${x}"` and `''${x}''`
+pub fn lone_interpolation(string: &SyntaxNode) -> Option<SyntaxNode> {
+    let mut children = string.children();
+    let interpolation = children.next()?;
+    let only = children.next().is_none()
+        && !string
+            .children_with_tokens()
+            .any(|c| c.kind() == SyntaxKind::TOKEN_STRING_CONTENT);
+    if only && interpolation.kind() == SyntaxKind::NODE_STRING_INTERPOL {
+        interpolation.first_child()
+    } else {
+        None
+    }
+}
+
+/// `key` as an attribute name, quoted unless it is an identifier
+pub fn key_text(key: &str) -> String {
+    let mut chars = key.chars();
+    let is_ident = matches!(chars.next(), Some(c) if c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '\'' | '-'))
+        && !KEYWORDS.contains(&key);
+    if is_ident {
+        key.to_owned()
+    } else {
+        let escaped = key
+            .replace('\\', "\\\\")
+            .replace('"', "\\\"")
+            .replace("${", "\\${");
+        format!("\"{}\"", escaped)
+    }
+}
@@ -2,6 +2,68 @@ use proc_macro2::TokenStream as TokenStream2;
 use quote::quote;
 use syn::{ItemStruct, Lit, Meta, MetaNameValue};
 
+#[derive(Default)]
+struct Sections {
+    summary: Vec<String>,
+    rationale: Vec<String>,
+    examples: Vec<Vec<String>>,
+}
+
+enum Heading {
+    Summary,
+    Rationale,
+    Example,
+    Other,
+}
+
+// split a lint's doc comment along its `## ` headings, code blocks under
+// `## Example` are collected in order: first the bad one, then the good one
+fn sections(explain: &str) -> Sections {
+    let mut sections = Sections::default();
+    let mut heading = Heading::Other;
+    let mut in_code = false;
+    for line in explain.lines() {
+        if let Some(title) = line.strip_prefix("## ").filter(|_| !in_code) {
+            heading = match title.trim().to_ascii_lowercase().as_str() {
+                "what it does" => Heading::Summary,
+                "why is this bad?" => Heading::Rationale,
+                "example" | "examples" => Heading::Example,
+                _ => Heading::Other,
+            };
+            continue;
+        }
+        match heading {
+            Heading::Summary => sections.summary.push(line.to_owned()),
+            Heading::Rationale => sections.rationale.push(line.to_owned()),
+            Heading::Example if line.trim_start().starts_with("```") => {
+                in_code = !in_code;
+                if in_code {
+                    sections.examples.push(Vec::new());
+                }
+            }
+            Heading::Example if in_code => {
+                sections.examples.last_mut().unwrap().push(line.to_owned())
+            }
+            _ => (),
+        }
+    }
+    sections
+}
+
+fn paragraph(lines: &[String]) -> String {
+    lines.join("\n").trim().to_owned()
+}
+
+fn example(examples: &[Vec<String>], idx: usize) -> TokenStream2 {
+    match examples.get(idx) {
+        Some(lines) => {
+            let code = lines.join("\n");
+            quote! { Some(#code) }
+        }
+        None => quote! { None },
+    }
+}
+
 pub fn generate_explain_impl(struct_item: &ItemStruct) -> TokenStream2 {
     let struct_name = &struct_item.ident;
     let explain = struct_item
@@ -18,11 +80,38 @@ pub fn generate_explain_impl(struct_item: &ItemStruct) -> TokenStream2 {
         .map(|s| s.strip_prefix(' ').unwrap_or(&s).to_owned())
         .collect::<Vec<_>>()
         .join("\n");
+    let sections = sections(&explain);
+    let summary = paragraph(&sections.summary);
+    let rationale = paragraph(&sections.rationale);
+    let bad_example = example(&sections.examples, 0);
+    let good_example = example(&sections.examples, 1);
     quote! {
         impl crate::Explain for #struct_name {
             fn explanation(&self) -> &'static str {
                 #explain
             }
+            fn sections(&self) -> crate::Explanation {
+                crate::Explanation {
+                    summary: #summary,
+                    rationale: #rationale,
+                    bad_example: #bad_example,
+                    good_example: #good_example,
+                }
+            }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_sections() {
+        let doc = "## What it does\nChecks for `a`.\n\n## Why is this bad?\nIt is bad.\n\n## Example\n\n```nix\na\n```\n\nTry:\n\n```nix\n# comment\nb\n```";
+        let sections = sections(doc);
+        assert_eq!(paragraph(&sections.summary), "Checks for `a`.");
+        assert_eq!(paragraph(&sections.rationale), "It is bad.");
+        assert_eq!(sections.examples, vec![vec!["a"], vec!["# comment", "b"]]);
+    }
+}
@@ -41,12 +41,20 @@ pub struct LintMeta<'μ> {
     name: &'μ Lit,
     note: &'μ Lit,
     code: &'μ Lit,
+    severity: Option<&'μ Expr>,
+    priority: Option<&'μ Lit>,
+    docs_url: Option<&'μ Lit>,
+    min_nix_version: Option<&'μ Lit>,
+    max_nix_version: Option<&'μ Lit>,
+    modes: Option<&'μ ExprArray>,
     match_with: MatchWith<'μ>,
 }
 
 enum MatchWith<'π> {
     Path(&'π Path),
     Array(&'π ExprArray),
+    // project lints, see `Rule::check_project`
+    Nothing,
 }
 
 fn extract<'λ>(id: &str, raw: &'λ RawLintMeta) -> &'λ Expr {
@@ -55,6 +63,10 @@ fn extract<'λ>(id: &str, raw: &'λ RawLintMeta) -> &'λ Expr {
         .unwrap_or_else(|| panic!("`{}` not present", id))
 }
 
+fn extract_opt<'λ>(id: &str, raw: &'λ RawLintMeta) -> Option<&'λ Expr> {
+    raw.0.get(&format_ident!("{}", id))
+}
+
 fn as_lit(e: &Expr) -> &Lit {
     match e {
         Expr::Lit(l) => &l.lit,
@@ -67,16 +79,31 @@ impl<'μ> LintMeta<'μ> {
         let name = as_lit(extract("name", raw));
         let note = as_lit(extract("note", raw));
         let code = as_lit(extract("code", raw));
-        let match_with_expr = extract("match_with", raw);
-        let match_with = match match_with_expr {
-            Expr::Path(p) => MatchWith::Path(&p.path),
-            Expr::Array(a) => MatchWith::Array(a),
-            _ => panic!("`match_with` is neither a path nor an array"),
+        let severity = extract_opt("severity", raw);
+        let priority = extract_opt("priority", raw).map(as_lit);
+        let docs_url = extract_opt("docs_url", raw).map(as_lit);
+        let min_nix_version = extract_opt("min_nix_version", raw).map(as_lit);
+        let max_nix_version = extract_opt("max_nix_version", raw).map(as_lit);
+        let modes = extract_opt("modes", raw).map(|e| match e {
+            Expr::Array(a) => a,
+            _ => panic!("`modes` is not an array"),
+        });
+        let match_with = match extract_opt("match_with", raw) {
+            Some(Expr::Path(p)) => MatchWith::Path(&p.path),
+            Some(Expr::Array(a)) => MatchWith::Array(a),
+            Some(_) => panic!("`match_with` is neither a path nor an array"),
+            None => MatchWith::Nothing,
         };
         Self {
             name,
             note,
             code,
+            severity,
+            priority,
+            docs_url,
+            min_nix_version,
+            max_nix_version,
+            modes,
             match_with,
         }
     }
@@ -108,6 +135,89 @@ impl<'μ> LintMeta<'μ> {
         }
     }
 
+    fn generate_severity_fn(&self) -> TokenStream2 {
+        match self.severity {
+            Some(severity) => quote! {
+                fn severity(&self) -> crate::Severity {
+                    #severity
+                }
+            },
+            None => quote! {
+                fn severity(&self) -> crate::Severity {
+                    crate::Severity::default()
+                }
+            },
+        }
+    }
+
+    fn generate_priority_fn(&self) -> TokenStream2 {
+        let priority = match self.priority {
+            Some(p) => quote! { #p },
+            None => quote! { 0 },
+        };
+        quote! {
+            fn priority(&self) -> u8 {
+                #priority
+            }
+        }
+    }
+
+    fn generate_docs_url_fn(&self) -> TokenStream2 {
+        let docs_url = match self.docs_url {
+            Some(url) => quote! { Some(#url) },
+            None => quote! { None },
+        };
+        quote! {
+            fn docs_url(&self) -> Option<&'static str> {
+                #docs_url
+            }
+        }
+    }
+
+    fn generate_nix_version_fn(fn_name: &str, version: Option<&Lit>) -> TokenStream2 {
+        let fn_name = format_ident!("{}", fn_name);
+        let body = match version {
+            Some(Lit::Str(v)) => {
+                let parts = v
+                    .value()
+                    .split('.')
+                    .map(|p| {
+                        p.parse::<u16>()
+                            .unwrap_or_else(|_| panic!("`{}` is not a valid version", v.value()))
+                    })
+                    .collect::<Vec<_>>();
+                match parts.as_slice() {
+                    [major, minor] => quote! {
+                        Some(crate::session::Version::new(#major, #minor, None))
+                    },
+                    [major, minor, patch] => quote! {
+                        Some(crate::session::Version::new(#major, #minor, Some(#patch)))
+                    },
+                    _ => panic!("`{}` is not a valid version", v.value()),
+                }
+            }
+            Some(_) => panic!("`{}` is not a string literal", fn_name),
+            None => quote! { None },
+        };
+        quote! {
+            fn #fn_name(&self) -> Option<crate::session::Version> {
+                #body
+            }
+        }
+    }
+
+    fn generate_modes_fn(&self) -> TokenStream2 {
+        let modes = match self.modes {
+            Some(a) => quote! { #a },
+            None => quote! { [] },
+        };
+        quote! {
+            fn modes(&self) -> &'static [crate::session::Mode] {
+                &#modes
+            }
+        }
+    }
+
     fn generate_match_with_fn(&self) -> TokenStream2 {
         match self.match_with {
             MatchWith::Path(p) => {
@@ -124,6 +234,13 @@ impl<'μ> LintMeta<'μ> {
                     }
                 }
             }
+            MatchWith::Nothing => {
+                quote! {
+                    fn match_with(&self, _with: &rnix::SyntaxKind) -> bool {
+                        false
+                    }
+                }
+            }
         }
     }
 
@@ -143,13 +260,26 @@ impl<'μ> LintMeta<'μ> {
                     }
                 }
             }
+            MatchWith::Nothing => {
+                quote! {
+                    fn match_kind(&self) -> Vec<rnix::SyntaxKind> {
+                        Vec::new()
+                    }
+                }
+            }
         }
     }
 
     fn generate_report_fn(&self) -> TokenStream2 {
         quote! {
             fn report(&self) -> crate::Report {
-                crate::Report::new(self.note(), self.code())
+                let report = crate::Report::new(self.note(), self.code())
+                    .severity(self.severity())
+                    .priority(self.priority());
+                match self.docs_url() {
+                    Some(docs_url) => report.docs_url(docs_url),
+                    None => report,
+                }
             }
         }
     }
@@ -160,6 +290,14 @@ pub fn generate_meta_impl(struct_name: &Ident, meta: &RawLintMeta) -> TokenStrea
     let name_fn = not_raw.generate_name_fn();
     let note_fn = not_raw.generate_note_fn();
     let code_fn = not_raw.generate_code_fn();
+    let severity_fn = not_raw.generate_severity_fn();
+    let priority_fn = not_raw.generate_priority_fn();
+    let docs_url_fn = not_raw.generate_docs_url_fn();
+    let min_nix_version_fn =
+        LintMeta::generate_nix_version_fn("min_nix_version", not_raw.min_nix_version);
+    let max_nix_version_fn =
+        LintMeta::generate_nix_version_fn("max_nix_version", not_raw.max_nix_version);
+    let modes_fn = not_raw.generate_modes_fn();
     let match_with_fn = not_raw.generate_match_with_fn();
     let match_kind = not_raw.generate_match_kind_fn();
     let report_fn = not_raw.generate_report_fn();
@@ -169,6 +307,12 @@ pub fn generate_meta_impl(struct_name: &Ident, meta: &RawLintMeta) -> TokenStrea
             #name_fn
             #note_fn
             #code_fn
+            #severity_fn
+            #priority_fn
+            #docs_url_fn
+            #min_nix_version_fn
+            #max_nix_version_fn
+            #modes_fn
             #match_with_fn
             #match_kind
             #report_fn
@@ -1,5 +1,5 @@
 use std::{
-    collections::HashMap,
+    collections::BTreeMap,
     default::Default,
     path::{Path, PathBuf},
 };
@@ -30,10 +30,44 @@ impl Interner {
     }
 }
 
+const BOM: &[u8] = b"\xef\xbb\xbf";
+
+/// How the contents of a file were decoded from the bytes read from disk
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Encoding {
+    /// The file starts with a byte order mark, left out of the contents
+    pub bom: bool,
+    /// The file is not valid UTF-8, invalid sequences were replaced by U+FFFD
+    pub lossy: bool,
+}
+
+impl Encoding {
+    pub fn decode(bytes: &[u8]) -> (String, Self) {
+        let (bytes, bom) = match bytes.strip_prefix(BOM) {
+            Some(rest) => (rest, true),
+            None => (bytes, false),
+        };
+        match std::str::from_utf8(bytes) {
+            Ok(src) => (src.to_owned(), Self { bom, lossy: false }),
+            Err(_) => {
+                let src = String::from_utf8_lossy(bytes).into_owned();
+                (src, Self { bom, lossy: true })
+            }
+        }
+    }
+    /// Bytes to write back to a file with this encoding
+    pub fn encode(&self, src: &str) -> Vec<u8> {
+        let bom = if self.bom { BOM } else { &[] };
+        [bom, src.as_bytes()].concat()
+    }
+}
+
 #[derive(Default)]
 pub struct ReadOnlyVfs {
     interner: Interner,
-    data: HashMap<FileId, Vec<u8>>,
+    // by `FileId`, to go over files in the order they were added
+    data: BTreeMap<FileId, Vec<u8>>,
+    encodings: BTreeMap<FileId, Encoding>,
 }
 
 impl ReadOnlyVfs {
@@ -64,18 +98,31 @@ impl ReadOnlyVfs {
         let file_id = self.alloc_file_id(path);
         self.data.insert(file_id, contents.to_owned());
     }
-    pub fn iter(&self) -> impl Iterator<Item = VfsEntry> {
+    /// Decode the bytes of a file as read from disk, see `Encoding`
+    pub fn read_file<P: AsRef<Path>>(&mut self, path: P, bytes: &[u8]) -> Encoding {
+        let (src, encoding) = Encoding::decode(bytes);
+        let file_id = self.alloc_file_id(&path);
+        self.set_file_contents(path, src.as_bytes());
+        self.encodings.insert(file_id, encoding);
+        encoding
+    }
+    pub fn encoding(&self, file_id: FileId) -> Encoding {
+        self.encodings.get(&file_id).copied().unwrap_or_default()
+    }
+    pub fn iter(&self) -> impl Iterator<Item = VfsEntry<'_>> {
         self.data.keys().map(move |file_id| VfsEntry {
             file_id: *file_id,
             file_path: self.file_path(*file_id),
             contents: self.get_str(*file_id),
+            encoding: self.encoding(*file_id),
         })
     }
-    pub fn par_iter(&self) -> impl ParallelIterator<Item = VfsEntry> {
+    pub fn par_iter(&self) -> impl ParallelIterator<Item = VfsEntry<'_>> {
         self.data.par_iter().map(move |(file_id, _)| VfsEntry {
             file_id: *file_id,
             file_path: self.file_path(*file_id),
             contents: self.get_str(*file_id),
+            encoding: self.encoding(*file_id),
         })
     }
 }
@@ -84,6 +131,7 @@ pub struct VfsEntry<'ρ> {
     pub file_id: FileId,
     pub file_path: &'ρ Path,
     pub contents: &'ρ str,
+    pub encoding: Encoding,
 }
 
 #[cfg(test)]
@@ -99,4 +147,37 @@ mod test {
         vfs.set_file_contents(f1, &data);
         assert_eq!(vfs.get(id1), &data);
     }
+
+    #[test]
+    fn encodings() {
+        let mut vfs = ReadOnlyVfs::default();
+        let id = vfs.alloc_file_id("bom.nix");
+        let bom = vfs.read_file("bom.nix", b"\xef\xbb\xbf{ }");
+        assert_eq!(
+            bom,
+            Encoding {
+                bom: true,
+                lossy: false
+            }
+        );
+        assert_eq!(vfs.get_str(id), "{ }");
+        assert_eq!(bom.encode("{ }"), b"\xef\xbb\xbf{ }");
+
+        let id = vfs.alloc_file_id("latin1.nix");
+        let latin1 = vfs.read_file("latin1.nix", b"\"caf\xe9\"");
+        assert!(latin1.lossy);
+        assert_eq!(vfs.get_str(id), "\"caf\u{fffd}\"");
+    }
+
+    #[test]
+    fn iter_in_insertion_order() {
+        let mut vfs = ReadOnlyVfs::default();
+        let paths = ["a.nix", "c/d.nix", "b.nix", "c/a.nix", "default.nix"];
+        for path in paths.iter() {
+            vfs.set_file_contents(path, b"{ }");
+        }
+        let iterated = vfs.iter().map(|e| e.file_path).collect::<Vec<_>>();
+        let expected = paths.iter().map(Path::new).collect::<Vec<_>>();
+        assert_eq!(iterated, expected);
+    }
 }